@@ -0,0 +1,130 @@
+use aoc_common::runner::{self, Outcome, Run};
+use aoc_common::{answer, normalise, ParseError};
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: aoc_common::alloc::CountingAllocator = aoc_common::alloc::CountingAllocator;
+
+// the answers to the real inputs, as `day-05 part1: <answer>`. empty ones aren't checked
+const ANSWERS: &str = include_str!("../../../answers.txt");
+
+// each part runs in a child process, see `runner::isolated`, so when one fails to parse its
+// input, gives the wrong answer, panics, crashes or runs past the timeout it's reported and
+// the rest carry on
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+// set in a child, to the label of the one part it runs
+static PART: OnceLock<String> = OnceLock::new();
+
+fn run_part<T: Display + 'static>(
+    label: &str,
+    process: fn(&str) -> Result<T, ParseError>,
+    input: &str,
+    failures: &mut Vec<String>,
+) -> u128 {
+    let process = |input: &str| {
+        process(input)
+            .map(|found| found.to_string())
+            .map_err(|err| err.to_string())
+    };
+    if let Some(part) = PART.get() {
+        if part == label {
+            runner::serve(process, input);
+        }
+        return 0;
+    }
+    let timeout = TIMEOUT.get().copied().unwrap_or(DEFAULT_TIMEOUT);
+    let Run {
+        outcome,
+        duration,
+        allocs,
+    } = runner::isolated(label, timeout);
+    // "Day 05 Part1" is kept as "day-05 part1"
+    let key = label.to_lowercase().replacen(' ', "-", 1);
+    let failure = match outcome {
+        Outcome::Ok(found) => match answer(ANSWERS, &key) {
+            Some(expected) if expected != found => Some(format!(
+                "wrong answer, expected {expected} but found {found}"
+            )),
+            _ => None,
+        },
+        failed => Some(failed.to_string()),
+    };
+    match failure {
+        None if cfg!(feature = "count-alloc") => println!("{label}: {duration:?} ({allocs})"),
+        None => println!("{label}: {duration:?}"),
+        Some(err) => {
+            println!("{label}: {err}");
+            failures.push(format!("{label}: {err}"));
+        }
+    }
+    duration.as_nanos()
+}
+
+const USAGE: &str = "usage: all-days [--timeout <seconds>]
+  --timeout    how long a part gets before it's given up on, 30 seconds by default";
+
+fn main() -> Result<(), ()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(label) = runner::part_label(&args) {
+        PART.get_or_init(|| label.to_string());
+        return all_days();
+    }
+    match args.as_slice() {
+        [] => {}
+        [flag, value] if flag == "--timeout" => {
+            let timeout = runner::parse_timeout(value).map_err(|err| eprintln!("{err}"))?;
+            TIMEOUT.get_or_init(|| timeout);
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return Err(());
+        }
+    }
+    all_days()
+}
+
+#[tracing::instrument]
+fn all_days() -> Result<(), ()> {
+    let mut total_time_in_nanos = 0;
+    let mut failures = Vec::new();
+
+    // day 1
+    let file = &normalise(include_str!("../../../day-01/input.txt"));
+    total_time_in_nanos += run_part("Day 01 Part1", day_01::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 01 Part2", day_01::part2::process, file, &mut failures);
+    println!();
+
+    // day 2
+    let file = &normalise(include_str!("../../../day-02/input.txt"));
+    total_time_in_nanos += run_part("Day 02 Part1", day_02::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 02 Part2", day_02::part2::process, file, &mut failures);
+    println!();
+
+    // day 3
+    let file = &normalise(include_str!("../../../day-03/input.txt"));
+    total_time_in_nanos += run_part("Day 03 Part1", day_03::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 03 Part2", day_03::part2::process, file, &mut failures);
+    println!();
+
+    // day 4
+    let file = &normalise(include_str!("../../../day-04/input.txt"));
+    total_time_in_nanos += run_part("Day 04 Part1", day_04::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 04 Part2", day_04::part2::process, file, &mut failures);
+    println!();
+
+    let total_duration = Duration::from_nanos(total_time_in_nanos as u64);
+
+    println!("Total time: {total_duration:?}");
+
+    if !failures.is_empty() {
+        println!();
+        println!("{} part(s) failed:", failures.len());
+        failures.iter().for_each(|failure| println!("{failure}"));
+        return Err(());
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use aoc_common::parse::number;
+use aoc_common::{blocks, ParseError};
+
+// the calories each elf carries, one blank line separated block per elf
+pub fn calories(input: &str) -> Result<Vec<u32>, ParseError> {
+    blocks(input)
+        .map(|elf| elf.lines().map(number::<u32>).sum())
+        .collect::<Result<Vec<u32>, ParseError>>()
+        .map_err(|err| err.locate(input))
+}
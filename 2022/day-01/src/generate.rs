@@ -0,0 +1,39 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of elves
+pub fn generate(seed: u64, size: usize) -> Generated<u32> {
+    let mut rng = Rng::with_seed(seed);
+    let mut elves: Vec<u32> = Vec::with_capacity(size);
+    let mut blocks: Vec<String> = Vec::with_capacity(size);
+
+    for _ in 0..size {
+        let items: Vec<u32> = (0..rng.usize(1..15))
+            .map(|_| rng.u32(1000..60_000))
+            .collect();
+        elves.push(items.iter().sum());
+        let lines: Vec<String> = items.iter().map(u32::to_string).collect();
+        blocks.push(lines.join("\n"));
+    }
+
+    elves.sort_unstable_by(|a, b| b.cmp(a));
+    Generated {
+        input: blocks.join("\n\n") + "\n",
+        part1: elves.first().copied(),
+        part2: Some(elves.iter().take(3).sum()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod part1;
+pub mod part2;
+
+pub mod elves;
+pub mod generate;
@@ -0,0 +1,34 @@
+use crate::elves::calories;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    calories(input)?
+        .into_iter()
+        .max()
+        .ok_or_else(|| ParseError::expected(input, "at least one elf"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+        assert_eq!(Ok(24000), process(input));
+    }
+}
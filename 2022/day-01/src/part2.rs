@@ -0,0 +1,33 @@
+use crate::elves::calories;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    let mut calories = calories(input)?;
+    calories.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(calories.iter().take(3).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+        assert_eq!(Ok(45000), process(input));
+    }
+}
@@ -0,0 +1,114 @@
+use aoc_common::ParseError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl FromStr for Outcome {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Outcome, Self::Err> {
+        match s {
+            "X" => Ok(Outcome::Lose),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(ParseError::expected(s, "X, Y or Z")),
+        }
+    }
+}
+
+impl Outcome {
+    pub fn score(&self) -> usize {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Lose => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Hand {
+    pub fn score(&self) -> usize {
+        match self {
+            Hand::Rock => 1,
+            Hand::Paper => 2,
+            Hand::Scissors => 3,
+        }
+    }
+
+    fn beats(&self) -> Hand {
+        match self {
+            Hand::Rock => Hand::Scissors,
+            Hand::Paper => Hand::Rock,
+            Hand::Scissors => Hand::Paper,
+        }
+    }
+
+    pub fn compare(&self, other: Hand) -> Outcome {
+        if *self == other {
+            Outcome::Draw
+        } else if self.beats() == other {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    // the hand to play against this one to get the outcome
+    pub fn against(&self, outcome: Outcome) -> Hand {
+        match outcome {
+            Outcome::Draw => *self,
+            Outcome::Lose => self.beats(),
+            Outcome::Win => self.beats().beats(),
+        }
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Hand, Self::Err> {
+        match s {
+            "A" | "X" => Ok(Hand::Rock),
+            "B" | "Y" => Ok(Hand::Paper),
+            "C" | "Z" => Ok(Hand::Scissors),
+            _ => Err(ParseError::expected(s, "A, B, C, X, Y or Z")),
+        }
+    }
+}
+
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub hand: Hand,
+}
+
+impl GameResult {
+    pub fn score(&self) -> usize {
+        self.outcome.score() + self.hand.score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against() {
+        for them in [Hand::Rock, Hand::Paper, Hand::Scissors] {
+            for outcome in [Outcome::Win, Outcome::Lose, Outcome::Draw] {
+                assert_eq!(outcome, them.against(outcome).compare(them));
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of rounds
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size {
+        // rock, paper and scissors are 0, 1 and 2, each beating the one before it
+        let (them, second) = (rng.usize(..3), rng.usize(..3));
+        input.push_str(&format!(
+            "{} {}\n",
+            (b'A' + them as u8) as char,
+            (b'X' + second as u8) as char
+        ));
+
+        // as a hand, then as lose, draw or win
+        let outcome = (second + 4 - them) % 3;
+        part1 += second + 1 + 3 * outcome;
+        let hand = (them + second + 2) % 3;
+        part2 += hand + 1 + 3 * second;
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::game::{GameResult, Hand};
+use aoc_common::parse::split_once;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    input
+        .lines()
+        .map(|line| Ok(parse_game(line)?.score()))
+        .sum::<Result<usize, ParseError>>()
+        .map_err(|err| err.locate(input))
+}
+
+// the second column is the hand we play
+fn parse_game(line: &str) -> Result<GameResult, ParseError> {
+    let (them, us) = split_once(line, " ")?;
+    let their_hand = them.parse::<Hand>()?;
+    let our_hand = us.parse::<Hand>()?;
+    Ok(GameResult {
+        outcome: our_hand.compare(their_hand),
+        hand: our_hand,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "A Y
+B X
+C Z";
+        assert_eq!(Ok(15), process(input));
+    }
+}
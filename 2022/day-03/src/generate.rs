@@ -0,0 +1,71 @@
+use aoc_common::generate::{Generated, Rng};
+
+const ITEMS: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// `size` is the number of groups of three rucksacks
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size {
+        // the items other than the badge are shared out so no two rucksacks in the group
+        // have any in common
+        let mut items: Vec<usize> = (0..ITEMS.len()).collect();
+        rng.shuffle(&mut items);
+        let badge = items.pop().expect("There are 52 items");
+        part2 += badge + 1;
+
+        for pool in items.chunks(items.len() / 3).take(3) {
+            let shared = match rng.bool() {
+                true => badge,
+                false => pool[0],
+            };
+            part1 += shared + 1;
+            // the rest of the pool is split between the compartments, with the badge in
+            // one of them when it isn't the shared item
+            let (left, right) = pool[1..].split_at(pool.len() / 2);
+            let mut halves = [left.to_vec(), right.to_vec()];
+            if shared != badge {
+                halves[rng.usize(..2)].push(badge);
+            }
+
+            let len = rng.usize(4..12);
+            let mut rucksack = String::new();
+            for half in &mut halves {
+                let mut compartment = vec![shared];
+                if half.contains(&badge) {
+                    compartment.push(badge);
+                }
+                while compartment.len() < len {
+                    compartment.push(half[rng.usize(..half.len())]);
+                }
+                rng.shuffle(&mut compartment);
+                rucksack.extend(compartment.iter().map(|&item| ITEMS[item] as char));
+            }
+            input.push_str(&rucksack);
+            input.push('\n');
+        }
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::ParseError;
+use std::str::FromStr;
+
+// which of the 52 item types are in a rucksack, a-z then A-Z
+#[derive(Debug, Clone, Copy)]
+pub struct Letters {
+    letters: [bool; 52],
+}
+
+impl FromStr for Letters {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Letters, Self::Err> {
+        s.bytes().enumerate().try_fold(
+            Letters {
+                letters: [false; 52],
+            },
+            |mut acc, (i, letter)| {
+                let index = match letter {
+                    b'a'..=b'z' => letter - b'a',
+                    b'A'..=b'Z' => letter - b'A' + 26,
+                    _ => return Err(ParseError::expected(&s[i..], "a letter")),
+                };
+                acc.letters[index as usize] = true;
+                Ok(acc)
+            },
+        )
+    }
+}
+
+impl Letters {
+    pub fn common(&self, other: &Letters) -> Letters {
+        let mut letters = self.letters;
+        for (letter, other) in letters.iter_mut().zip(other.letters) {
+            *letter &= other;
+        }
+        Letters { letters }
+    }
+
+    // the priority of the last item type there is, from 1 for a to 52 for Z
+    pub fn priority(&self) -> usize {
+        self.letters
+            .iter()
+            .rposition(|&letter| letter)
+            .map_or(0, |i| i + 1)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::letters::Letters;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            if line.len() % 2 != 0 {
+                return Err(ParseError::expected(line, "an even number of items"));
+            }
+            let (first, second) = line.split_at(line.len() / 2);
+            let first = first.parse::<Letters>()?;
+            let second = second.parse::<Letters>()?;
+            Ok(first.common(&second).priority())
+        })
+        .sum::<Result<usize, ParseError>>()
+        .map_err(|err| err.locate(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+        assert_eq!(Ok(157), process(input));
+    }
+}
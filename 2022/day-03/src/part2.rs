@@ -0,0 +1,37 @@
+use crate::letters::Letters;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    input
+        .lines()
+        .collect::<Vec<&str>>()
+        .chunks(3)
+        .map(|group| {
+            let [first, second, third] = group else {
+                return Err(ParseError::expected(group[0], "groups of three rucksacks"));
+            };
+            let first = first.parse::<Letters>()?;
+            let second = second.parse::<Letters>()?;
+            let third = third.parse::<Letters>()?;
+            Ok(first.common(&second).common(&third).priority())
+        })
+        .sum::<Result<usize, ParseError>>()
+        .map_err(|err| err.locate(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+        assert_eq!(Ok(70), process(input));
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of pairs
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size {
+        let mut section = || {
+            let start = rng.usize(1..90);
+            (start, start + rng.usize(..10))
+        };
+        let (left, right) = (section(), section());
+        input.push_str(&format!("{}-{},{}-{}\n", left.0, left.1, right.0, right.1));
+
+        let covers = |a: (usize, usize), b: (usize, usize)| a.0 <= b.0 && b.1 <= a.1;
+        if covers(left, right) || covers(right, left) {
+            part1 += 1;
+        }
+        if left.0 <= right.1 && right.0 <= left.1 {
+            part2 += 1;
+        }
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::tasks::{parse_tasks, Tasks};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_tasks(input)?
+        .iter()
+        .filter(|pair| Tasks::contains(pair))
+        .count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+        assert_eq!(Ok(2), process(input));
+    }
+}
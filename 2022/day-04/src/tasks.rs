@@ -0,0 +1,58 @@
+use aoc_common::parse::{number, split_once};
+use aoc_common::ParseError;
+use std::str::FromStr;
+
+// the sections each elf in a pair is assigned
+pub struct Tasks {
+    left: Task,
+    right: Task,
+}
+
+struct Task {
+    start: usize,
+    end: usize,
+}
+
+impl FromStr for Tasks {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = split_once(s, ",")?;
+        Ok(Tasks {
+            left: left.parse()?,
+            right: right.parse()?,
+        })
+    }
+}
+
+impl FromStr for Task {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = split_once(s, "-")?;
+        Ok(Task {
+            start: number(start)?,
+            end: number(end)?,
+        })
+    }
+}
+
+impl Tasks {
+    // one of the pair covers all of the other's sections
+    pub fn contains(&self) -> bool {
+        self.left.start <= self.right.start && self.right.end <= self.left.end
+            || self.right.start <= self.left.start && self.left.end <= self.right.end
+    }
+
+    pub fn disjoint(&self) -> bool {
+        self.left.end < self.right.start || self.right.end < self.left.start
+    }
+}
+
+pub fn parse_tasks(input: &str) -> Result<Vec<Tasks>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.parse::<Tasks>())
+        .collect::<Result<Vec<Tasks>, ParseError>>()
+        .map_err(|err| err.locate(input))
+}
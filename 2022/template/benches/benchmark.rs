@@ -0,0 +1,31 @@
+use aoc_common::generate::bench_input;
+use aoc_common::normalise;
+use {{crate_name}}::*;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+// the example stands in for the real input until the day has a generator
+fn input() -> String {
+    bench_input(env!("CARGO_MANIFEST_DIR"), || {
+        normalise(include_str!("../examples/example1.txt")).into_owned()
+    })
+}
+
+#[divan::bench]
+fn part1(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1::process(divan::black_box(&input)).expect("Valid input"));
+}
+
+#[divan::bench]
+fn part2(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2::process(divan::black_box(&input)).expect("Valid input"));
+}
@@ -0,0 +1,10 @@
+use aoc_common::{read_input, ParseError};
+use {{crate_name}}::part1::process;
+
+#[tracing::instrument]
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = process(&file)?;
+    println!("{}", result);
+    Ok(())
+}
@@ -1,313 +1,341 @@
+use aoc_common::runner::{self, Outcome, Run};
+use aoc_common::{answer, normalise, ParseError};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use std::time::Duration;
+use tracing_chrome::{ChromeLayerBuilder, EventOrSpan, FlushGuard};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+
+const USAGE: &str =
+    "usage: all-days [--trace pretty | --trace chrome [--trace-file <file>]] [--timeout <seconds>]
+  --trace pretty    log each span to stderr as it closes, with its busy and idle time
+  --trace chrome    write the spans to a trace file for chrome://tracing or ui.perfetto.dev
+  --trace-file      where the chrome trace goes, trace.json by default
+  --timeout         how long a part gets before it's given up on, 30 seconds by default";
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: aoc_common::alloc::CountingAllocator = aoc_common::alloc::CountingAllocator;
+
+// the answers to the real inputs, as `day-05 part1: <answer>`. empty ones aren't checked
+const ANSWERS: &str = include_str!("../../../answers.txt");
+
+// each part runs in a child process, see `runner::isolated`, so when one fails to parse its
+// input, gives the wrong answer, panics, crashes or runs past the timeout it's reported and
+// the rest carry on
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+// set in a child, to the label of the one part it runs
+static PART: OnceLock<String> = OnceLock::new();
+// spans can only be traced in this process, so when they are the parts run here instead
+static TRACED: AtomicBool = AtomicBool::new(false);
+
+fn run_part<T: Display + 'static>(
+    label: &str,
+    process: fn(&str) -> Result<T, ParseError>,
+    input: &str,
+    failures: &mut Vec<String>,
+) -> u128 {
+    let process = |input: &str| {
+        process(input)
+            .map(|found| found.to_string())
+            .map_err(|err| err.to_string())
+    };
+    if let Some(part) = PART.get() {
+        if part == label {
+            runner::serve(process, input);
+        }
+        return 0;
+    }
+    let timeout = TIMEOUT.get().copied().unwrap_or(DEFAULT_TIMEOUT);
+    let Run {
+        outcome,
+        duration,
+        allocs,
+    } = match TRACED.load(Ordering::Relaxed) {
+        true => runner::run_here(process, input),
+        false => runner::isolated(label, timeout),
+    };
+    // "Day 05 Part1" is kept as "day-05 part1"
+    let key = label.to_lowercase().replacen(' ', "-", 1);
+    let failure = match outcome {
+        Outcome::Ok(found) => match answer(ANSWERS, &key) {
+            Some(expected) if expected != found => Some(format!(
+                "wrong answer, expected {expected} but found {found}"
+            )),
+            _ => None,
+        },
+        failed => Some(failed.to_string()),
+    };
+    match failure {
+        None if cfg!(feature = "count-alloc") => println!("{label}: {duration:?} ({allocs})"),
+        None => println!("{label}: {duration:?}"),
+        Some(err) => {
+            println!("{label}: {err}");
+            failures.push(format!("{label}: {err}"));
+        }
+    }
+    duration.as_nanos()
+}
 
-#[tracing::instrument]
 fn main() -> Result<(), ()> {
-    use std::time::Instant;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(label) = runner::part_label(&args) {
+        PART.get_or_init(|| label.to_string());
+        return all_days();
+    }
+    // the chrome trace is only written out once this is dropped
+    let _guard = configure(&args).map_err(|err| eprintln!("{err}"))?;
+    all_days()
+}
+
+fn configure(args: &[String]) -> Result<Option<FlushGuard>, String> {
+    let mut trace = None;
+    let mut file = "trace.json";
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--trace", Some(value)) => trace = Some(value.as_str()),
+            ("--trace-file", Some(value)) => file = value,
+            ("--timeout", Some(value)) => {
+                let timeout = runner::parse_timeout(value)?;
+                TIMEOUT.set(timeout).map_err(|_| String::from(USAGE))?;
+            }
+            _ => return Err(String::from(USAGE)),
+        }
+    }
+    TRACED.store(trace.is_some(), Ordering::Relaxed);
+    install_tracing(trace, file)
+}
+
+fn install_tracing(trace: Option<&str>, file: &str) -> Result<Option<FlushGuard>, String> {
+    match trace {
+        None => Ok(None),
+        Some("pretty") => {
+            tracing_subscriber::fmt()
+                .pretty()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(std::io::stderr)
+                .init();
+            Ok(None)
+        }
+        Some("chrome") => {
+            // every day has a `process` span, so they're told apart by module
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .file(file)
+                .name_fn(Box::new(|event_or_span| {
+                    let metadata = match event_or_span {
+                        EventOrSpan::Event(event) => event.metadata(),
+                        EventOrSpan::Span(span) => span.metadata(),
+                    };
+                    format!("{}::{}", metadata.target(), metadata.name())
+                }))
+                .build();
+            tracing_subscriber::registry().with(layer).init();
+            eprintln!("writing the trace to {file}");
+            Ok(Some(guard))
+        }
+        Some(other) => Err(format!("unknown trace format '{other}'\n{USAGE}")),
+    }
+}
 
+#[tracing::instrument]
+fn all_days() -> Result<(), ()> {
     let mut total_time_in_nanos = 0;
+    let mut failures = Vec::new();
 
     // day 1
-    let file = include_str!("../../../day-01/input.txt");
-    let now = Instant::now();
-    let _result = day_01::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 01 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_01::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 01 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-01/input.txt"));
+    total_time_in_nanos += run_part("Day 01 Part1", day_01::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 01 Part2", day_01::part2::process, file, &mut failures);
     println!();
 
     // day 2
-    let file = include_str!("../../../day-02/input.txt");
-    let now = Instant::now();
-    let _result = day_02::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 02 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_02::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 02 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-02/input.txt"));
+    total_time_in_nanos += run_part("Day 02 Part1", day_02::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 02 Part2", day_02::part2::process, file, &mut failures);
     println!();
 
     // day 3
-    let file = include_str!("../../../day-03/input.txt");
-    let now = Instant::now();
-    let _result = day_03::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 03 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_03::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 03 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-03/input.txt"));
+    total_time_in_nanos += run_part("Day 03 Part1", day_03::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 03 Part2", day_03::part2::process, file, &mut failures);
     println!();
 
     // day 4
-    let file = include_str!("../../../day-04/input.txt");
-    let now = Instant::now();
-    let _result = day_04::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 04 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_04::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 04 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-04/input.txt"));
+    total_time_in_nanos += run_part("Day 04 Part1", day_04::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 04 Part2", day_04::part2::process, file, &mut failures);
     println!();
 
     // day 5
-    let file = include_str!("../../../day-05/input.txt");
-    let now = Instant::now();
-    let _result = day_05::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 05 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_05::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 05 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-05/input.txt"));
+    total_time_in_nanos += run_part("Day 05 Part1", day_05::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 05 Part2", day_05::part2::process, file, &mut failures);
     println!();
 
     // day 6
-    let file = include_str!("../../../day-06/input.txt");
-    let now = Instant::now();
-    let _result = day_06::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 06 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_06::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 06 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-06/input.txt"));
+    total_time_in_nanos += run_part("Day 06 Part1", day_06::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 06 Part2", day_06::part2::process, file, &mut failures);
     println!();
 
     // day 7
-    let file = include_str!("../../../day-07/input.txt");
-    let now = Instant::now();
-    let _result = day_07::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 07 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_07::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 07 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-07/input.txt"));
+    total_time_in_nanos += run_part("Day 07 Part1", day_07::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 07 Part2", day_07::part2::process, file, &mut failures);
     println!();
 
     // day 8
-    let file = include_str!("../../../day-08/input.txt");
-    let now = Instant::now();
-    let _result = day_08::part1_hash::process(file);
-    let duration = now.elapsed();
-    println!("Day 08 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_08::part2_hash::process(file);
-    let duration = now.elapsed();
-    println!("Day 08 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-08/input.txt"));
+    total_time_in_nanos += run_part(
+        "Day 08 Part1",
+        day_08::part1_hash::process,
+        file,
+        &mut failures,
+    );
+    total_time_in_nanos += run_part(
+        "Day 08 Part2",
+        day_08::part2_hash::process,
+        file,
+        &mut failures,
+    );
     println!();
 
     // day 9
-    let file = include_str!("../../../day-09/input.txt");
-    let now = Instant::now();
-    let _result = day_09::part1_pascal::process(file);
-    let duration = now.elapsed();
-    println!("Day 09 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_09::part2_pascal::process(file);
-    let duration = now.elapsed();
-    println!("Day 09 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-09/input.txt"));
+    total_time_in_nanos += run_part(
+        "Day 09 Part1",
+        day_09::part1_pascal::process,
+        file,
+        &mut failures,
+    );
+    total_time_in_nanos += run_part(
+        "Day 09 Part2",
+        day_09::part2_pascal::process,
+        file,
+        &mut failures,
+    );
     println!();
 
     // day 10
-    let file = include_str!("../../../day-10/input.txt");
-    let now = Instant::now();
-    let _result = day_10::part1_no_map::process(file);
-    let duration = now.elapsed();
-    println!("Day 10 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_10::part2_no_map::process(file);
-    let duration = now.elapsed();
-    println!("Day 10 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-10/input.txt"));
+    total_time_in_nanos += run_part(
+        "Day 10 Part1",
+        day_10::part1_no_map::process,
+        file,
+        &mut failures,
+    );
+    total_time_in_nanos += run_part(
+        "Day 10 Part2",
+        day_10::part2_no_map::process,
+        file,
+        &mut failures,
+    );
     println!();
 
     // day 11
-    let file = include_str!("../../../day-11/input.txt");
-    let now = Instant::now();
-    let _result = day_11::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 11 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_11::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 11 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-11/input.txt"));
+    total_time_in_nanos += run_part("Day 11 Part1", day_11::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 11 Part2", day_11::part2::process, file, &mut failures);
     println!();
 
     // day 12
-    let file = include_str!("../../../day-12/input.txt");
-    let now = Instant::now();
-    let _result = day_12::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 12 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_12::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 12 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-12/input.txt"));
+    total_time_in_nanos += run_part("Day 12 Part1", day_12::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 12 Part2", day_12::part2::process, file, &mut failures);
     println!();
 
     // day 13
-    let file = include_str!("../../../day-13/input.txt");
-    let now = Instant::now();
-    let _result = day_13::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 13 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_13::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 13 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-13/input.txt"));
+    total_time_in_nanos += run_part("Day 13 Part1", day_13::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 13 Part2", day_13::part2::process, file, &mut failures);
     println!();
 
     // day 14
-    let file = include_str!("../../../day-14/input.txt");
-    let now = Instant::now();
-    let _result = day_14::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 14 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_14::part2_hash::process(file);
-    let duration = now.elapsed();
-    println!("Day 14 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-14/input.txt"));
+    total_time_in_nanos += run_part("Day 14 Part1", day_14::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part(
+        "Day 14 Part2",
+        day_14::part2_hash::process,
+        file,
+        &mut failures,
+    );
     println!();
 
     // day 15
-    let file = include_str!("../../../day-15/input.txt");
-    let now = Instant::now();
-    let _result = day_15::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 15 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_15::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 15 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-15/input.txt"));
+    total_time_in_nanos += run_part("Day 15 Part1", day_15::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 15 Part2", day_15::part2::process, file, &mut failures);
     println!();
 
     // day 16
-    let file = include_str!("../../../day-16/input.txt");
-    let now = Instant::now();
-    let _result = day_16::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 16 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_16::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 16 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-16/input.txt"));
+    total_time_in_nanos += run_part("Day 16 Part1", day_16::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part(
+        "Day 16 Part2",
+        day_16::part2_graph::process,
+        file,
+        &mut failures,
+    );
     println!();
 
     // day 17
-    let file = include_str!("../../../day-17/input.txt");
-    let now = Instant::now();
-    let _result = day_17::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 17 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_17::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 17 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-17/input.txt"));
+    total_time_in_nanos += run_part("Day 17 Part1", day_17::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 17 Part2", day_17::part2::process, file, &mut failures);
     println!();
 
     // day 18
-    let file = include_str!("../../../day-18/input.txt");
-    let now = Instant::now();
-    let _result = day_18::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 18 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_18::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 18 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-18/input.txt"));
+    total_time_in_nanos += run_part("Day 18 Part1", day_18::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 18 Part2", day_18::part2::process, file, &mut failures);
     println!();
 
     // day 19
-    let file = include_str!("../../../day-19/input.txt");
-    let now = Instant::now();
-    let _result = day_19::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 19 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_19::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 19 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-19/input.txt"));
+    total_time_in_nanos += run_part("Day 19 Part1", day_19::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 19 Part2", day_19::part2::process, file, &mut failures);
     println!();
 
     // day 20
-    let file = include_str!("../../../day-20/input.txt");
-    let now = Instant::now();
-    let _result = day_20::part1_no_map::process(file);
-    let duration = now.elapsed();
-    println!("Day 20 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_20::part2_no_map::process(file);
-    let duration = now.elapsed();
-    println!("Day 20 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    let file = &normalise(include_str!("../../../day-20/input.txt"));
+    total_time_in_nanos += run_part(
+        "Day 20 Part1",
+        day_20::part1_no_map::process,
+        file,
+        &mut failures,
+    );
+    total_time_in_nanos += run_part(
+        "Day 20 Part2",
+        day_20::part2_no_map::process,
+        file,
+        &mut failures,
+    );
+    println!();
+
+    // day 24
+    let file = &normalise(include_str!("../../../day-24/input.txt"));
+    total_time_in_nanos += run_part("Day 24 Part1", day_24::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 24 Part2", day_24::part2::process, file, &mut failures);
+    println!();
+
+    // day 25
+    let file = &normalise(include_str!("../../../day-25/input.txt"));
+    total_time_in_nanos += run_part("Day 25 Part1", day_25::part1::process, file, &mut failures);
     println!();
 
     let total_duration = Duration::from_nanos(total_time_in_nanos as u64);
 
     println!("Total time: {total_duration:?}");
+
+    if !failures.is_empty() {
+        println!();
+        println!("{} part(s) failed:", failures.len());
+        failures.iter().for_each(|failure| println!("{failure}"));
+        return Err(());
+    }
     Ok(())
 }
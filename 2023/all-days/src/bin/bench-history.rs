@@ -0,0 +1,156 @@
+use aoc_common::bench::{self, Record};
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::process::{Command, ExitCode};
+
+const USAGE: &str = "usage: bench-history [--history <file>] <command>
+  record [--commit <commit>]    read divan output from stdin and add it to the history
+  report <base> [<head>] [--threshold <percent>]
+                                median changes between two commits, head defaults to the latest
+  table [<commit>]              the runs at a commit as divan's table, for benchmarks.txt
+  markdown [<commit>]           the runs at a commit as a Markdown table";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let history =
+        take_option(&mut args, "--history").unwrap_or_else(|| String::from("bench-history.jsonl"));
+    let result = match args.first().map(String::as_str) {
+        Some("record") => record(&history, &mut args),
+        Some("report") => report(&history, &mut args),
+        Some("table") => at_commit(&history, &args).map(|records| {
+            print!(
+                "{}",
+                bench::divan_table(&records.iter().collect::<Vec<_>>())
+            );
+            true
+        }),
+        Some("markdown") => at_commit(&history, &args).map(|records| {
+            print!(
+                "{}",
+                bench::markdown_table(&records.iter().collect::<Vec<_>>())
+            );
+            true
+        }),
+        _ => Err(String::from(USAGE)),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// divan's output is passed through, so piping into this still shows the results
+fn record(history: &str, args: &mut Vec<String>) -> Result<bool, String> {
+    let commit = match take_option(args, "--commit") {
+        Some(commit) => commit,
+        None => current_commit()?,
+    };
+    let mut output = String::new();
+    std::io::stdin()
+        .read_to_string(&mut output)
+        .map_err(|err| format!("couldn't read the benchmark output: {err}"))?;
+    print!("{output}");
+
+    let records = bench::parse_divan(&output, &commit);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history)
+        .map_err(|err| format!("couldn't open {history}: {err}"))?;
+    for record in &records {
+        writeln!(file, "{}", record.to_json()?)
+            .map_err(|err| format!("couldn't write to {history}: {err}"))?;
+    }
+    eprintln!(
+        "recorded {} benchmark(s) for {commit} in {history}",
+        records.len()
+    );
+    Ok(true)
+}
+
+fn report(history: &str, args: &mut Vec<String>) -> Result<bool, String> {
+    let threshold = match take_option(args, "--threshold") {
+        Some(threshold) => threshold
+            .parse()
+            .map_err(|_| format!("expected a percentage for --threshold, found '{threshold}'"))?,
+        None => 5.0,
+    };
+    let records = load(history)?;
+    let commits = bench::commits(&records);
+    let base = args.get(1).ok_or_else(|| String::from(USAGE))?;
+    let head = match args.get(2) {
+        Some(head) => head.as_str(),
+        None => commits
+            .last()
+            .ok_or_else(|| format!("{history} is empty"))?,
+    };
+    for commit in [base.as_str(), head] {
+        if !commits.contains(&commit) {
+            return Err(format!("there are no runs for {commit} in {history}"));
+        }
+    }
+
+    let deltas = bench::compare(&records, base, head);
+    print!("{}", bench::report(&deltas, threshold));
+    let regressions = deltas
+        .iter()
+        .filter(|delta| delta.is_regression(threshold))
+        .count();
+    if regressions > 0 {
+        println!("{regressions} regression(s) over {threshold}% from {base} to {head}");
+    }
+    Ok(regressions == 0)
+}
+
+fn at_commit(history: &str, args: &[String]) -> Result<Vec<Record>, String> {
+    let records = load(history)?;
+    let commit = match args.get(1) {
+        Some(commit) => commit.as_str(),
+        None => *bench::commits(&records)
+            .last()
+            .ok_or_else(|| format!("{history} is empty"))?,
+    };
+    let runs: Vec<Record> = bench::at_commit(&records, commit)
+        .into_iter()
+        .cloned()
+        .collect();
+    match runs.is_empty() {
+        true => Err(format!("there are no runs for {commit} in {history}")),
+        false => Ok(runs),
+    }
+}
+
+fn load(history: &str) -> Result<Vec<Record>, String> {
+    let input = std::fs::read_to_string(history)
+        .map_err(|err| format!("couldn't read {history}: {err}"))?;
+    bench::parse_history(&input).map_err(|err| format!("{history}: {err}"))
+}
+
+// runs with uncommitted changes are kept apart from the commit they're based on
+fn current_commit() -> Result<String, String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .ok_or_else(|| String::from("couldn't ask git for the commit, pass --commit instead"))
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    match git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        true => Ok(commit),
+        false => Ok(format!("{commit}-dirty")),
+    }
+}
+
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let at = args.iter().position(|arg| arg == name)?;
+    let value = args.get(at + 1).cloned();
+    args.drain(at..(at + 2).min(args.len()));
+    value
+}
@@ -0,0 +1,350 @@
+use std::io::{self, Read};
+
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// aho-corasick over bytes, with every state's move on every byte worked out up front so
+// scanning is one table lookup a byte. matches can overlap, so "twone" is 2 then 1
+pub struct Automaton {
+    next: Vec<[u32; 256]>,
+    // the (value, length) of the words ending at each state, longest first
+    outputs: Vec<Vec<(u32, usize)>>,
+    // the value and length of the longest of those, and the value of the shortest, for
+    // the calibrator
+    ends: Vec<Option<(u32, usize, u32)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub value: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Automaton {
+    // a word given twice keeps its last value
+    pub fn new<'w>(words: impl IntoIterator<Item = (&'w str, u32)>) -> Automaton {
+        const NONE: u32 = u32::MAX;
+        let mut goto = vec![[NONE; 256]];
+        let mut own: Vec<Option<(u32, usize)>> = vec![None];
+        for (word, value) in words {
+            assert!(!word.is_empty(), "words to match can't be empty");
+            let mut state = 0;
+            for &byte in word.as_bytes() {
+                if goto[state][byte as usize] == NONE {
+                    goto[state][byte as usize] = goto.len() as u32;
+                    goto.push([NONE; 256]);
+                    own.push(None);
+                }
+                state = goto[state][byte as usize] as usize;
+            }
+            own[state] = Some((value, word.len()));
+        }
+
+        // breadth first, so a state's fail state, being shallower, is finished before it
+        let mut next = vec![[0; 256]; goto.len()];
+        let mut outputs = vec![Vec::new(); goto.len()];
+        let mut fail = vec![0; goto.len()];
+        outputs[0].extend(own[0]);
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let child = goto[state][byte];
+                if child == NONE {
+                    next[state][byte] = match state {
+                        0 => 0,
+                        _ => next[fail[state]][byte],
+                    };
+                    continue;
+                }
+                let child = child as usize;
+                next[state][byte] = child as u32;
+                fail[child] = match state {
+                    0 => 0,
+                    _ => next[fail[state]][byte] as usize,
+                };
+                let inherited = outputs[fail[child]].clone();
+                outputs[child].extend(own[child]);
+                outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        let ends = outputs
+            .iter()
+            .map(|words| {
+                let (longest, len) = *words.first()?;
+                Some((longest, len, words.last()?.0))
+            })
+            .collect();
+        Automaton {
+            next,
+            outputs,
+            ends,
+        }
+    }
+
+    // the digits, and the digit words in english
+    pub fn english() -> Automaton {
+        Automaton::new(DIGITS.into_iter().chain(ENGLISH))
+    }
+
+    // in the order they end, and longest first when they end together
+    pub fn matches<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        haystack
+            .iter()
+            .scan(0, |state, &byte| {
+                *state = self.next[*state][byte as usize] as usize;
+                Some(*state)
+            })
+            .enumerate()
+            .flat_map(move |(at, state)| {
+                self.outputs[state].iter().map(move |&(value, len)| Match {
+                    value,
+                    start: at + 1 - len,
+                    end: at + 1,
+                })
+            })
+    }
+}
+
+// works out each line's calibration value from input given a piece at a time, in one pass.
+// the first match is the one that starts first, the longest if several do, and the last is
+// the one that ends last, the shortest if several do. words shouldn't have a newline in them
+pub struct Calibrator<'a> {
+    automaton: &'a Automaton,
+    state: usize,
+    // how far into the line the bytes have got
+    at: usize,
+    // the (start, value) of the first match so far. one ending later can still start
+    // earlier, when a word has another inside it
+    first: Option<(usize, u32)>,
+    last: u32,
+    in_line: bool,
+}
+
+impl<'a> Calibrator<'a> {
+    pub fn new(automaton: &'a Automaton) -> Calibrator<'a> {
+        Calibrator {
+            automaton,
+            state: 0,
+            at: 0,
+            first: None,
+            last: 0,
+            in_line: false,
+        }
+    }
+
+    // calls `line` with the value of each line the bytes finish, or None when a line has
+    // nothing in it to match
+    pub fn feed(&mut self, bytes: &[u8], mut line: impl FnMut(Option<u32>)) {
+        for &byte in bytes {
+            if byte == b'\n' {
+                line(self.value());
+                continue;
+            }
+            self.in_line = true;
+            self.at += 1;
+            self.state = self.automaton.next[self.state][byte as usize] as usize;
+            if let Some((longest, len, shortest)) = self.automaton.ends[self.state] {
+                let start = self.at - len;
+                if self.first.is_none_or(|(first, _)| start <= first) {
+                    self.first = Some((start, longest));
+                }
+                self.last = shortest;
+            }
+        }
+    }
+
+    // the value of a last line with no newline after it
+    pub fn finish(mut self) -> Option<Option<u32>> {
+        self.in_line.then(|| self.value())
+    }
+
+    fn value(&mut self) -> Option<u32> {
+        let value = self.first.map(|(_, first)| first * 10 + self.last);
+        self.state = 0;
+        self.at = 0;
+        self.first = None;
+        self.in_line = false;
+        value
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub sum: u64,
+    pub lines: usize,
+    // the first line, counting from 0, with nothing to match
+    pub missing: Option<usize>,
+}
+
+impl Calibration {
+    fn add(&mut self, value: Option<u32>) {
+        match value {
+            Some(value) => self.sum += u64::from(value),
+            None => {
+                self.missing.get_or_insert(self.lines);
+            }
+        }
+        self.lines += 1;
+    }
+}
+
+pub fn calibrate(automaton: &Automaton, input: &[u8]) -> Calibration {
+    let mut calibration = Calibration::default();
+    let mut calibrator = Calibrator::new(automaton);
+    calibrator.feed(input, |value| calibration.add(value));
+    if let Some(value) = calibrator.finish() {
+        calibration.add(value);
+    }
+    calibration
+}
+
+// the same, reading the input a block at a time
+pub fn calibrate_reader(automaton: &Automaton, mut reader: impl Read) -> io::Result<Calibration> {
+    let mut calibration = Calibration::default();
+    let mut calibrator = Calibrator::new(automaton);
+    let mut buffer = vec![0; 1 << 16];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => calibrator.feed(&buffer[..read], |value| calibration.add(value)),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    if let Some(value) = calibrator.finish() {
+        calibration.add(value);
+    }
+    Ok(calibration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    fn values(automaton: &Automaton, haystack: &str) -> Vec<u32> {
+        automaton
+            .matches(haystack.as_bytes())
+            .map(|m| m.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let automaton = Automaton::english();
+        assert_eq!(vec![2, 1, 3, 4], values(&automaton, "xtwone3four"));
+        assert_eq!(vec![8, 2], values(&automaton, "eightwo"));
+        let matches: Vec<Match> = automaton.matches(b"zoneight").collect();
+        assert_eq!(
+            vec![
+                Match {
+                    value: 1,
+                    start: 1,
+                    end: 4
+                },
+                Match {
+                    value: 8,
+                    start: 3,
+                    end: 8
+                },
+            ],
+            matches
+        );
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let automaton = Automaton::new([("zero", 0), ("null", 0), ("eins", 1), ("ein", 1)]);
+        assert_eq!(vec![1, 0, 0], values(&automaton, "einullzero"));
+        // a word at the end of a longer one comes after it
+        let automaton = Automaton::new([("she", 1), ("he", 2), ("hers", 3), ("his", 4)]);
+        assert_eq!(vec![1, 2, 3], values(&automaton, "ushers"));
+        assert!(values(&Automaton::new([]), "anything").is_empty());
+    }
+
+    #[test]
+    fn test_nested_words() {
+        // "two" ends first, but "network" starts first, so it's the first digit
+        let automaton = Automaton::new([("network", 1), ("two", 2), ("w", 3), ("k", 4)]);
+        assert_eq!(vec![3, 2, 1, 4], values(&automaton, "network"));
+        assert_eq!(14, calibrate(&automaton, b"network").sum);
+        assert_eq!(34, calibrate(&automaton, b"xnwk").sum);
+        // of the words starting together the longest is first, and of those ending
+        // together the shortest is last
+        let automaton = Automaton::new([("ein", 1), ("eins", 2), ("s", 3)]);
+        assert_eq!(23, calibrate(&automaton, b"eins").sum);
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let automaton = Automaton::english();
+        let expected = Calibration {
+            sum: 281,
+            lines: 7,
+            missing: None,
+        };
+        assert_eq!(expected, calibrate(&automaton, EXAMPLE.as_bytes()));
+        assert_eq!(
+            Calibration {
+                sum: 22,
+                lines: 3,
+                missing: Some(1),
+            },
+            calibrate(&automaton, b"one\nabc\none\n")
+        );
+    }
+
+    #[test]
+    fn test_reader_pieces() {
+        // words split across reads still match
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+                let read = self.0.len().min(buffer.len()).min(3);
+                buffer[..read].copy_from_slice(&self.0[..read]);
+                self.0 = &self.0[read..];
+                Ok(read)
+            }
+        }
+        let automaton = Automaton::english();
+        let calibration = calibrate_reader(&automaton, Trickle(EXAMPLE.as_bytes())).unwrap();
+        assert_eq!(281, calibration.sum);
+        for seed in 0..3 {
+            let generated = crate::generate::generate(seed, 200);
+            let calibration =
+                calibrate_reader(&automaton, Trickle(generated.input.as_bytes())).unwrap();
+            assert_eq!(generated.part2, Some(calibration.sum as u32));
+        }
+    }
+}
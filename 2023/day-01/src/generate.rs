@@ -0,0 +1,68 @@
+use aoc_common::generate::{Generated, Rng};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+// none of these letters appear in a digit word, so filler can't spell one by accident
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+// `size` is the number of lines in the calibration document
+pub fn generate(seed: u64, size: usize) -> Generated<u32> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size {
+        // (value, is it a digit rather than a word)
+        let mut tokens: Vec<(u32, bool)> = (0..rng.usize(1..6))
+            .map(|_| (rng.u32(1..10), rng.bool()))
+            .collect();
+        let digit = rng.usize(..=tokens.len());
+        tokens.insert(digit, (rng.u32(1..10), true));
+
+        for (value, is_digit) in &tokens {
+            push_filler(&mut rng, &mut input);
+            match is_digit {
+                true => input.push(char::from_digit(*value, 10).expect("Single digit")),
+                false => input.push_str(WORDS[*value as usize - 1]),
+            }
+        }
+        push_filler(&mut rng, &mut input);
+        input.push('\n');
+
+        let digits: Vec<u32> = tokens
+            .iter()
+            .filter(|(_, is_digit)| *is_digit)
+            .map(|(value, _)| *value)
+            .collect();
+        part1 += digits[0] * 10 + digits[digits.len() - 1];
+        part2 += tokens[0].0 * 10 + tokens[tokens.len() - 1].0;
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+fn push_filler(rng: &mut Rng, input: &mut String) {
+    for _ in 0..rng.usize(..5) {
+        input.push(FILLER[rng.usize(..FILLER.len())] as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 200);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -1,2 +1,6 @@
 pub mod part1;
 pub mod part2;
+pub mod generate;
+
+pub mod automaton;
+pub mod part2_automaton;
@@ -1,22 +1,24 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> u32 {
-    return input.lines().map(parse_words).sum();
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    input
+        .lines()
+        .map(parse_words)
+        .sum::<Result<u32, ParseError>>()
+        .map_err(|err| err.locate(input))
 }
 
-fn parse_words(s: &str) -> u32 {
+fn parse_words(s: &str) -> Result<u32, ParseError> {
     let first_idx = s
         .find(|x: char| x.is_ascii_digit())
-        .expect("should have one digit");
+        .ok_or_else(|| ParseError::expected(s, "at least one digit"))?;
     let last_idx = s.rfind(|x: char| x.is_ascii_digit()).unwrap_or(first_idx);
 
-    let first_number: u32 = s[first_idx..=first_idx]
-        .parse()
-        .expect("Failed to parse first digit");
-    let last_number: u32 = s[last_idx..=last_idx]
-        .parse()
-        .expect("Failed to parse last digit");
+    let first_number = (s.as_bytes()[first_idx] - b'0') as u32;
+    let last_number = (s.as_bytes()[last_idx] - b'0') as u32;
 
-    first_number * 10 + last_number
+    Ok(first_number * 10 + last_number)
 }
 
 #[cfg(test)]
@@ -30,6 +32,6 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
 
-        assert_eq!(142, process(input))
+        assert_eq!(Ok(142), process(input))
     }
 }
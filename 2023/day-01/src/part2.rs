@@ -1,14 +1,21 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> u32 {
-    return input.lines().map(parse_words).sum();
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    input
+        .lines()
+        .map(parse_words)
+        .sum::<Result<u32, ParseError>>()
+        .map_err(|err| err.locate(input))
 }
 
-fn parse_words(line: &str) -> u32 {
-    let first = first_number(line).expect("should be a number");
+fn parse_words(line: &str) -> Result<u32, ParseError> {
+    let first =
+        first_number(line).ok_or_else(|| ParseError::expected(line, "a digit or digit word"))?;
 
     let last = last_number(line).unwrap_or(first);
 
-    first * 10 + last
+    Ok(first * 10 + last)
 }
 
 fn first_number(line: &str) -> Option<u32> {
@@ -94,6 +101,6 @@ xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen";
-        assert_eq!(281, process(input))
+        assert_eq!(Ok(281), process(input))
     }
 }
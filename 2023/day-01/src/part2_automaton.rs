@@ -0,0 +1,34 @@
+use crate::automaton::{calibrate, Automaton};
+use aoc_common::ParseError;
+use std::sync::OnceLock;
+
+static ENGLISH: OnceLock<Automaton> = OnceLock::new();
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    let calibration = calibrate(ENGLISH.get_or_init(Automaton::english), input.as_bytes());
+    if let Some(missing) = calibration.missing {
+        let line = input.lines().nth(missing).unwrap_or(input);
+        return Err(ParseError::expected(line, "a digit or digit word").locate(input));
+    }
+    u32::try_from(calibration.sum)
+        .map_err(|_| ParseError::new(input, "the calibration values add up to more than a u32"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part2_automaton() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        assert_eq!(Ok(281), process(input));
+        assert!(process("one\nabc\n").is_err());
+    }
+}
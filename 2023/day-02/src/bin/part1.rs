@@ -1,8 +1,11 @@
-use day_02::part1::process;
+use aoc_common::{read_input, ParseError};
+use day_02::part1::{process_with, BAG};
 
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+// the bag can be given as an argument, like "12 red, 13 green, 14 blue"
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let bag = std::env::args().nth(1).unwrap_or_else(|| BAG.to_string());
+    let result = process_with(&file, &bag)?;
     println!("{}", result);
     Ok(())
 }
@@ -0,0 +1,149 @@
+use aoc_common::parse::{number, split_once};
+use aoc_common::ParseError;
+use std::collections::BTreeMap;
+
+// how many cubes of each colour, for what's in a bag or the most of each a game showed.
+// a colour that isn't there has none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes<'a>(BTreeMap<&'a str, usize>);
+
+impl<'a> Cubes<'a> {
+    // like "3 blue, 4 red"
+    pub fn parse(s: &'a str) -> Result<Cubes<'a>, ParseError> {
+        let mut cubes = Cubes::default();
+        for cube in s.split(", ") {
+            let (count, colour) = split_once(cube, " ")?;
+            cubes.add_most(colour, number(count)?);
+        }
+        Ok(cubes)
+    }
+
+    pub fn get(&self, colour: &str) -> usize {
+        self.0.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.0.keys().copied()
+    }
+
+    // keeps the larger count for the colour
+    fn add_most(&mut self, colour: &'a str, count: usize) {
+        let most = self.0.entry(colour).or_insert(0);
+        *most = (*most).max(count);
+    }
+
+    // the larger count of each colour in either
+    pub fn union(&self, other: &Cubes<'a>) -> Cubes<'a> {
+        let mut cubes = self.clone();
+        for (&colour, &count) in &other.0 {
+            cubes.add_most(colour, count);
+        }
+        cubes
+    }
+
+    // whether there's at least as many of every colour as `other` has
+    pub fn holds(&self, other: &Cubes) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(colour, &count)| self.get(colour) >= count)
+    }
+
+    pub fn power(&self, colours: &[&str]) -> usize {
+        colours.iter().map(|colour| self.get(colour)).product()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: usize,
+    // the most of each colour shown at once, the least the bag can hold
+    pub least: Cubes<'a>,
+}
+
+impl<'a> Game<'a> {
+    pub fn parse(s: &'a str) -> Result<Game<'a>, ParseError> {
+        let (game, draws) = split_once(s, ": ")?;
+        let id = number(split_once(game, " ")?.1)?;
+        let mut least = Cubes::default();
+        for draw in draws.split("; ") {
+            least = least.union(&Cubes::parse(draw)?);
+        }
+        Ok(Game { id, least })
+    }
+
+    pub fn possible_with(&self, bag: &Cubes) -> bool {
+        bag.holds(&self.least)
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game<'_>>, ParseError> {
+    input.lines().map(Game::parse).collect()
+}
+
+pub fn possible<'g, 'a>(
+    games: &'g [Game<'a>],
+    bag: &'g Cubes,
+) -> impl Iterator<Item = &'g Game<'a>> + 'g {
+    games.iter().filter(move |game| game.possible_with(bag))
+}
+
+// the smallest bag every game could have come from
+pub fn smallest_bag<'a>(games: &[Game<'a>]) -> Cubes<'a> {
+    games
+        .iter()
+        .fold(Cubes::default(), |bag, game| bag.union(&game.least))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn ids<'g>(games: impl Iterator<Item = &'g Game<'g>>) -> Vec<usize> {
+        games.map(|game| game.id).collect()
+    }
+
+    #[test]
+    fn test_possible() {
+        let games = &parse_games(EXAMPLE).unwrap();
+        let bag = Cubes::parse("12 red, 13 green, 14 blue").unwrap();
+        assert_eq!(vec![1, 2, 5], ids(possible(games, &bag)));
+        let bag = Cubes::parse("20 red, 13 green, 15 blue").unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5], ids(possible(games, &bag)));
+        // no green at all
+        let bag = Cubes::parse("20 red, 20 blue").unwrap();
+        assert!(ids(possible(games, &bag)).is_empty());
+    }
+
+    #[test]
+    fn test_bags() {
+        let games = parse_games(EXAMPLE).unwrap();
+        assert_eq!(
+            Cubes::parse("4 red, 2 green, 6 blue").unwrap(),
+            games[0].least
+        );
+        assert_eq!(48, games[0].least.power(&["red", "green", "blue"]));
+        let smallest = smallest_bag(&games);
+        assert_eq!(Cubes::parse("20 red, 13 green, 15 blue").unwrap(), smallest);
+        assert!(games.iter().all(|game| game.possible_with(&smallest)));
+    }
+
+    #[test]
+    fn test_any_colours() {
+        let games = parse_games("Game 7: 2 teal, 1 red; 5 teal\nGame 8: 1 ochre").unwrap();
+        assert_eq!(
+            vec!["red", "teal"],
+            games[0].least.colours().collect::<Vec<_>>()
+        );
+        let bag = Cubes::parse("5 teal, 1 red, 3 ochre").unwrap();
+        assert!(games.iter().all(|game| game.possible_with(&bag)));
+        assert_eq!(0, games[1].least.power(&["ochre", "teal"]));
+        assert!(Game::parse("Game 9: blue 3").is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use aoc_common::generate::{Generated, Rng};
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+const LIMITS: [usize; 3] = [12, 13, 14];
+
+// `size` is the number of games
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for id in 1..=size {
+        let mut most = [0; 3];
+        let draws: Vec<String> = (0..rng.usize(1..7))
+            .map(|_| {
+                let mut colours = vec![0, 1, 2];
+                rng.shuffle(&mut colours);
+                colours.truncate(rng.usize(1..=3));
+                let cubes: Vec<String> = colours
+                    .iter()
+                    .map(|&colour| {
+                        let count = rng.usize(1..=20);
+                        most[colour] = most[colour].max(count);
+                        format!("{} {}", count, COLOURS[colour])
+                    })
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        input.push_str(&format!("Game {}: {}\n", id, draws.join("; ")));
+
+        if most.iter().zip(LIMITS).all(|(&most, limit)| most <= limit) {
+            part1 += id;
+        }
+        part2 += most.iter().product::<usize>();
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
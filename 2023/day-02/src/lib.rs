@@ -1,2 +1,5 @@
 pub mod part1;
 pub mod part2;
+pub mod generate;
+
+pub mod game;
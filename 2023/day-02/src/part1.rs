@@ -1,67 +1,17 @@
-use std::str::FromStr;
+use crate::game::{parse_games, possible, Cubes};
+use aoc_common::ParseError;
 
-pub fn process(input: &str) -> usize {
-    return input
-        .lines()
-        .flat_map(|line| line.parse::<Game>())
-        .filter(|game| game.is_valid())
-        .map(|game| game.id)
-        .sum();
-}
-
-struct Game {
-    id: usize,
-    most_blue: usize,
-    most_green: usize,
-    most_red: usize,
-}
+pub const BAG: &str = "12 red, 13 green, 14 blue";
 
-impl Game {
-    fn is_valid(&self) -> bool {
-        self.most_blue <= 14 && self.most_green <= 13 && self.most_red <= 12
-    }
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    process_with(input, BAG)
 }
 
-impl FromStr for Game {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split_once(": ").expect("It has a colon");
-        let game_id = parts
-            .0
-            .split_once(' ')
-            .expect("It has a space")
-            .1
-            .parse::<usize>()
-            .expect("It's a number");
-        let words: &str = parts.1;
-        Ok(words.split("; ").flat_map(|word| word.split(", ")).fold(
-            Game {
-                id: game_id,
-                most_blue: 0,
-                most_green: 0,
-                most_red: 0,
-            },
-            |acc, word| {
-                let (number, color) = word.split_once(' ').expect("Should have a space");
-                let count = number.parse::<usize>().expect("Should be a number");
-                let mut game = acc;
-                match color {
-                    "blue" => {
-                        game.most_blue = game.most_blue.max(count);
-                    }
-                    "green" => {
-                        game.most_green = game.most_green.max(count);
-                    }
-                    "red" => {
-                        game.most_red = game.most_red.max(count);
-                    }
-                    _ => todo!(),
-                };
-                game
-            },
-        ))
-    }
+// the sum of the ids of the games that could have come from the bag, like "12 red, 13 green"
+pub fn process_with(input: &str, bag: &str) -> Result<usize, ParseError> {
+    let games = parse_games(input).map_err(|err| err.locate(input))?;
+    let bag = Cubes::parse(bag)?;
+    Ok(possible(&games, &bag).map(|game| game.id).sum())
 }
 
 #[cfg(test)]
@@ -76,6 +26,7 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        assert_eq!(8, process(input))
+        assert_eq!(Ok(8), process(input));
+        assert_eq!(Ok(2), process_with(input, "4 red, 3 green, 4 blue"));
     }
 }
@@ -0,0 +1,101 @@
+use aoc_common::generate::{Generated, Rng};
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+struct Placed {
+    value: u32,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+// `size` is the width and height of the schematic
+pub fn generate(seed: u64, size: usize) -> Generated<u32> {
+    let mut rng = Rng::with_seed(seed);
+    let mut grid = vec![vec![b'.'; size]; size];
+    let mut numbers = Vec::new();
+
+    for (row, line) in grid.iter_mut().enumerate() {
+        let mut col = 0;
+        while col < size {
+            let len = rng.usize(1..=3);
+            // numbers never touch each other on a line, or they'd merge
+            let after_number = numbers
+                .last()
+                .is_some_and(|number: &Placed| number.row == row && number.end + 1 == col);
+            if !after_number && col + len <= size && rng.u8(..100) < 15 {
+                let value = rng.u32(10u32.pow(len as u32 - 1).max(1)..10u32.pow(len as u32));
+                line[col..col + len].copy_from_slice(value.to_string().as_bytes());
+                numbers.push(Placed {
+                    value,
+                    row,
+                    start: col,
+                    end: col + len - 1,
+                });
+                col += len;
+            } else {
+                if rng.u8(..100) < 6 {
+                    line[col] = SYMBOLS[rng.usize(..SYMBOLS.len())];
+                }
+                col += 1;
+            }
+        }
+    }
+
+    let touches = |number: &Placed, row: usize, col: usize| {
+        number.row.abs_diff(row) <= 1 && number.start <= col + 1 && col <= number.end + 1
+    };
+    let mut part1 = 0;
+    let mut part2 = 0;
+    for number in &numbers {
+        let is_part = (number.row.saturating_sub(1)..(number.row + 2).min(size)).any(|row| {
+            (number.start.saturating_sub(1)..(number.end + 2).min(size))
+                .any(|col| SYMBOLS.contains(&grid[row][col]))
+        });
+        if is_part {
+            part1 += number.value;
+        }
+    }
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            if cell != b'*' {
+                continue;
+            }
+            let nearby: Vec<u32> = numbers
+                .iter()
+                .filter(|number| touches(number, row, col))
+                .map(|number| number.value)
+                .collect();
+            if nearby.len() == 2 {
+                part2 += nearby[0] * nearby[1];
+            }
+        }
+    }
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in grid {
+        input.push_str(&String::from_utf8(line).expect("Ascii"));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 40);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -1,26 +1,20 @@
-use crate::schematic::{Number, Schematic};
+use crate::schematic::{text_at, Schematic};
+use aoc_common::ParseError;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> u32 {
-    let schematic = input.parse::<Schematic>().unwrap();
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    let schematic = input
+        .parse::<Schematic>()
+        .map_err(|err| err.locate(input))?;
 
-    let numbers = schematic.get_numbers();
-
-    numbers
-        .iter()
-        .filter(|number| is_part_number(number, &schematic))
-        .map(|number| number.value)
-        .sum()
-}
-
-fn is_part_number(number: &Number, schematic: &Schematic) -> bool {
-    for (x, y) in number.position.iter() {
-        if schematic.neighbours_symbol(*x, *y) {
-            return true;
-        }
-    }
-
-    false
+    // bound so the iterator borrowing `schematic` is dropped before it
+    let total = schematic.part_numbers().try_fold(0u32, |total, number| {
+        total.checked_add(number.value).ok_or_else(|| {
+            let at = text_at(input, number.row, number.start);
+            ParseError::new(at, "the part numbers are too big to add up").locate(input)
+        })
+    });
+    total
 }
 
 #[cfg(test)]
@@ -39,6 +33,12 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        assert_eq!(4361, process(input));
+        assert_eq!(Ok(4361), process(input));
+    }
+
+    #[test]
+    fn test_too_big() {
+        let error = process("4000000000*4000000000").unwrap_err();
+        assert_eq!((1, 12), (error.line, error.column));
     }
 }
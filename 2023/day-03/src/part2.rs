@@ -1,43 +1,26 @@
-use crate::schematic::{Number, Schematic};
-use hashbrown::HashMap;
-
-#[tracing::instrument]
-pub fn process(input: &str) -> u32 {
-    let schematic = input.parse::<Schematic>().unwrap();
-
-    let numbers = schematic.get_numbers();
-    let position_map = build_map(&numbers);
-
-    let mut total = 0;
-    for row in 0..schematic.height {
-        for col in 0..schematic.width {
-            if let Some(cell) = schematic.get_cell(row, col) {
-                if cell == '*' {
-                    total += schematic.compute_gear(row, col, &position_map)
-                }
-            }
-        }
-    }
-
+use crate::schematic::{text_at, Schematic};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<u32, ParseError> {
+    let schematic = input
+        .parse::<Schematic>()
+        .map_err(|err| err.locate(input))?;
+
+    // bound so the iterator borrowing `schematic` is dropped before it
+    let total = schematic.gears(2).try_fold(0u32, |total, gear| {
+        schematic
+            .adjacent(gear)
+            .try_fold(1u32, |ratio, number| ratio.checked_mul(number.value))
+            .and_then(|ratio| total.checked_add(ratio))
+            .ok_or_else(|| {
+                let at = text_at(input, gear.row, gear.col);
+                ParseError::new(at, "the gear ratios are too big to add up").locate(input)
+            })
+    });
     total
 }
 
-pub fn hash_coord(row: usize, col: usize) -> usize {
-    row + 1000 * col
-}
-
-fn build_map(numbers: &Vec<Number>) -> HashMap<usize, &Number> {
-    let mut out = HashMap::new();
-
-    for number in numbers {
-        for position in &number.position {
-            out.insert(hash_coord(position.0, position.1), number);
-        }
-    }
-
-    out
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,6 +37,12 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        assert_eq!(467835, process(input));
+        assert_eq!(Ok(467835), process(input));
+    }
+
+    #[test]
+    fn test_too_big() {
+        let error = process("..99999*99999").unwrap_err();
+        assert_eq!((1, 8), (error.line, error.column));
     }
 }
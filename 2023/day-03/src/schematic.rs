@@ -1,29 +1,121 @@
-use crate::part2::hash_coord;
+use aoc_common::ParseError;
 use std::str::FromStr;
-use hashbrown::HashMap;
+
+// the digits from `start` up to `end` on a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: char,
+    pub row: usize,
+    pub col: usize,
+    // indices into the schematic's numbers, each number once however many digits touch it
+    pub numbers: Vec<usize>,
+}
 
 #[derive(Debug)]
 pub struct Schematic {
     grid: Vec<Vec<char>>,
     pub width: usize,
     pub height: usize,
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    // whether each number touches a symbol
+    attached: Vec<bool>,
 }
 
 impl FromStr for Schematic {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::expected(s, "a non-empty schematic"))?
+            .chars()
+            .count();
         let grid = s
             .lines()
-            .map(|line| line.chars().collect::<Vec<char>>())
-            .collect::<Vec<_>>();
+            .map(|line| match line.chars().count() == width {
+                true => Ok(line.chars().collect::<Vec<char>>()),
+                false => Err(ParseError::expected(
+                    line,
+                    &format!("a row {} characters wide", width),
+                )),
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
         let height = grid.len();
-        let width = grid.first().expect("Grid is non empty").len();
+
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        // which number each digit belongs to
+        let mut number_at = vec![vec![None; width]; height];
+        for (row, (text, line)) in s.lines().zip(&grid).enumerate() {
+            let mut col = 0;
+            while col < width {
+                let cell = line[col];
+                if let Some(digit) = cell.to_digit(10) {
+                    let mut number = Number {
+                        value: digit,
+                        row,
+                        start: col,
+                        end: col + 1,
+                    };
+                    number_at[row][col] = Some(numbers.len());
+                    while let Some(digit) = line.get(number.end).and_then(|c| c.to_digit(10)) {
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(digit))
+                            .ok_or_else(|| {
+                                ParseError::new(from_col(text, col), "the number is too big")
+                            })?;
+                        number_at[row][number.end] = Some(numbers.len());
+                        number.end += 1;
+                    }
+                    col = number.end;
+                    numbers.push(number);
+                    continue;
+                }
+                if cell != '.' {
+                    symbols.push(Symbol {
+                        kind: cell,
+                        row,
+                        col,
+                        numbers: Vec::new(),
+                    });
+                }
+                col += 1;
+            }
+        }
+
+        let mut attached = vec![false; numbers.len()];
+        for symbol in &mut symbols {
+            let rows = symbol.row.saturating_sub(1)..(symbol.row + 2).min(height);
+            let cols = symbol.col.saturating_sub(1)..(symbol.col + 2).min(width);
+            for line in &number_at[rows] {
+                for &number in line[cols.clone()].iter().flatten() {
+                    if !symbol.numbers.contains(&number) {
+                        symbol.numbers.push(number);
+                        attached[number] = true;
+                    }
+                }
+            }
+        }
 
         Ok(Schematic {
             grid,
-            height,
             width,
+            height,
+            numbers,
+            symbols,
+            attached,
         })
     }
 }
@@ -35,115 +127,147 @@ impl Schematic {
         Some(*col)
     }
 
-    fn get_min_max(&self, row: usize, col: usize) -> (usize, usize, usize, usize) {
-        let row_min = if row == 0 { 0 } else { row - 1 };
-        let row_max = if row == self.height - 1 {
-            self.height
-        } else {
-            row + 2
-        };
-        let col_min = if col == 0 { 0 } else { col - 1 };
-        let col_max = if col == self.width - 1 {
-            self.width
-        } else {
-            col + 2
-        };
-
-        (row_min, row_max, col_min, col_max)
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
     }
 
-    pub fn neighbours_symbol(&self, row: usize, col: usize) -> bool {
-        let (row_min, row_max, col_min, col_max) = self.get_min_max(row, col);
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
 
-        for i in row_min..row_max {
-            for j in col_min..col_max {
-                let cell = self.grid.get(i).unwrap().get(j).unwrap();
-                if !(cell.is_ascii_digit() || *cell == '.') {
-                    return true;
-                }
+    pub fn adjacent<'a>(&'a self, symbol: &'a Symbol) -> impl Iterator<Item = &'a Number> + 'a {
+        symbol.numbers.iter().map(|&number| &self.numbers[number])
+    }
+
+    // numbers next to any symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.attached)
+            .filter_map(|(number, &attached)| attached.then_some(number))
+    }
+
+    pub fn unattached(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.attached)
+            .filter_map(|(number, &attached)| (!attached).then_some(number))
+    }
+
+    // numbers next to a symbol of the kind, each once even when it's next to several
+    pub fn next_to(&self, kind: char) -> impl Iterator<Item = &Number> {
+        let mut wanted = vec![false; self.numbers.len()];
+        for symbol in self.symbols.iter().filter(|symbol| symbol.kind == kind) {
+            for &number in &symbol.numbers {
+                wanted[number] = true;
             }
         }
+        self.numbers
+            .iter()
+            .zip(wanted)
+            .filter_map(|(number, wanted)| wanted.then_some(number))
+    }
 
-        false
+    // '*'s next to exactly `count` numbers
+    pub fn gears(&self, count: usize) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.kind == '*' && symbol.numbers.len() == count)
     }
+}
 
-    pub fn get_numbers(&self) -> Vec<Number> {
-        let mut out: Vec<Number> = Vec::new();
-        for i in 0..self.width {
-            let row = self.grid.get(i).expect("i is in range");
+// the row from `col` on. the grid is in chars, the text is sliced in bytes
+pub fn from_col(row: &str, col: usize) -> &str {
+    let at = row.char_indices().nth(col).map_or(row.len(), |(at, _)| at);
+    &row[at..]
+}
 
-            let mut current_number: Vec<u32> = Vec::new();
-            let mut current_positions: Vec<(usize, usize)> = Vec::new();
+// the text at a cell of the schematic, for pointing errors at it
+pub fn text_at(input: &str, row: usize, col: usize) -> &str {
+    input
+        .lines()
+        .nth(row)
+        .map_or(input, |line| from_col(line, col))
+}
 
-            for j in 0..self.height {
-                let cell = row.get(j).expect("j is in range");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                match cell.is_ascii_digit() {
-                    true => {
-                        current_number.push(cell.to_digit(10).expect("It's a number"));
-                        current_positions.push((i, j));
-                    }
-                    false => {
-                        if !current_number.is_empty() {
-                            out.push(Number {
-                                value: current_number.iter().fold(0, |acc, curr| acc * 10 + curr),
-                                position: current_positions.clone(),
-                            });
-                            current_number = Vec::new();
-                            current_positions = Vec::new();
-                        }
-                    }
-                }
-            }
-            if !current_number.is_empty() {
-                out.push(Number {
-                    value: current_number.iter().fold(0, |acc, curr| acc * 10 + curr),
-                    position: current_positions.clone(),
-                });
-            }
-        }
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
 
-        out
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u32> {
+        numbers.map(|number| number.value).collect()
     }
 
-    pub fn compute_gear(
-        &self,
-        row: usize,
-        col: usize,
-        position_map: &HashMap<usize, &Number>,
-    ) -> u32 {
-        let mut surrounding_numbers: Vec<u32> = Vec::new();
-
-        let (row_min, row_max, col_min, col_max) = self.get_min_max(row, col);
-
-        for i in row_min..row_max {
-            for j in col_min..col_max {
-                if let Some(cell) = self.get_cell(i, j) {
-                    if cell.is_ascii_digit() {
-                        if let Some(number) = position_map.get(&hash_coord(i, j)) {
-                            if surrounding_numbers.contains(&number.value) {
-                                continue;
-                            }
-                            surrounding_numbers.push(number.value);
-                        }
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_numbers() {
+        let schematic: Schematic = EXAMPLE.parse().unwrap();
+        assert_eq!(10, schematic.numbers().len());
+        assert_eq!(
+            Number {
+                value: 633,
+                row: 2,
+                start: 6,
+                end: 9,
+            },
+            schematic.numbers()[3]
+        );
+        assert_eq!(vec![114, 58], values(schematic.unattached()));
+        assert_eq!(8, schematic.part_numbers().count());
+    }
 
-        if surrounding_numbers.len() == 2 {
-            return surrounding_numbers
-                .into_iter()
-                .reduce(|acc, curr| acc * curr)
-                .unwrap();
-        }
+    #[test]
+    fn test_symbols() {
+        let schematic: Schematic = EXAMPLE.parse().unwrap();
+        assert_eq!(6, schematic.symbols().len());
+        assert_eq!(vec![633], values(schematic.next_to('#')));
+        assert_eq!(vec![467, 35, 617, 755, 598], values(schematic.next_to('*')));
+        assert_eq!(vec![664], values(schematic.next_to('$')));
 
-        0
+        let gears: Vec<Vec<u32>> = schematic
+            .gears(2)
+            .map(|gear| values(schematic.adjacent(gear)))
+            .collect();
+        assert_eq!(vec![vec![467, 35], vec![755, 598]], gears);
+        let lonely: Vec<(usize, usize)> = schematic
+            .gears(1)
+            .map(|gear| (gear.row, gear.col))
+            .collect();
+        assert_eq!(vec![(4, 3)], lonely);
     }
-}
 
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub struct Number {
-    pub value: u32,
-    pub position: Vec<(usize, usize)>,
+    #[test]
+    fn test_repeated_numbers() {
+        // the same value either side is still two numbers, and rows needn't be as long as
+        // there are rows
+        let schematic: Schematic = "12*12.\n......".parse().unwrap();
+        let gears: Vec<Vec<u32>> = schematic
+            .gears(2)
+            .map(|gear| values(schematic.adjacent(gear)))
+            .collect();
+        assert_eq!(vec![vec![12, 12]], gears);
+        assert_eq!(Some('*'), schematic.get_cell(0, 2));
+    }
+
+    #[test]
+    fn test_bad_schematics() {
+        // rows are as wide as they have characters, not bytes
+        let schematic: Schematic = "1é\n1é".parse().unwrap();
+        assert_eq!(2, schematic.width);
+        assert_eq!(vec![1, 1], values(schematic.next_to('é')));
+
+        let input = "..99999999999*";
+        let error = input.parse::<Schematic>().unwrap_err().locate(input);
+        assert_eq!((1, 3), (error.line, error.column));
+    }
 }
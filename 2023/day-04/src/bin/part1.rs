@@ -1,9 +1,10 @@
+use aoc_common::{read_input, ParseError};
 use day_04::part1::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = process(&file)?;
     println!("{}", result);
     Ok(())
 }
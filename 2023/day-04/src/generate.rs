@@ -0,0 +1,72 @@
+use aoc_common::generate::{Generated, Rng};
+
+const WINNING: usize = 10;
+const HAVE: usize = 25;
+// copies multiply quickly, so cards stop winning once they have this many
+const MAX_COPIES: usize = 100_000;
+
+// `size` is the number of scratchcards
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let mut copies = vec![1; size];
+    let mut part1 = 0;
+
+    for card in 0..size {
+        let remaining = size - card - 1;
+        let matches = match rng.bool() || copies[card] > MAX_COPIES {
+            true => 0,
+            false => rng.usize(..=WINNING.min(remaining)),
+        };
+
+        let mut numbers: Vec<usize> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let winning = &numbers[..WINNING];
+        let mut have: Vec<usize> = winning[..matches]
+            .iter()
+            .chain(&numbers[WINNING..WINNING + HAVE - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut have);
+
+        let line = |numbers: &[usize]| {
+            let numbers: Vec<String> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+            numbers.join(" ")
+        };
+        input.push_str(&format!(
+            "Card {:>3}: {} | {}\n",
+            card + 1,
+            line(winning),
+            line(&have)
+        ));
+
+        if matches > 0 {
+            part1 += 1 << (matches - 1);
+        }
+        let won = copies[card];
+        copies[card + 1..=card + matches]
+            .iter_mut()
+            .for_each(|count| *count += won);
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(copies.iter().sum()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
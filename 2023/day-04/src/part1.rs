@@ -1,32 +1,17 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    input.lines().map(parse_card).sum()
-}
-
-fn parse_card(card: &str) -> usize {
-    let parts = card.split_once(": ").expect("It has a colon");
-
-    let numbers = parts.1.split_once(" | ").expect("It has a pipe");
+use crate::scratchcard::parse_cards;
+use aoc_common::ParseError;
 
-    let winner_numbers = numbers
-        .0
-        .split_ascii_whitespace()
-        .flat_map(|x| x.parse::<usize>())
-        .collect::<Vec<usize>>();
-    let my_numbers = numbers
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.parse::<usize>())
-        .collect::<Vec<usize>>();
-
-    intersect(&winner_numbers, &my_numbers)
-        .iter()
-        .fold(1, |acc, _curr| acc * 2)
-        >> 1
-}
-
-fn intersect(vec1: &[usize], vec2: &[usize]) -> Vec<usize> {
-    vec1.iter().filter(|x| vec2.contains(x)).copied().collect()
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let cards = parse_cards(input).map_err(|err| err.locate(input))?;
+    input
+        .lines()
+        .zip(&cards)
+        .try_fold(0usize, |total, (line, card)| {
+            card.points()
+                .and_then(|points| total.checked_add(points))
+                .ok_or_else(|| ParseError::new(line, "too many points to add up").locate(input))
+        })
 }
 
 #[cfg(test)]
@@ -41,6 +26,17 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(13, process(input));
+        assert_eq!(Ok(13), process(input));
+    }
+
+    #[test]
+    fn test_too_many_points() {
+        let numbers = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = format!("Card 1: 1 | 2\nCard 2: {numbers} | {numbers}");
+        let error = process(&input).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
     }
 }
@@ -1,41 +1,20 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let mut counts: Vec<usize> = lines.iter().enumerate().map(|_| 1).collect();
-
-    for i in 0..lines.len() {
-        let winners = parse_card(lines.get(i).expect("We can index this"));
-        let previous_count = counts[i];
-
-        for j in 1..=winners {
-            counts[i + j] += previous_count
-        }
-    }
-
-    counts.iter().sum()
-}
-
-fn parse_card(card: &str) -> usize {
-    let parts = card.split_once(": ").expect("It has a colon");
-
-    let numbers = parts.1.split_once(" | ").expect("It has a pipe");
-
-    let winner_numbers = numbers
-        .0
-        .split_ascii_whitespace()
-        .flat_map(|x| x.parse::<usize>())
-        .collect::<Vec<usize>>();
-    let my_numbers = numbers
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.parse::<usize>())
-        .collect::<Vec<usize>>();
-
-    intersect(&winner_numbers, &my_numbers).len()
-}
-
-fn intersect(vec1: &[usize], vec2: &[usize]) -> Vec<usize> {
-    vec1.iter().filter(|x| vec2.contains(x)).copied().collect()
+use crate::scratchcard::{copies, parse_cards};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let cards = parse_cards(input).map_err(|err| err.locate(input))?;
+    let too_many = |card: usize| {
+        let line = input.lines().nth(card).unwrap_or(input);
+        ParseError::new(line, "too many cards to add up").locate(input)
+    };
+    copies(&cards)
+        .map_err(too_many)?
+        .into_iter()
+        .enumerate()
+        .try_fold(0usize, |total, (card, count)| {
+            total.checked_add(count).ok_or_else(|| too_many(card))
+        })
 }
 
 #[cfg(test)]
@@ -50,6 +29,29 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(30, process(input));
+        assert_eq!(Ok(30), process(input));
+    }
+
+    #[test]
+    fn test_too_many_cards() {
+        let numbers = (1..=70)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = vec![format!("Card 1: {numbers} | {numbers}"); 70].join("\n");
+        let error = process(&input).unwrap_err();
+        assert_eq!((65, 1), (error.line, error.column));
+        // each count fits, but not all of them together: the first 64 cards add up to
+        // 2^64 - 1 and the last one is the one too many
+        let mut cards: Vec<String> = (1..64)
+            .rev()
+            .map(|matches| {
+                let numbers = (1..=matches).map(|n| n.to_string()).collect::<Vec<_>>();
+                format!("Card 1: {0} | {0}", numbers.join(" "))
+            })
+            .collect();
+        cards.extend(["Card 1: 1 | 2".to_string(), "Card 1: 1 | 2".to_string()]);
+        let error = process(&cards.join("\n")).unwrap_err();
+        assert_eq!((65, 1), (error.line, error.column));
     }
 }
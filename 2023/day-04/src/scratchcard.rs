@@ -0,0 +1,197 @@
+use aoc_common::parse::{number, split_once};
+use aoc_common::ParseError;
+use std::str::FromStr;
+
+// a set of numbers, where the ones below 128 are bits and anything bigger is kept sorted
+// to the side. the puzzle's numbers are all below 100, so the side list stays empty
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Numbers {
+    small: u128,
+    large: Vec<usize>,
+}
+
+impl Numbers {
+    pub fn insert(&mut self, n: usize) {
+        match n < 128 {
+            true => self.small |= 1 << n,
+            false => {
+                if let Err(at) = self.large.binary_search(&n) {
+                    self.large.insert(at, n);
+                }
+            }
+        }
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        match n < 128 {
+            true => self.small & (1 << n) != 0,
+            false => self.large.binary_search(&n).is_ok(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.small.count_ones() as usize + self.large.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // how many numbers are in both
+    pub fn common(&self, other: &Numbers) -> usize {
+        let large = match self.large.len() <= other.large.len() {
+            true => self.large.iter().filter(|&&n| other.contains(n)).count(),
+            false => other.large.iter().filter(|&&n| self.contains(n)).count(),
+        };
+        (self.small & other.small).count_ones() as usize + large
+    }
+}
+
+impl FromStr for Numbers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = Numbers::default();
+        for n in s.split_ascii_whitespace() {
+            numbers.insert(number(n)?);
+        }
+        Ok(numbers)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning: Numbers,
+    pub have: Numbers,
+}
+
+impl FromStr for Scratchcard {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (card, numbers) = split_once(s, ": ")?;
+        let id = card
+            .strip_prefix("Card")
+            .ok_or_else(|| ParseError::expected(card, "'Card'"))?;
+        let (winning, have) = split_once(numbers, " | ")?;
+        Ok(Scratchcard {
+            id: number(id.trim_start())?,
+            winning: winning.parse()?,
+            have: have.parse()?,
+        })
+    }
+}
+
+impl Scratchcard {
+    pub fn matches(&self) -> usize {
+        self.winning.common(&self.have)
+    }
+
+    // doubles for each match after the first, so past 64 of them it won't fit
+    pub fn points(&self) -> Option<usize> {
+        match self.matches() {
+            0 => Some(0),
+            matches => 1usize.checked_shl(u32::try_from(matches - 1).ok()?),
+        }
+    }
+}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Scratchcard>, ParseError> {
+    input.lines().map(str::parse).collect()
+}
+
+// how many of each card there are once every card's copies have won theirs. a card can
+// win copies of cards past the end of the table, and those are left out. fails with the
+// index of a card there are too many of to count
+pub fn copies(cards: &[Scratchcard]) -> Result<Vec<usize>, usize> {
+    let mut copies = vec![1usize; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let won = (i + 1)..(i + 1 + card.matches()).min(cards.len());
+        let count = copies[i];
+        for (index, later) in won.clone().zip(&mut copies[won]) {
+            *later = later.checked_add(count).ok_or(index)?;
+        }
+    }
+    Ok(copies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_cards() {
+        let cards = parse_cards(EXAMPLE).unwrap();
+        let matches: Vec<usize> = cards.iter().map(Scratchcard::matches).collect();
+        assert_eq!(vec![4, 2, 2, 1, 0, 0], matches);
+        let points: Vec<Option<usize>> = cards.iter().map(Scratchcard::points).collect();
+        assert_eq!(
+            vec![Some(8), Some(2), Some(2), Some(1), Some(0), Some(0)],
+            points
+        );
+        assert_eq!(3, cards[2].id);
+        assert_eq!(Ok(vec![1, 2, 4, 8, 14, 1]), copies(&cards));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let numbers: Numbers = "5 127 128 1000 5".parse().unwrap();
+        assert_eq!(4, numbers.len());
+        assert!(numbers.contains(127) && numbers.contains(1000));
+        assert!(!numbers.contains(6) && !numbers.contains(999));
+        let other: Numbers = "1000 127 3".parse().unwrap();
+        assert_eq!(2, numbers.common(&other));
+        assert!(Numbers::default().is_empty());
+    }
+
+    #[test]
+    fn test_wins_past_the_end() {
+        let cards = parse_cards("Card 1: 1 2 | 1 2\nCard 2: 3 4 5 | 3 4 5").unwrap();
+        assert_eq!(Ok(vec![1, 2]), copies(&cards));
+    }
+
+    #[test]
+    fn test_too_many_points() {
+        let card = |count: usize| {
+            let numbers = (1..=count)
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("Card 1: {numbers} | {numbers}")
+                .parse::<Scratchcard>()
+                .unwrap()
+        };
+        assert_eq!(Some(1 << 63), card(64).points());
+        assert_eq!(None, card(65).points());
+    }
+
+    #[test]
+    fn test_too_many_copies() {
+        // every card wins a copy of each one after it, so card n has 2^n copies
+        let numbers = (1..=70)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = vec![format!("Card 1: {numbers} | {numbers}"); 70].join("\n");
+        let cards = parse_cards(&input).unwrap();
+        assert_eq!(Err(64), copies(&cards));
+        assert_eq!(Ok(1 << 63), copies(&cards[..64]).map(|copies| copies[63]));
+    }
+
+    #[test]
+    fn test_bad_cards() {
+        let err = "Crad 1: 1 | 2".parse::<Scratchcard>().unwrap_err();
+        assert_eq!("expected 'Card', found 'Crad 1'", err.message);
+        let err = "Card 1: 1 x | 2".parse::<Scratchcard>().unwrap_err();
+        assert_eq!("expected a number, found 'x'", err.message);
+        assert!("Card 1: 1 2".parse::<Scratchcard>().is_err());
+    }
+}
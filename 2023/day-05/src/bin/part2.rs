@@ -1,13 +1,14 @@
+use aoc_common::{read_input, ParseError};
 use day_05::part2::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
+fn main() -> Result<(), ParseError> {
     use std::time::Instant;
 
-    let file = include_str!("../../input.txt");
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
 
     let now = Instant::now();
-    let result = process(file);
+    let result = process(&file)?;
     println!("{}", result);
 
     let elapsed = now.elapsed();
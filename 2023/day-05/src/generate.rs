@@ -0,0 +1,102 @@
+use aoc_common::generate::{Generated, Rng};
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+const SEED_RANGES: usize = 10;
+
+// `size` is the number of ranges in each map. values run up to size^4 * 5000,
+// which is about the size of the real thing for 30 ranges and small enough for
+// part2_dumb to get through for a handful
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let universe = (size.pow(4) * 5000).clamp(4 * SEED_RANGES, u32::MAX as usize);
+
+    let seeds = cuts(&mut rng, universe, 2 * SEED_RANGES);
+    let mut ranges: Vec<(usize, usize)> = seeds
+        .chunks(2)
+        .map(|pair| (pair[0], pair[1] - pair[0]))
+        .collect();
+    rng.shuffle(&mut ranges);
+    let seeds: Vec<String> = ranges
+        .iter()
+        .map(|(start, len)| format!("{} {}", start, len))
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    let mut locations: Vec<usize> = ranges
+        .iter()
+        .flat_map(|&(start, len)| [start, len])
+        .collect();
+    for name in MAPS {
+        // the map shuffles consecutive pieces of the whole range around
+        let mut bounds = cuts(&mut rng, universe, size - 1);
+        bounds.insert(0, 0);
+        bounds.push(universe);
+        let mut pieces: Vec<(usize, usize)> =
+            bounds.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut pieces);
+        let mut lines = Vec::new();
+        let mut dest = 0;
+        for (source, len) in pieces {
+            lines.push((dest, source, len));
+            dest += len;
+        }
+        rng.shuffle(&mut lines);
+
+        input.push_str(&format!("\n{} map:\n", name));
+        for (dest, source, len) in &lines {
+            input.push_str(&format!("{} {} {}\n", dest, source, len));
+        }
+        for location in locations.iter_mut() {
+            let (dest, source, _) = lines
+                .iter()
+                .find(|(_, source, len)| *source <= *location && *location < source + len)
+                .expect("The map covers everything");
+            *location = dest + (*location - source);
+        }
+    }
+
+    Generated {
+        input,
+        part1: locations.into_iter().min(),
+        part2: None,
+    }
+}
+
+// distinct, sorted points strictly between 0 and the end
+fn cuts(rng: &mut Rng, end: usize, count: usize) -> Vec<usize> {
+    let mut points = Vec::with_capacity(count);
+    while points.len() < count {
+        let point = rng.usize(1..end);
+        if !points.contains(&point) {
+            points.push(point);
+        }
+    }
+    points.sort();
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, part2_dumb};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 4);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(
+                part2_dumb::process(&generated.input),
+                part2::process(&generated.input)
+            );
+        }
+    }
+}
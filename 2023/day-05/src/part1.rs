@@ -1,31 +1,50 @@
+use aoc_common::parse::{number, split_once};
+use aoc_common::{blocks, ParseError};
 use std::str::FromStr;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let parts = input.split_once("\n\n").expect("Unix endings");
-    let seeds = parse_seeds(parts.0);
-    let mappings: Vec<Vec<Mapping>> = parts.1.split("\n\n").map(parse_maps).collect();
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (seeds, mappings) = tracing::info_span!("parse")
+        .in_scope(|| parse_input(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
 
-    seeds
+    Ok(seeds
         .iter()
         .map(|seed| apply_mappings(*seed, &mappings))
         .min()
-        .expect("Should be a min value")
+        .expect("There's at least one seed"))
 }
 
-fn parse_seeds(s: &str) -> Vec<usize> {
-    s.split_once(": ")
-        .unwrap()
+type ParseOutput = (Vec<usize>, Vec<Vec<Mapping>>);
+
+fn parse_input(input: &str) -> Result<ParseOutput, ParseError> {
+    let mut blocks = blocks(input);
+    let seeds = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(input, "the almanac is empty"))?;
+    let seeds = parse_seeds(seeds)?;
+    if seeds.is_empty() {
+        return Err(ParseError::expected(input, "at least one seed"));
+    }
+    let mappings = blocks
+        .map(parse_maps)
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    Ok((seeds, mappings))
+}
+
+fn parse_seeds(s: &str) -> Result<Vec<usize>, ParseError> {
+    split_once(s, ": ")?
         .1
         .split_ascii_whitespace()
-        .flat_map(|x| x.parse::<usize>())
+        .map(number::<usize>)
         .collect()
 }
 
-fn parse_maps(s: &str) -> Vec<Mapping> {
+fn parse_maps(s: &str) -> Result<Vec<Mapping>, ParseError> {
     s.lines()
         .skip(1)
-        .flat_map(|line| line.parse::<Mapping>())
+        .map(|line| line.parse::<Mapping>())
         .collect()
 }
 
@@ -55,25 +74,16 @@ impl Mapping {
 }
 
 impl FromStr for Mapping {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut numbers = s.split_ascii_whitespace();
-        let dest_start = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
-        let source_start = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
-        let range = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
+        let numbers = s
+            .split_ascii_whitespace()
+            .map(number::<usize>)
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        let [dest_start, source_start, range] = numbers[..] else {
+            return Err(ParseError::expected(s, "three numbers"));
+        };
         Ok(Mapping::new(dest_start, source_start, range))
     }
 }
@@ -149,6 +159,6 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-        assert_eq!(35, process(input));
+        assert_eq!(Ok(35), process(input));
     }
 }
@@ -1,37 +1,54 @@
+use aoc_common::parse::{number, split_once};
+use aoc_common::{blocks, ParseError};
 use std::str::FromStr;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let parts = input.split_once("\n\n").expect("Unix endings");
-    let seeds = parse_seeds(parts.0);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (seeds, mappings) = parse_input(input).map_err(|err| err.locate(input))?;
     dbg!(&seeds);
-    let mappings: Vec<Vec<Mapping>> = parts.1.split("\n\n").map(parse_maps).collect();
 
-    *(apply_mappings(seeds, &mappings)
+    Ok(*(apply_mappings(seeds, &mappings)
         .iter()
         .min()
-        .expect("It's a number"))
+        .expect("There's at least one seed")))
 }
 
-fn parse_seeds(s: &str) -> Vec<Interval> {
-    let seeds = s.split_once(": ").unwrap().1.split(' ');
-    let mut starts = Vec::new();
-    let mut ranges = Vec::new();
-    seeds.enumerate().for_each(|(i, val)| match i % 2 == 0 {
-        true => starts.push(val.parse::<usize>().expect("number")),
-        false => ranges.push(val.parse::<usize>().expect("number")),
-    });
-    starts
-        .iter()
-        .zip(ranges.iter())
-        .map(|(start, range)| Interval::new(*start, start + range))
-        .collect()
+type ParseOutput = (Vec<Interval>, Vec<Vec<Mapping>>);
+
+fn parse_input(input: &str) -> Result<ParseOutput, ParseError> {
+    let mut blocks = blocks(input);
+    let seeds = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(input, "the almanac is empty"))?;
+    let seeds = parse_seeds(seeds)?;
+    if seeds.is_empty() {
+        return Err(ParseError::expected(input, "at least one seed"));
+    }
+    let mappings = blocks
+        .map(parse_maps)
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    Ok((seeds, mappings))
+}
+
+fn parse_seeds(s: &str) -> Result<Vec<Interval>, ParseError> {
+    let seeds = split_once(s, ": ")?.1;
+    let numbers = seeds
+        .split(' ')
+        .map(number::<usize>)
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    if numbers.len() % 2 != 0 {
+        return Err(ParseError::expected(seeds, "pairs of seed numbers"));
+    }
+    Ok(numbers
+        .chunks(2)
+        .map(|pair| Interval::new(pair[0], pair[0] + pair[1]))
+        .collect())
 }
 
-fn parse_maps(s: &str) -> Vec<Mapping> {
+fn parse_maps(s: &str) -> Result<Vec<Mapping>, ParseError> {
     s.lines()
         .skip(1)
-        .flat_map(|line| line.parse::<Mapping>())
+        .map(|line| line.parse::<Mapping>())
         .collect()
 }
 
@@ -90,25 +107,16 @@ struct Mapping {
 }
 
 impl FromStr for Mapping {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut numbers = s.split_ascii_whitespace();
-        let dest_start = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
-        let source_start = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
-        let range = numbers
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("It's a number");
+        let numbers = s
+            .split_ascii_whitespace()
+            .map(number::<usize>)
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        let [dest_start, source_start, range] = numbers[..] else {
+            return Err(ParseError::expected(s, "three numbers"));
+        };
         Ok(Mapping {
             dest_start,
             source_start,
@@ -196,6 +204,6 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-        assert_eq!(46, process(input));
+        assert_eq!(Ok(46), process(input));
     }
 }
@@ -1,26 +1,41 @@
+use aoc_common::generate::bench_input;
 use day_06::*;
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
+fn input() -> String {
+    bench_input(env!("CARGO_MANIFEST_DIR"), || {
+        generate::generate(0, 4).input
+    })
+}
+
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",)));
+fn part1(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part1_brute() {
-    part1_brute::process(divan::black_box(include_str!("../input.txt",)));
+fn part1_brute(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1_brute::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",)));
+fn part2(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2_brute() {
-    part2_brute::process(divan::black_box(include_str!("../input.txt",)));
+fn part2_brute(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2_brute::process(divan::black_box(&input)).expect("Valid input"));
 }
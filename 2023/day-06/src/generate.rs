@@ -0,0 +1,45 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of races
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut times = String::from("Time:    ");
+    let mut distances = String::from("Distance:");
+    let mut part1 = 1;
+
+    for _ in 0..size {
+        // holding for `shortest` or longer, up to time - shortest, is the only way to win.
+        // the record is the distance from holding one millisecond less
+        let time = rng.usize(10..100);
+        let shortest = rng.usize(time / 10..=time / 2);
+        let record = (shortest - 1) * (time - shortest + 1);
+        times.push_str(&format!("{:>7}", time));
+        distances.push_str(&format!("{:>7}", record));
+        part1 *= time - 2 * shortest + 1;
+    }
+
+    Generated {
+        input: format!("{}\n{}\n", times, distances),
+        part1: Some(part1),
+        part2: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_brute, part2, part2_brute};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 3);
+            let part1 = generated.part1.map(|ways| ways as f64);
+            assert_eq!(part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part1, part1_brute::process(&generated.input).ok());
+
+            let brute = part2_brute::process(&generated.input).expect("Valid input");
+            assert_eq!(Ok(brute as f64), part2::process(&generated.input));
+        }
+    }
+}
@@ -3,3 +3,4 @@ pub mod part2;
 
 pub mod part1_brute;
 pub mod part2_brute;
+pub mod generate;
@@ -1,11 +1,14 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> f64 {
-    let races = parse_races(input);
+use aoc_common::parse::{number, split_once};
+use aoc_common::ParseError;
 
-    races
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<f64, ParseError> {
+    let races = parse_races(input).map_err(|err| err.locate(input))?;
+
+    Ok(races
         .iter()
         .map(|race| run_race(*race))
-        .fold(1_f64, |acc, curr| acc * curr)
+        .fold(1_f64, |acc, curr| acc * curr))
 }
 
 fn run_race(race: (f64, f64)) -> f64 {
@@ -38,30 +41,24 @@ fn prev_int(num: f64) -> f64 {
     num.floor()
 }
 
-fn parse_races(input: &str) -> Vec<(f64, f64)> {
-    let lines = input.split_once('\n').expect("Unix endings");
+fn parse_races(input: &str) -> Result<Vec<(f64, f64)>, ParseError> {
+    let lines = split_once(input, "\n")?;
 
-    let times: Vec<f64> = lines
-        .0
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.trim().parse::<f64>())
-        .collect();
-    let distances: Vec<f64> = lines
-        .1
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.trim().parse::<f64>())
-        .collect();
+    let times = parse_numbers(lines.0)?;
+    let distances = parse_numbers(lines.1)?;
 
-    times
+    Ok(times
         .iter()
         .zip(distances.iter())
         .map(|(x, y)| (*x, *y))
+        .collect())
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<f64>, ParseError> {
+    split_once(line, ": ")?
+        .1
+        .split_ascii_whitespace()
+        .map(number::<f64>)
         .collect()
 }
 
@@ -74,6 +71,6 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        assert_eq!(288.0, process(input));
+        assert_eq!(Ok(288.0), process(input));
     }
 }
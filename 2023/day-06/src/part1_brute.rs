@@ -1,8 +1,11 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let races = parse_races(input);
+use aoc_common::parse::{number, split_once};
+use aoc_common::ParseError;
 
-    races.iter().map(|race| run_race(*race)).product::<usize>()
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let races = parse_races(input).map_err(|err| err.locate(input))?;
+
+    Ok(races.iter().map(|race| run_race(*race)).product::<usize>())
 }
 
 fn run_race(race: (usize, usize)) -> usize {
@@ -17,30 +20,24 @@ fn run_race(race: (usize, usize)) -> usize {
     })
 }
 
-fn parse_races(input: &str) -> Vec<(usize, usize)> {
-    let lines = input.split_once('\n').expect("Unix endings");
+fn parse_races(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let lines = split_once(input, "\n")?;
 
-    let times: Vec<usize> = lines
-        .0
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.trim().parse::<usize>())
-        .collect();
-    let distances: Vec<usize> = lines
-        .1
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .flat_map(|x| x.trim().parse::<usize>())
-        .collect();
+    let times = parse_numbers(lines.0)?;
+    let distances = parse_numbers(lines.1)?;
 
-    times
+    Ok(times
         .iter()
         .zip(distances.iter())
         .map(|(x, y)| (*x, *y))
+        .collect())
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<usize>, ParseError> {
+    split_once(line, ": ")?
+        .1
+        .split_ascii_whitespace()
+        .map(number::<usize>)
         .collect()
 }
 
@@ -53,6 +50,6 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        assert_eq!(288, process(input));
+        assert_eq!(Ok(288), process(input));
     }
 }
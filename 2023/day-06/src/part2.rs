@@ -1,8 +1,11 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> f64 {
-    let race = parse_races(input);
+use aoc_common::parse::split_once;
+use aoc_common::ParseError;
 
-    run_race(race)
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<f64, ParseError> {
+    let race = parse_races(input).map_err(|err| err.locate(input))?;
+
+    Ok(run_race(race))
 }
 
 fn run_race(race: (f64, f64)) -> f64 {
@@ -35,32 +38,24 @@ fn prev_int(num: f64) -> f64 {
     num.floor()
 }
 
-fn parse_races(input: &str) -> (f64, f64) {
-    let lines = input.split_once('\n').expect("Unix endings");
+fn parse_races(input: &str) -> Result<(f64, f64), ParseError> {
+    let lines = split_once(input, "\n")?;
 
-    let time: f64 = lines
-        .0
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .collect::<Vec<&str>>()
-        .join("")
-        .parse()
-        .expect("It's a number");
+    let time = parse_kerned_number(lines.0)?;
+    let distance = parse_kerned_number(lines.1)?;
+
+    Ok((time, distance))
+}
 
-    let distance: f64 = lines
-        .1
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
+// the spaces between the digits are just bad kerning
+fn parse_kerned_number(line: &str) -> Result<f64, ParseError> {
+    let digits = split_once(line, ": ")?.1;
+    digits
         .split_ascii_whitespace()
         .collect::<Vec<&str>>()
         .join("")
         .parse()
-        .expect("It's a number");
-
-    (time, distance)
+        .map_err(|_| ParseError::expected(digits, "a number"))
 }
 
 #[cfg(test)]
@@ -72,6 +67,6 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        assert_eq!(71503.0, process(input));
+        assert_eq!(Ok(71503.0), process(input));
     }
 }
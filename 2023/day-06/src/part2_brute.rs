@@ -1,8 +1,11 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let race = parse_races(input);
+use aoc_common::parse::split_once;
+use aoc_common::ParseError;
 
-    run_race(race)
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let race = parse_races(input).map_err(|err| err.locate(input))?;
+
+    Ok(run_race(race))
 }
 
 fn run_race(race: (usize, usize)) -> usize {
@@ -19,32 +22,24 @@ fn run_race(race: (usize, usize)) -> usize {
     total
 }
 
-fn parse_races(input: &str) -> (usize, usize) {
-    let lines = input.split_once('\n').expect("Unix endings");
+fn parse_races(input: &str) -> Result<(usize, usize), ParseError> {
+    let lines = split_once(input, "\n")?;
 
-    let time: usize = lines
-        .0
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
-        .split_ascii_whitespace()
-        .collect::<Vec<&str>>()
-        .join("")
-        .parse()
-        .expect("It's a number");
+    let time = parse_kerned_number(lines.0)?;
+    let distance = parse_kerned_number(lines.1)?;
+
+    Ok((time, distance))
+}
 
-    let distance: usize = lines
-        .1
-        .split_once(": ")
-        .expect("Starts with a colon")
-        .1
+// the spaces between the digits are just bad kerning
+fn parse_kerned_number(line: &str) -> Result<usize, ParseError> {
+    let digits = split_once(line, ": ")?.1;
+    digits
         .split_ascii_whitespace()
         .collect::<Vec<&str>>()
         .join("")
         .parse()
-        .expect("It's a number");
-
-    (time, distance)
+        .map_err(|_| ParseError::expected(digits, "a number"))
 }
 
 #[cfg(test)]
@@ -56,6 +51,6 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        assert_eq!(71503, process(input));
+        assert_eq!(Ok(71503), process(input));
     }
 }
@@ -0,0 +1,30 @@
+use aoc_common::generate::{Generated, Rng};
+use std::collections::HashSet;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+// `size` is the number of hands, which are all different like in the real input
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+
+    while seen.len() < size {
+        // drawing from a few cards at a time gives more pairs and houses than pure chance
+        let pool: Vec<u8> = (0..rng.usize(2..=5))
+            .map(|_| CARDS[rng.usize(..CARDS.len())])
+            .collect();
+        let hand: String = (0..5)
+            .map(|_| pool[rng.usize(..pool.len())] as char)
+            .collect();
+        if seen.insert(hand.clone()) {
+            input.push_str(&format!("{} {}\n", hand, rng.usize(1..1000)));
+        }
+    }
+
+    Generated {
+        input,
+        part1: None,
+        part2: None,
+    }
+}
@@ -1,31 +1,39 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    let mut hands = parse_hands(_input);
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(_input))]
+pub fn process(_input: &str) -> Result<usize, ParseError> {
+    let mut hands = parse_hands(_input).map_err(|err| err.locate(_input))?;
     hands.sort();
 
-    hands
+    Ok(hands
         .iter()
         .rev()
         .enumerate()
         .map(|(rank, hand)| hand.bid * (rank + 1))
-        .sum::<usize>()
+        .sum::<usize>())
 }
 
-fn parse_hands(input: &str) -> Vec<Hand> {
+fn parse_hands(input: &str) -> Result<Vec<Hand>, ParseError> {
     input.lines().map(parse_hand).collect()
 }
 
-fn parse_hand(input: &str) -> Hand {
-    let parts = input.split_once(' ').expect("a space");
-    let bid = parts.1.parse::<usize>().expect("It's a number");
-    let cards: Vec<Rank> = parts.0.chars().map(Rank::new).collect();
+fn parse_hand(input: &str) -> Result<Hand, ParseError> {
+    let (cards_str, bid) = parse::split_once(input, " ")?;
+    let bid = parse::number::<usize>(bid)?;
+    let cards = cards_str
+        .char_indices()
+        .map(|(i, c)| Rank::new(c).ok_or_else(|| ParseError::expected(&cards_str[i..], "a card")))
+        .collect::<Result<Vec<Rank>, ParseError>>()?;
+    if cards.len() != 5 {
+        return Err(ParseError::expected(cards_str, "a hand of 5 cards"));
+    }
     let hand_type = HandType::new(&cards);
 
-    Hand {
+    Ok(Hand {
         cards,
         hand_type,
         bid,
-    }
+    })
 }
 
 #[derive(Eq, PartialEq, PartialOrd, Debug, Ord, Hash, Copy, Clone)]
@@ -121,8 +129,8 @@ enum Rank {
 }
 
 impl Rank {
-    fn new(c: char) -> Rank {
-        match c {
+    fn new(c: char) -> Option<Rank> {
+        let rank = match c {
             'A' => Rank::Ace,
             'K' => Rank::King,
             'Q' => Rank::Queen,
@@ -136,8 +144,9 @@ impl Rank {
             '4' => Rank::Four,
             '3' => Rank::Three,
             '2' => Rank::Two,
-            _ => panic!("Invalid character"),
-        }
+            _ => return None,
+        };
+        Some(rank)
     }
 
     fn index(&self) -> usize {
@@ -190,6 +199,6 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        assert_eq!(5905, process(input));
+        assert_eq!(Ok(5905), process(input));
     }
 }
@@ -0,0 +1,123 @@
+use aoc_common::generate::{Generated, Rng};
+
+const GHOSTS: usize = 6;
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// `size` is roughly the number of steps each ghost takes to reach its end node, and
+// can be at most 1300 before the three letter names run out. every node after a
+// start comes in a pair with the same exits, so either direction keeps a ghost on its
+// loop and it reaches the end after a prime number of steps
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    assert!((2..=1300).contains(&size), "Between 2 and 1300 steps");
+    let mut rng = Rng::with_seed(seed);
+
+    let directions: String = (0..rng.usize(250..300))
+        .map(|_| if rng.bool() { 'L' } else { 'R' })
+        .collect();
+
+    // inner nodes never end in 'A' or 'Z'
+    let mut inner: Vec<String> = prefixes()
+        .iter()
+        .flat_map(|prefix| {
+            LETTERS[1..25]
+                .iter()
+                .map(move |&c| format!("{}{}", prefix, c as char))
+        })
+        .collect();
+    rng.shuffle(&mut inner);
+    let mut ends: Vec<String> = prefixes()
+        .into_iter()
+        .filter(|prefix| prefix != "AA" && prefix != "ZZ")
+        .collect();
+    rng.shuffle(&mut ends);
+    ends.insert(0, String::from("AA"));
+
+    let mut lengths: Vec<usize> = Vec::new();
+    let mut candidate = size.max(3) * 4 / 5;
+    while lengths.len() < GHOSTS {
+        if is_prime(candidate) && rng.bool() {
+            lengths.push(candidate);
+        }
+        candidate += 1;
+    }
+    rng.shuffle(&mut lengths);
+
+    let mut nodes = Vec::new();
+    for (ghost, &length) in lengths.iter().enumerate() {
+        let start = format!("{}A", ends[ghost]);
+        let end = match ghost {
+            0 => String::from("ZZZ"),
+            _ => format!("{}Z", ends[ghost]),
+        };
+        let layers: Vec<(String, String)> = (1..length)
+            .map(|_| {
+                (
+                    inner.pop().expect("Enough names"),
+                    inner.pop().expect("Enough names"),
+                )
+            })
+            .collect();
+
+        nodes.push((start, layers[0].clone()));
+        for pair in layers.windows(2) {
+            nodes.push((pair[0].0.clone(), pair[1].clone()));
+            nodes.push((pair[0].1.clone(), pair[1].clone()));
+        }
+        let last = &layers[layers.len() - 1];
+        nodes.push((last.0.clone(), (end.clone(), end.clone())));
+        nodes.push((last.1.clone(), (end.clone(), end.clone())));
+        nodes.push((end, layers[0].clone()));
+    }
+    rng.shuffle(&mut nodes);
+
+    let mut input = format!("{}\n\n", directions);
+    for (node, (left, right)) in nodes {
+        let (left, right) = if rng.bool() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        input.push_str(&format!("{} = ({}, {})\n", node, left, right));
+    }
+
+    Generated {
+        input,
+        part1: Some(lengths[0]),
+        part2: Some(lengths.iter().product()),
+    }
+}
+
+fn prefixes() -> Vec<String> {
+    LETTERS
+        .iter()
+        .flat_map(|&a| {
+            LETTERS
+                .iter()
+                .map(move |&b| format!("{}{}", a as char, b as char))
+        })
+        .collect()
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_hash, part2, part2_hash};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..3 {
+            let generated = generate(seed, 200);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part1, part1_hash::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2_hash::process(&generated.input).ok());
+        }
+    }
+}
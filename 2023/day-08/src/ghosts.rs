@@ -0,0 +1,396 @@
+use aoc_common::{parse, two_blocks, ParseError};
+use hashbrown::HashMap;
+
+// the nodes numbered in the order they're first named, with their exits
+pub struct Network<'a> {
+    names: Vec<&'a str>,
+    exits: Vec<[usize; 2]>,
+    at_end: Vec<bool>,
+    // 0 for left and 1 for right
+    directions: Vec<usize>,
+}
+
+// where a ghost is at an end node, once its walk is cut into the steps before it loops and
+// the loop. the state that repeats is the node with the place in the directions, not just
+// the node, so a ghost is on its loop from `pre_period` steps on and it's `period` long
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub pre_period: usize,
+    pub period: usize,
+    // steps before the loop that end on a 'Z'
+    pub early: Vec<usize>,
+    // how far into the loop each of its 'Z's are
+    pub offsets: Vec<usize>,
+}
+
+impl Cycle {
+    pub fn at_end(&self, step: usize) -> bool {
+        match step.checked_sub(self.pre_period) {
+            None => self.early.contains(&step),
+            Some(into) => self.offsets.contains(&(into % self.period)),
+        }
+    }
+
+    // the steps on the loop that are at an end, as residues of the smallest modulus that
+    // still says which they are. a ghost at an end every so many steps all the way round
+    // has the one residue, rather than one for each time round
+    pub fn residues(&self) -> (Vec<usize>, usize) {
+        let mut steps: Vec<usize> = self
+            .offsets
+            .iter()
+            .map(|offset| (self.pre_period + offset) % self.period)
+            .collect();
+        steps.sort_unstable();
+        for modulus in (1..self.period).filter(|&modulus| self.period.is_multiple_of(modulus)) {
+            let repeats = self.period / modulus;
+            let next = |step: &usize| (step + modulus) % self.period;
+            if steps.len().is_multiple_of(repeats)
+                && steps
+                    .iter()
+                    .all(|step| steps.binary_search(&next(step)).is_ok())
+            {
+                let mut residues: Vec<usize> = steps.iter().map(|step| step % modulus).collect();
+                residues.sort_unstable();
+                residues.dedup();
+                return (residues, modulus);
+            }
+        }
+        (steps, self.period)
+    }
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Network<'a>, ParseError> {
+        let (directions, nodes) = two_blocks(input)?;
+        let directions = directions
+            .char_indices()
+            .map(|(i, c)| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(ParseError::expected(&directions[i..], "'L' or 'R'")),
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+        let mut numbers: HashMap<&str, usize> = HashMap::new();
+        let mut names = Vec::new();
+        let mut number = |name: &'a str| {
+            *numbers.entry(name).or_insert_with(|| {
+                names.push(name);
+                names.len() - 1
+            })
+        };
+        let mut exits = Vec::new();
+        let mut defined = Vec::new();
+        for line in nodes.lines() {
+            let (source, targets) = parse::split_once(line, " = ")?;
+            let (left, right) = targets
+                .strip_prefix('(')
+                .and_then(|t| t.strip_suffix(')'))
+                .and_then(|t| t.split_once(", "))
+                .ok_or_else(|| ParseError::expected(targets, "'(left, right)'"))?;
+            let source = number(source);
+            let node_exits = [number(left), number(right)];
+            if exits.len() <= source {
+                exits.resize(source + 1, [0, 0]);
+                defined.resize(source + 1, false);
+            }
+            exits[source] = node_exits;
+            defined[source] = true;
+        }
+        exits.resize(names.len(), [0, 0]);
+        defined.resize(names.len(), false);
+        if let Some(missing) = defined.iter().position(|&defined| !defined) {
+            let name = names[missing];
+            let mentioned = nodes
+                .lines()
+                .find_map(|line| {
+                    let (_, targets) = line.split_once(" = ")?;
+                    targets.find(name).map(|at| &targets[at..])
+                })
+                .unwrap_or(nodes);
+            return Err(ParseError::new(
+                mentioned,
+                format!("there's no node {name}"),
+            ));
+        }
+        if directions.is_empty() {
+            return Err(ParseError::new(input, "there are no directions"));
+        }
+
+        let at_end = names.iter().map(|name| name.ends_with('Z')).collect();
+        Ok(Network {
+            names,
+            exits,
+            at_end,
+            directions,
+        })
+    }
+
+    pub fn starts(&self) -> Vec<usize> {
+        (0..self.names.len())
+            .filter(|&node| self.names[node].ends_with('A'))
+            .collect()
+    }
+
+    pub fn name(&self, node: usize) -> &'a str {
+        self.names[node]
+    }
+
+    // the state is the node with the place in the directions, so the period is a whole
+    // number of passes through them, found from the node at the start of each pass. the loop
+    // can start part way through the pass before the first repeat, where the walk meets
+    // itself a period ahead
+    pub fn cycle(&self, start: usize) -> Cycle {
+        let pass = self.directions.len();
+        let unseen = usize::MAX;
+        let mut seen = vec![unseen; self.names.len()];
+        let mut starts = Vec::new();
+        let mut ends = Vec::new();
+        let mut node = start;
+        while seen[node] == unseen {
+            seen[node] = starts.len();
+            let steps = starts.len() * pass;
+            starts.push(node);
+            for (step, &direction) in self.directions.iter().enumerate() {
+                if self.at_end[node] {
+                    ends.push(steps + step);
+                }
+                node = self.exits[node][direction];
+            }
+        }
+        let first = seen[node];
+        let period = (starts.len() - first) * pass;
+
+        let mut pre_period = first * pass;
+        if first > 0 {
+            let (mut behind, mut ahead) = (starts[first - 1], starts[starts.len() - 1]);
+            let mut into = 0;
+            while behind != ahead {
+                let direction = self.directions[into];
+                behind = self.exits[behind][direction];
+                ahead = self.exits[ahead][direction];
+                into += 1;
+            }
+            pre_period = (first - 1) * pass + into;
+        }
+        Cycle {
+            pre_period,
+            period,
+            early: ends
+                .iter()
+                .copied()
+                .filter(|&end| end < pre_period)
+                .collect(),
+            offsets: ends
+                .iter()
+                .filter(|&&end| (pre_period..pre_period + period).contains(&end))
+                .map(|end| end - pre_period)
+                .collect(),
+        }
+    }
+}
+
+// the first step where every ghost is at an end node together, or None if they never are.
+// until every ghost is on its loop each step is tried, after that each ghost is at an end
+// on the steps matching one of its offsets, and those are combined with the chinese
+// remainder theorem, allowing for loop lengths that share factors
+pub fn first_together(cycles: &[Cycle]) -> Option<usize> {
+    if cycles.is_empty() {
+        return None;
+    }
+    let settled = cycles.iter().map(|cycle| cycle.pre_period).max()?;
+    if let Some(step) = (0..settled).find(|&step| cycles.iter().all(|c| c.at_end(step))) {
+        return Some(step);
+    }
+
+    let mut congruences = vec![(0, 1)];
+    for cycle in cycles {
+        let (residues, modulus) = cycle.residues();
+        let mut combined: Vec<(u128, u128)> = congruences
+            .iter()
+            .flat_map(|&congruence| {
+                residues
+                    .iter()
+                    .filter_map(move |&residue| crt(congruence, (residue as u128, modulus as u128)))
+            })
+            .collect();
+        combined.sort_unstable();
+        combined.dedup();
+        congruences = combined;
+    }
+
+    let settled = settled as u128;
+    congruences
+        .into_iter()
+        .map(|(residue, modulus)| match residue >= settled {
+            true => residue,
+            false => residue + (settled - residue).div_ceil(modulus) * modulus,
+        })
+        .min()
+        .and_then(|step| usize::try_from(step).ok())
+}
+
+// x = a (mod m) and x = b (mod n) as one congruence mod lcm(m, n), when they agree
+fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<(u128, u128)> {
+    let (g, p) = extended_gcd(m as i128, n as i128);
+    let difference = b as i128 - a as i128;
+    if difference % g != 0 {
+        return None;
+    }
+    let step = n as i128 / g;
+    let k = (difference / g % step * p).rem_euclid(step) as u128;
+    let lcm = m / g as u128 * n;
+    Some(((a + m * k) % lcm, lcm))
+}
+
+// gcd(a, b), with p so that a * p = gcd (mod b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_p, mut p) = (1, 0);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_p, p) = (p, old_p - quotient * p);
+    }
+    (old_r, old_p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn together(input: &str) -> Option<usize> {
+        let network = Network::parse(input).unwrap();
+        let cycles: Vec<Cycle> = network
+            .starts()
+            .into_iter()
+            .map(|start| network.cycle(start))
+            .collect();
+        first_together(&cycles)
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        let network = Network::parse(input).unwrap();
+        let cycles: Vec<Cycle> = network.starts().iter().map(|&s| network.cycle(s)).collect();
+        assert_eq!("11A", network.name(network.starts()[0]));
+        assert_eq!(
+            Cycle {
+                pre_period: 1,
+                period: 2,
+                early: vec![],
+                offsets: vec![1],
+            },
+            cycles[0]
+        );
+        assert_eq!(
+            Cycle {
+                pre_period: 1,
+                period: 6,
+                early: vec![],
+                offsets: vec![2, 5],
+            },
+            cycles[1]
+        );
+        assert_eq!(Some(6), first_together(&cycles));
+    }
+
+    #[test]
+    fn test_unaligned() {
+        // the first ghost is at an end after 2 steps and then every 3, the second after 1
+        // and then every 2, so the lcm of the first ends would say 2
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)";
+        assert_eq!(Some(5), together(input));
+
+        // only ever at an end before it loops
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11C, 11C)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)";
+        assert_eq!(Some(1), together(input));
+    }
+
+    #[test]
+    fn test_never_together() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        assert_eq!(None, together(input));
+        assert_eq!(None, first_together(&[]));
+    }
+
+    #[test]
+    fn test_residues() {
+        let cycle = Cycle {
+            pre_period: 3,
+            period: 12,
+            early: vec![],
+            offsets: vec![1, 5, 9],
+        };
+        assert_eq!((vec![0], 4), cycle.residues());
+        let cycle = Cycle {
+            offsets: vec![1, 2, 5, 6, 9, 10],
+            ..cycle
+        };
+        assert_eq!((vec![0, 1], 4), cycle.residues());
+        let cycle = Cycle {
+            offsets: vec![1, 2],
+            ..cycle
+        };
+        assert_eq!((vec![4, 5], 12), cycle.residues());
+        let cycle = Cycle {
+            offsets: vec![],
+            ..cycle
+        };
+        assert_eq!((vec![], 1), cycle.residues());
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((5, 6)), crt((2, 3), (1, 2)));
+        assert_eq!(Some((10, 12)), crt((4, 6), (2, 4)));
+        assert_eq!(None, crt((1, 6), (2, 4)));
+        assert_eq!(Some((3, 7)), crt((0, 1), (3, 7)));
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let err = Network::parse("L\n\nAAA = (BBB, BBB)").err().unwrap();
+        assert_eq!("there's no node BBB", err.message);
+    }
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = crate::generate::generate(seed, 100);
+            assert_eq!(generated.part2, together(&generated.input), "seed {seed}");
+        }
+    }
+}
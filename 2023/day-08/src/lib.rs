@@ -3,3 +3,7 @@ pub mod part2;
 
 pub mod part1_hash;
 pub mod part2_hash;
+
+pub mod part2_cycles;
+pub mod ghosts;
+pub mod generate;
@@ -1,8 +1,9 @@
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (directions, map) = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (directions, map) = parse_input(input).map_err(|err| err.locate(input))?;
 
     let mut steps = 0;
     let mut location = "AAA";
@@ -19,25 +20,24 @@ pub fn process(input: &str) -> usize {
         steps += 1
     }
 
-    steps
+    Ok(steps)
 }
 
-fn parse_input(input: &str) -> (Vec<Dir>, HashMap<&str, (&str, &str)>) {
-    let parts = input.split_once("\n\n").expect("unix endings");
-    let directions = parts.0.chars().map(Dir::new).collect();
+type ParseOutput<'a> = (Vec<Dir>, HashMap<&'a str, (&'a str, &'a str)>);
+
+fn parse_input(input: &str) -> Result<ParseOutput<'_>, ParseError> {
+    let (directions, nodes) = two_blocks(input)?;
+    let directions = parse_directions(directions)?;
 
     let mut outmap = HashMap::new();
 
-    parts.1.lines().for_each(|line| {
-        let halves = line.split_once(" = ").expect("It has an equals");
-        let source = halves.0;
-        let left = &halves.1[1..4];
-        let right = &halves.1[6..9];
+    for line in nodes.lines() {
+        let (source, left, right) = parse_node(line)?;
 
         outmap.insert(source, (left, right));
-    });
+    }
 
-    (directions, outmap)
+    Ok((directions, outmap))
 }
 
 #[derive(Debug)]
@@ -47,15 +47,31 @@ enum Dir {
 }
 
 impl Dir {
-    fn new(c: char) -> Dir {
+    fn new(c: char) -> Option<Dir> {
         match c {
-            'R' => Dir::R,
-            'L' => Dir::L,
-            _ => panic!("Not a valid char {}", c),
+            'R' => Some(Dir::R),
+            'L' => Some(Dir::L),
+            _ => None,
         }
     }
 }
 
+fn parse_directions(s: &str) -> Result<Vec<Dir>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| Dir::new(c).ok_or_else(|| ParseError::expected(&s[i..], "'L' or 'R'")))
+        .collect()
+}
+
+fn parse_node(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let (source, targets) = parse::split_once(line, " = ")?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| ParseError::expected(targets, "'(left, right)'"))?;
+    Ok((source, left, right))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,7 +87,7 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(2, process(input));
+        assert_eq!(Ok(2), process(input));
     }
 
     #[test]
@@ -81,6 +97,6 @@ ZZZ = (ZZZ, ZZZ)";
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(6, process(input));
+        assert_eq!(Ok(6), process(input));
     }
 }
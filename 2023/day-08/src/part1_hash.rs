@@ -1,9 +1,10 @@
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
 use std::hash::{Hash, Hasher};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (directions, map) = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (directions, map) = parse_input(input).map_err(|err| err.locate(input))?;
 
     let mut steps = 0;
     let mut location = Location {
@@ -29,25 +30,27 @@ pub fn process(input: &str) -> usize {
         steps += 1
     }
 
-    steps
+    Ok(steps)
 }
 
-fn parse_input(input: &str) -> (Vec<Dir>, HashMap<Location, (Location, Location)>) {
-    let parts = input.split_once("\n\n").expect("unix endings");
-    let directions = parts.0.chars().map(Dir::new).collect();
+type ParseOutput = (Vec<Dir>, HashMap<Location, (Location, Location)>);
+
+fn parse_input(input: &str) -> Result<ParseOutput, ParseError> {
+    let (directions, nodes) = two_blocks(input)?;
+    let directions = parse_directions(directions)?;
 
     let mut outmap = HashMap::new();
 
-    parts.1.lines().for_each(|line| {
-        let halves = line.split_once(" = ").expect("It has an equals");
-        let source = Location::new(halves.0);
-        let left = Location::new(&halves.1[1..4]);
-        let right = Location::new(&halves.1[6..9]);
+    for line in nodes.lines() {
+        let (source, left, right) = parse_node(line)?;
+        let source = Location::new(source)?;
+        let left = Location::new(left)?;
+        let right = Location::new(right)?;
 
         outmap.insert(source, (left, right));
-    });
+    }
 
-    (directions, outmap)
+    Ok((directions, outmap))
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
@@ -66,15 +69,15 @@ impl Hash for Location {
 }
 
 impl Location {
-    fn new(s: &str) -> Location {
-        if s.len() != 3 {
-            panic!("Should be length 3, {}", s.len());
+    fn new(s: &str) -> Result<Location, ParseError> {
+        if s.chars().count() != 3 {
+            return Err(ParseError::expected(s, "a 3 character location"));
         }
-        Location {
+        Ok(Location {
             a: s.chars().nth(0).expect("Exists"),
             b: s.chars().nth(1).expect("Exists"),
             c: s.chars().nth(2).expect("Exists"),
-        }
+        })
     }
 }
 
@@ -85,15 +88,31 @@ enum Dir {
 }
 
 impl Dir {
-    fn new(c: char) -> Dir {
+    fn new(c: char) -> Option<Dir> {
         match c {
-            'R' => Dir::R,
-            'L' => Dir::L,
-            _ => panic!("Not a valid char {}", c),
+            'R' => Some(Dir::R),
+            'L' => Some(Dir::L),
+            _ => None,
         }
     }
 }
 
+fn parse_directions(s: &str) -> Result<Vec<Dir>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| Dir::new(c).ok_or_else(|| ParseError::expected(&s[i..], "'L' or 'R'")))
+        .collect()
+}
+
+fn parse_node(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let (source, targets) = parse::split_once(line, " = ")?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| ParseError::expected(targets, "'(left, right)'"))?;
+    Ok((source, left, right))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,7 +128,7 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(2, process(input));
+        assert_eq!(Ok(2), process(input));
     }
 
     #[test]
@@ -119,6 +138,6 @@ ZZZ = (ZZZ, ZZZ)";
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(6, process(input));
+        assert_eq!(Ok(6), process(input));
     }
 }
@@ -1,8 +1,10 @@
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (directions, map, initial_locations) = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (directions, map, initial_locations) =
+        parse_input(input).map_err(|err| err.locate(input))?;
 
     let mut steps_counts: Vec<usize> = Vec::new();
     for location in initial_locations {
@@ -24,7 +26,7 @@ pub fn process(input: &str) -> usize {
         steps_counts.push(steps);
     }
 
-    steps_counts.iter().fold(1, |total, curr| lcm(total, *curr))
+    Ok(steps_counts.iter().fold(1, |total, curr| lcm(total, *curr)))
 }
 
 fn lcm(first: usize, second: usize) -> usize {
@@ -48,27 +50,24 @@ fn gcd(first: usize, second: usize) -> usize {
 
 type ParseOutput<'a> = (Vec<Dir>, HashMap<&'a str, (&'a str, &'a str)>, Vec<&'a str>);
 
-fn parse_input(input: &str) -> ParseOutput {
-    let parts = input.split_once("\n\n").expect("unix endings");
-    let directions = parts.0.chars().map(Dir::new).collect();
+fn parse_input(input: &str) -> Result<ParseOutput<'_>, ParseError> {
+    let (directions, nodes) = two_blocks(input)?;
+    let directions = parse_directions(directions)?;
 
     let mut outmap = HashMap::new();
     let mut initial_locations = Vec::new();
 
-    parts.1.lines().for_each(|line| {
-        let halves = line.split_once(" = ").expect("It has an equals");
-        let source = halves.0;
-        let left = &halves.1[1..4];
-        let right = &halves.1[6..9];
+    for line in nodes.lines() {
+        let (source, left, right) = parse_node(line)?;
 
         outmap.insert(source, (left, right));
 
         if source.ends_with('A') {
             initial_locations.push(source);
         }
-    });
+    }
 
-    (directions, outmap, initial_locations)
+    Ok((directions, outmap, initial_locations))
 }
 
 #[derive(Debug)]
@@ -78,15 +77,31 @@ enum Dir {
 }
 
 impl Dir {
-    fn new(c: char) -> Dir {
+    fn new(c: char) -> Option<Dir> {
         match c {
-            'R' => Dir::R,
-            'L' => Dir::L,
-            _ => panic!("Not a valid char {}", c),
+            'R' => Some(Dir::R),
+            'L' => Some(Dir::L),
+            _ => None,
         }
     }
 }
 
+fn parse_directions(s: &str) -> Result<Vec<Dir>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| Dir::new(c).ok_or_else(|| ParseError::expected(&s[i..], "'L' or 'R'")))
+        .collect()
+}
+
+fn parse_node(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let (source, targets) = parse::split_once(line, " = ")?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| ParseError::expected(targets, "'(left, right)'"))?;
+    Ok((source, left, right))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +118,6 @@ mod tests {
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
-        assert_eq!(6, process(input));
+        assert_eq!(Ok(6), process(input));
     }
 }
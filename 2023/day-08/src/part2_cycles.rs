@@ -0,0 +1,50 @@
+use crate::ghosts::{first_together, Cycle, Network};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let network = Network::parse(input).map_err(|err| err.locate(input))?;
+    let cycles: Vec<Cycle> = network
+        .starts()
+        .into_iter()
+        .map(|start| network.cycle(start))
+        .collect();
+    first_together(&cycles).ok_or_else(|| {
+        ParseError::new(
+            input,
+            "the ghosts are never all on a node ending in 'Z' at once",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        assert_eq!(Ok(6), process(input));
+    }
+
+    #[test]
+    fn test_never_together() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        assert!(process(input).is_err());
+    }
+}
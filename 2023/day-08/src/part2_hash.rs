@@ -1,12 +1,14 @@
-use rayon::prelude::*;
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
+use rayon::prelude::*;
 use std::hash::Hash;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (directions, map, initial_locations) = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (directions, map, initial_locations) =
+        parse_input(input).map_err(|err| err.locate(input))?;
 
-    initial_locations
+    let steps = initial_locations
         .par_iter()
         .map(|location| {
             let mut curr_location = *location;
@@ -25,7 +27,9 @@ pub fn process(input: &str) -> usize {
             }
             steps
         })
-        .reduce(|| 1, lcm)
+        .reduce(|| 1, lcm);
+
+    Ok(steps)
 }
 
 fn lcm(first: usize, second: usize) -> usize {
@@ -53,27 +57,27 @@ type ParseOutput = (
     Vec<Location>,
 );
 
-fn parse_input(input: &str) -> ParseOutput {
-    let parts = input.split_once("\n\n").expect("unix endings");
-    let directions = parts.0.chars().map(Dir::new).collect();
+fn parse_input(input: &str) -> Result<ParseOutput, ParseError> {
+    let (directions, nodes) = two_blocks(input)?;
+    let directions = parse_directions(directions)?;
 
     let mut outmap = HashMap::new();
     let mut initial_locations = Vec::new();
 
-    parts.1.lines().for_each(|line| {
-        let halves = line.split_once(" = ").expect("It has an equals");
-        let source = Location::new(halves.0);
-        let left = Location::new(&halves.1[1..4]);
-        let right = Location::new(&halves.1[6..9]);
+    for line in nodes.lines() {
+        let (source, left, right) = parse_node(line)?;
+        let source = Location::new(source)?;
+        let left = Location::new(left)?;
+        let right = Location::new(right)?;
 
         outmap.insert(source, (left, right));
 
         if source.value & 0xff == 1 {
             initial_locations.push(source);
         }
-    });
+    }
 
-    (directions, outmap, initial_locations)
+    Ok((directions, outmap, initial_locations))
 }
 
 #[derive(Eq, PartialEq, Clone, Copy, Hash)]
@@ -82,17 +86,17 @@ struct Location {
 }
 
 impl Location {
-    fn new(s: &str) -> Location {
-        if s.len() != 3 {
-            panic!("Should be length 3, {}", s.len());
+    fn new(s: &str) -> Result<Location, ParseError> {
+        if s.chars().count() != 3 {
+            return Err(ParseError::expected(s, "a 3 character location"));
         }
         let a = char_to_digit(s.chars().nth(0).expect("Exists"));
         let b = char_to_digit(s.chars().nth(1).expect("Exists"));
         let c = char_to_digit(s.chars().nth(2).expect("Exists"));
 
-        Location {
+        Ok(Location {
             value: a << 16 | b << 8 | c,
-        }
+        })
     }
 }
 
@@ -137,15 +141,31 @@ enum Dir {
 }
 
 impl Dir {
-    fn new(c: char) -> Dir {
+    fn new(c: char) -> Option<Dir> {
         match c {
-            'R' => Dir::R,
-            'L' => Dir::L,
-            _ => panic!("Not a valid char {}", c),
+            'R' => Some(Dir::R),
+            'L' => Some(Dir::L),
+            _ => None,
         }
     }
 }
 
+fn parse_directions(s: &str) -> Result<Vec<Dir>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| Dir::new(c).ok_or_else(|| ParseError::expected(&s[i..], "'L' or 'R'")))
+        .collect()
+}
+
+fn parse_node(line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let (source, targets) = parse::split_once(line, " = ")?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| ParseError::expected(targets, "'(left, right)'"))?;
+    Ok((source, left, right))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,6 +182,6 @@ mod tests {
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
-        assert_eq!(6, process(input));
+        assert_eq!(Ok(6), process(input));
     }
 }
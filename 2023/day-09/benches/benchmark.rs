@@ -1,26 +1,41 @@
+use aoc_common::generate::bench_input;
 use day_09::*;
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
+fn input() -> String {
+    bench_input(env!("CARGO_MANIFEST_DIR"), || {
+        generate::generate(0, 200).input
+    })
+}
+
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",)));
+fn part1(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part1_pascal() {
-    part1_pascal::process(divan::black_box(include_str!("../input.txt",)));
+fn part1_pascal(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1_pascal::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",)));
+fn part2(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2_pascal() {
-    part2_pascal::process(divan::black_box(include_str!("../input.txt",)));
+fn part2_pascal(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2_pascal::process(divan::black_box(&input)).expect("Valid input"));
 }
@@ -1,12 +1,13 @@
+use aoc_common::{read_input, ParseError};
 use day_09::part2::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = process(&file)?;
     println!("{}", result);
 
-    let result = day_09::part2_pascal::process(file);
+    let result = day_09::part2_pascal::process(&file)?;
     println!("{}", result);
     Ok(())
 }
@@ -0,0 +1,58 @@
+use aoc_common::generate::{Generated, Rng};
+
+const LENGTH: usize = 21;
+
+// `size` is the number of histories. each one is built up from a constant row of
+// differences, one level at a time, so the values either side of it come for free
+pub fn generate(seed: u64, size: usize) -> Generated<isize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size {
+        // one extra value at each end, the ones the puzzle asks for
+        let mut values = vec![rng.isize(-9..=9); LENGTH + 2];
+        for _ in 0..rng.usize(1..=12) {
+            let mut level = vec![rng.isize(-9..=9)];
+            for difference in &values[..=LENGTH] {
+                level.push(level[level.len() - 1] + difference);
+            }
+            values = level;
+        }
+
+        let history: Vec<String> = values[1..=LENGTH].iter().map(isize::to_string).collect();
+        input.push_str(&history.join(" "));
+        input.push('\n');
+        part1 += values[LENGTH + 1];
+        part2 += values[0];
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_pascal, part2, part2_pascal};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(
+                generated.part1,
+                part1_pascal::process(&generated.input).ok()
+            );
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(
+                generated.part2,
+                part2_pascal::process(&generated.input).ok()
+            );
+        }
+    }
+}
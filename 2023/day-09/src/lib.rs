@@ -5,3 +5,4 @@ pub mod part1_pascal;
 pub mod part2_pascal;
 
 pub mod pascal;
+pub mod generate;
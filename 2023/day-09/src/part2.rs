@@ -1,16 +1,17 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> isize {
-    input
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<isize, ParseError> {
+    let histories = input
         .lines()
         .map(parse_input)
-        .map(|nums| prev_num(&nums))
-        .sum()
+        .collect::<Result<Vec<Vec<isize>>, ParseError>>()
+        .map_err(|err| err.locate(input))?;
+    Ok(histories.iter().map(|nums| prev_num(nums)).sum())
 }
 
-fn parse_input(line: &str) -> Vec<isize> {
-    line.split_ascii_whitespace()
-        .flat_map(|x| x.parse::<isize>())
-        .collect()
+fn parse_input(line: &str) -> Result<Vec<isize>, ParseError> {
+    line.split_ascii_whitespace().map(parse::number).collect()
 }
 
 fn prev_num(nums: &[isize]) -> isize {
@@ -36,6 +37,6 @@ mod tests {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        assert_eq!(2, process(input));
+        assert_eq!(Ok(2), process(input));
     }
 }
@@ -1,19 +1,22 @@
 use crate::pascal::Pascal;
+use aoc_common::{parse, ParseError};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> isize {
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<isize, ParseError> {
     let mut pascal = Pascal::new();
-    input
+    let histories = input
         .lines()
         .map(parse_input)
-        .map(|nums| get_value(&nums, &mut pascal))
-        .sum()
+        .collect::<Result<Vec<Vec<isize>>, ParseError>>()
+        .map_err(|err| err.locate(input))?;
+    Ok(histories
+        .iter()
+        .map(|nums| get_value(nums, &mut pascal))
+        .sum())
 }
 
-fn parse_input(line: &str) -> Vec<isize> {
-    line.split_ascii_whitespace()
-        .flat_map(|x| x.parse::<isize>())
-        .collect()
+fn parse_input(line: &str) -> Result<Vec<isize>, ParseError> {
+    line.split_ascii_whitespace().map(parse::number).collect()
 }
 
 fn get_value(nums: &[isize], pascal: &mut Pascal) -> isize {
@@ -37,6 +40,6 @@ mod tests {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        assert_eq!(2, process(input));
+        assert_eq!(Ok(2), process(input));
     }
 }
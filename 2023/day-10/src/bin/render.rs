@@ -0,0 +1,10 @@
+use aoc_common::read_input;
+use day_10::maze::Maze;
+
+#[tracing::instrument]
+fn main() -> Result<(), day_10::maze::MazeError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let maze = Maze::parse(&file)?;
+    println!("{}", maze.render());
+    Ok(())
+}
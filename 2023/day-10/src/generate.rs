@@ -0,0 +1,113 @@
+use aoc_common::generate::{outline, region, Generated, Rng};
+
+const JUNK: &[u8] = b"|-LJ7F..";
+
+// `size` is the width and height of the sketch. the loop runs around a random region
+// drawn at half scale, so the enclosed area follows from Pick's theorem: the region
+// covers 4 tiles per cell, and the loop's own tiles take half a tile each off that
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let cells = size.max(5).saturating_sub(3) / 2;
+    let region = region(&mut rng, cells, cells, cells * cells * 3 / 5);
+    let area = 4 * region.iter().flatten().filter(|&&cell| cell).count();
+
+    let corners = outline(&region);
+    let mut path = Vec::new();
+    for (i, &(row, col)) in corners.iter().enumerate() {
+        let (next_row, next_col) = corners[(i + 1) % corners.len()];
+        let (mut row, mut col) = (2 * row + 1, 2 * col + 1);
+        let (next_row, next_col) = (2 * next_row + 1, 2 * next_col + 1);
+        while (row, col) != (next_row, next_col) {
+            path.push((row, col));
+            row = step(row, next_row);
+            col = step(col, next_col);
+        }
+    }
+
+    let mut grid: Vec<Vec<u8>> = (0..size)
+        .map(|_| (0..size).map(|_| JUNK[rng.usize(..JUNK.len())]).collect())
+        .collect();
+    for (i, &(row, col)) in path.iter().enumerate() {
+        let before = path[(i + path.len() - 1) % path.len()];
+        let after = path[(i + 1) % path.len()];
+        grid[row][col] = pipe((row, col), before, after);
+    }
+
+    // part2 counts crossings straight off the sketch, so the start goes on a pipe
+    // that doesn't lead north. junk next to it mustn't look like it joins on either
+    let starts: Vec<(usize, usize)> = path
+        .iter()
+        .copied()
+        .filter(|&(row, col)| b"-F7".contains(&grid[row][col]))
+        .collect();
+    let (row, col) = starts[rng.usize(..starts.len())];
+    for (r, c) in [
+        (row - 1, col),
+        (row + 1, col),
+        (row, col - 1),
+        (row, col + 1),
+    ] {
+        if !path.contains(&(r, c)) {
+            grid[r][c] = b'.';
+        }
+    }
+    grid[row][col] = b'S';
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in grid {
+        input.push_str(&String::from_utf8(line).expect("Ascii"));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(path.len() / 2),
+        part2: Some(area - path.len() / 2 + 1),
+    }
+}
+
+fn step(from: usize, to: usize) -> usize {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Less => from + 1,
+        std::cmp::Ordering::Greater => from - 1,
+        std::cmp::Ordering::Equal => from,
+    }
+}
+
+fn pipe(at: (usize, usize), before: (usize, usize), after: (usize, usize)) -> u8 {
+    let side = |other: (usize, usize)| match (other.0.cmp(&at.0), other.1.cmp(&at.1)) {
+        (std::cmp::Ordering::Less, _) => 'N',
+        (std::cmp::Ordering::Greater, _) => 'S',
+        (_, std::cmp::Ordering::Greater) => 'E',
+        _ => 'W',
+    };
+    match (side(before), side(after)) {
+        ('N', 'S') | ('S', 'N') => b'|',
+        ('E', 'W') | ('W', 'E') => b'-',
+        ('N', 'E') | ('E', 'N') => b'L',
+        ('N', 'W') | ('W', 'N') => b'J',
+        ('S', 'W') | ('W', 'S') => b'7',
+        _ => b'F',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_no_map, part2, part2_no_map};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 41);
+            let part2 = generated.part2.map(|tiles| tiles as isize);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(
+                generated.part1,
+                part1_no_map::process(&generated.input).ok()
+            );
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(part2, part2_no_map::process(&generated.input).ok());
+        }
+    }
+}
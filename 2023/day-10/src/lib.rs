@@ -3,3 +3,6 @@ pub mod part2;
 
 pub mod part1_no_map;
 pub mod part2_no_map;
+
+pub mod maze;
+pub mod generate;
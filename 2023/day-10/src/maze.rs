@@ -0,0 +1,504 @@
+use aoc_common::{parse, ParseError};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Tile {
+    Loop,
+    Inside,
+    Outside,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MazeError {
+    NoStart,
+    MultipleStarts(Coord, Coord),
+    // fewer than two neighbours connect to the start, or none of the options close the loop
+    BrokenStart(Coord),
+    // more than one pipe could sit under the start and still close a loop
+    AmbiguousStart { start: Coord, pipes: Vec<char> },
+    BrokenLoop(Coord),
+}
+
+// the tiles are 1-based, like the line and col of a ParseError
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::NoStart => write!(f, "no start tile 'S' in the maze"),
+            MazeError::MultipleStarts(a, b) => {
+                write!(f, "more than one start tile, at {a} and {b}")
+            }
+            MazeError::BrokenStart(at) => write!(f, "no pipe at the start {at} forms a loop"),
+            MazeError::AmbiguousStart { start, pipes } => {
+                write!(f, "the start {start} could be any of {pipes:?}")
+            }
+            MazeError::BrokenLoop(at) => write!(f, "the loop is broken after {at}"),
+        }
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.row + 1, self.col + 1)
+    }
+}
+
+impl std::error::Error for MazeError {}
+
+impl MazeError {
+    // the tile the problem was found at, if it's down to one
+    pub fn at(&self) -> Option<Coord> {
+        match self {
+            MazeError::NoStart | MazeError::MultipleStarts(..) => None,
+            MazeError::BrokenStart(at) | MazeError::BrokenLoop(at) => Some(*at),
+            MazeError::AmbiguousStart { start, .. } => Some(*start),
+        }
+    }
+}
+
+// checks the sketch is a rectangle of known tiles with a single start,
+// returning the (row, col) of the start
+pub fn check_sketch(input: &str) -> Result<(usize, usize), ParseError> {
+    parse::grid(input, "|-LJ7F.S", "a pipe, '.' or 'S'")?;
+
+    let mut start = None;
+    for (row, line) in input.lines().enumerate() {
+        for (col, _) in line.match_indices('S') {
+            if start.is_some() {
+                return Err(ParseError::new(
+                    &line[col..],
+                    "more than one start tile 'S'",
+                ));
+            }
+            start = Some((row, col));
+        }
+    }
+
+    start.ok_or_else(|| ParseError::new(input, "no start tile 'S' in the sketch"))
+}
+
+// the maze for the parts, which report problems as a located ParseError like every other
+// day. a broken or ambiguous start comes from `Maze::parse`, pointed at the tile it's about
+pub fn parse_sketch(input: &str) -> Result<Maze, ParseError> {
+    check_sketch(input).map_err(|err| err.locate(input))?;
+    Maze::parse(input).map_err(|err| locate_error(input, err))
+}
+
+pub fn locate_error(input: &str, err: MazeError) -> ParseError {
+    let found = err
+        .at()
+        .and_then(|at| input.lines().nth(at.row)?.get(at.col..))
+        .unwrap_or(input);
+    ParseError::new(found, err.to_string()).locate(input)
+}
+
+// the start and the pipe under it, for the parts that walk the loop themselves. the pipe
+// comes from which of the four neighbours join onto the start, without tracing the loop
+// or allocating. when three or four of them do, only tracing tells which pair closes the
+// loop, so that's left to `Maze::parse`
+pub fn infer_start(input: &str) -> Result<(Coord, char), ParseError> {
+    let (row, col) = check_sketch(input).map_err(|err| err.locate(input))?;
+    let start = Coord { row, col };
+    let tile = |at: Coord| input.lines().nth(at.row)?.as_bytes().get(at.col).copied();
+
+    let mut open = DIRECTIONS.into_iter().filter(|dir| {
+        neighbour(start, *dir)
+            .and_then(tile)
+            .and_then(connections)
+            .is_some_and(|dirs| dirs.contains(&dir.opposite()))
+    });
+    match (open.next(), open.next(), open.next()) {
+        (Some(a), Some(b), None) => Ok((start, pipe_between(a, b) as char)),
+        (Some(_), Some(_), Some(_)) => parse_sketch(input).map(|maze| (start, maze.start_pipe())),
+        _ => Err(locate_error(input, MazeError::BrokenStart(start))),
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+impl Direction {
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+fn connections(pipe: u8) -> Option<[Direction; 2]> {
+    match pipe {
+        b'|' => Some([Direction::North, Direction::South]),
+        b'-' => Some([Direction::East, Direction::West]),
+        b'L' => Some([Direction::North, Direction::East]),
+        b'J' => Some([Direction::North, Direction::West]),
+        b'7' => Some([Direction::South, Direction::West]),
+        b'F' => Some([Direction::East, Direction::South]),
+        _ => None,
+    }
+}
+
+fn pipe_between(a: Direction, b: Direction) -> u8 {
+    *b"|-LJ7F"
+        .iter()
+        .find(|&&pipe| connections(pipe).is_some_and(|dirs| dirs.contains(&a) && dirs.contains(&b)))
+        .expect("Two different directions make a pipe")
+}
+
+#[derive(Debug)]
+pub struct Maze {
+    // the start is replaced by the pipe underneath it
+    grid: Vec<Vec<u8>>,
+    start: Coord,
+    path: Vec<Coord>,
+}
+
+impl Maze {
+    pub fn parse(input: &str) -> Result<Maze, MazeError> {
+        let mut grid: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+
+        let mut starts = grid.iter().enumerate().flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, &c)| c == b'S')
+                .map(move |(col, _)| Coord { row, col })
+        });
+        let start = starts.next().ok_or(MazeError::NoStart)?;
+        if let Some(other) = starts.next() {
+            return Err(MazeError::MultipleStarts(start, other));
+        }
+
+        let open: Vec<Direction> = DIRECTIONS
+            .into_iter()
+            .filter(|dir| {
+                step(&grid, start, *dir)
+                    .and_then(|next| connections(grid[next.row][next.col]))
+                    .is_some_and(|dirs| dirs.contains(&dir.opposite()))
+            })
+            .collect();
+
+        let mut loops = Vec::new();
+        for (i, a) in open.iter().enumerate() {
+            for b in &open[i + 1..] {
+                let pipe = pipe_between(*a, *b);
+                grid[start.row][start.col] = pipe;
+                if let Ok(path) = trace(&grid, start) {
+                    loops.push((pipe, path));
+                }
+            }
+        }
+
+        match loops.len() {
+            0 => Err(MazeError::BrokenStart(start)),
+            1 => {
+                let (pipe, path) = loops.pop().expect("There's one loop");
+                grid[start.row][start.col] = pipe;
+                Ok(Maze { grid, start, path })
+            }
+            _ => Err(MazeError::AmbiguousStart {
+                start,
+                pipes: loops.iter().map(|(pipe, _)| *pipe as char).collect(),
+            }),
+        }
+    }
+
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    pub fn start_pipe(&self) -> char {
+        self.grid[self.start.row][self.start.col] as char
+    }
+
+    // the loop in walking order, beginning at the start
+    pub fn path(&self) -> &[Coord] {
+        &self.path
+    }
+
+    // scans each row and counts how many times it crosses the loop, only pipes
+    // with a northern connection count so that F-J is one crossing but F-7 is none
+    pub fn classify(&self) -> Vec<Vec<Tile>> {
+        let mut tiles: Vec<Vec<Tile>> = self
+            .grid
+            .iter()
+            .map(|line| vec![Tile::Outside; line.len()])
+            .collect();
+        self.path
+            .iter()
+            .for_each(|coord| tiles[coord.row][coord.col] = Tile::Loop);
+
+        for (row, line) in tiles.iter_mut().enumerate() {
+            let mut inside = false;
+            for (col, tile) in line.iter_mut().enumerate() {
+                match tile {
+                    Tile::Loop => {
+                        if matches!(self.grid[row][col], b'|' | b'L' | b'J') {
+                            inside = !inside;
+                        }
+                    }
+                    _ if inside => *tile = Tile::Inside,
+                    _ => {}
+                }
+            }
+        }
+
+        tiles
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.classify()
+            .iter()
+            .flat_map(|line| line.iter())
+            .filter(|&&t| t == tile)
+            .count()
+    }
+
+    pub fn render(&self) -> String {
+        self.classify()
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                line.iter()
+                    .enumerate()
+                    .map(|(col, tile)| match tile {
+                        Tile::Loop => box_drawing(self.grid[row][col]),
+                        Tile::Inside => '█',
+                        Tile::Outside => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+fn box_drawing(pipe: u8) -> char {
+    match pipe {
+        b'|' => '│',
+        b'-' => '─',
+        b'L' => '└',
+        b'J' => '┘',
+        b'7' => '┐',
+        b'F' => '┌',
+        _ => panic!("Not a pipe: {}", pipe as char),
+    }
+}
+
+fn step(grid: &[Vec<u8>], coord: Coord, dir: Direction) -> Option<Coord> {
+    let next = neighbour(coord, dir)?;
+    (next.col < grid.get(next.row)?.len()).then_some(next)
+}
+
+// the tile next door, if it isn't off the top or left. the bottom and right are up to the
+// caller
+fn neighbour(coord: Coord, dir: Direction) -> Option<Coord> {
+    Some(match dir {
+        Direction::North => Coord {
+            row: coord.row.checked_sub(1)?,
+            col: coord.col,
+        },
+        Direction::East => Coord {
+            row: coord.row,
+            col: coord.col + 1,
+        },
+        Direction::South => Coord {
+            row: coord.row + 1,
+            col: coord.col,
+        },
+        Direction::West => Coord {
+            row: coord.row,
+            col: coord.col.checked_sub(1)?,
+        },
+    })
+}
+
+fn trace(grid: &[Vec<u8>], start: Coord) -> Result<Vec<Coord>, MazeError> {
+    let max_length = grid.iter().map(Vec::len).sum::<usize>();
+    let mut path = vec![start];
+    let mut pos = start;
+    let mut dir = connections(grid[start.row][start.col]).ok_or(MazeError::BrokenStart(start))?[0];
+
+    while path.len() <= max_length {
+        let next = step(grid, pos, dir).ok_or(MazeError::BrokenLoop(pos))?;
+        let [a, b] = connections(grid[next.row][next.col]).ok_or(MazeError::BrokenLoop(pos))?;
+        let entry = dir.opposite();
+        dir = match (a == entry, b == entry) {
+            (true, _) => b,
+            (_, true) => a,
+            _ => return Err(MazeError::BrokenLoop(pos)),
+        };
+        if next == start {
+            return Ok(path);
+        }
+        path.push(next);
+        pos = next;
+    }
+
+    Err(MazeError::BrokenLoop(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let input = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+        let maze = Maze::parse(input).expect("Valid maze");
+        assert_eq!('F', maze.start_pipe());
+        assert_eq!(16, maze.path().len());
+        assert_eq!(Coord { row: 2, col: 0 }, maze.path()[0]);
+        assert_eq!(8, maze.path().len() / 2);
+    }
+
+    #[test]
+    fn test_classify() {
+        let input = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+        let maze = Maze::parse(input).expect("Valid maze");
+        assert_eq!(8, maze.count(Tile::Inside));
+        assert_eq!(maze.path().len(), maze.count(Tile::Loop));
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let maze = Maze::parse(input).expect("Valid maze");
+        let expected = [
+            "           ",
+            " ┌───────┐ ",
+            " │┌─────┐│ ",
+            " ││     ││ ",
+            " ││     ││ ",
+            " │└─┐ ┌─┘│ ",
+            " │██│ │██│ ",
+            " └──┘ └──┘ ",
+            "           ",
+        ]
+        .join("\n");
+        assert_eq!(expected, maze.render());
+    }
+
+    #[test]
+    fn test_broken_start() {
+        assert_eq!(
+            Err(MazeError::NoStart),
+            Maze::parse("...\n.F7\n.LJ").map(|_| ())
+        );
+        assert_eq!(
+            Err(MazeError::BrokenStart(Coord { row: 1, col: 1 })),
+            Maze::parse("...\n.S-\n.LJ").map(|_| ())
+        );
+        assert_eq!(
+            Err(MazeError::BrokenStart(Coord { row: 0, col: 0 })),
+            Maze::parse("S-7\n|.|\nL-.").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_check_sketch() {
+        assert_eq!(Ok((1, 1)), check_sketch("...\n.S-\n.LJ"));
+        let input = "...\n.Sx\n.LJ";
+        assert_eq!(
+            "line 2, col 3: expected a pipe, '.' or 'S', found 'x'",
+            check_sketch(input).unwrap_err().locate(input).to_string()
+        );
+        assert_eq!(
+            "no start tile 'S' in the sketch",
+            check_sketch("...\n.F7\n.LJ").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_start() {
+        let input = "F7.
+LS7
+.LJ";
+        assert_eq!(
+            Err(MazeError::AmbiguousStart {
+                start: Coord { row: 1, col: 1 },
+                pipes: vec!['J', 'F'],
+            }),
+            Maze::parse(input).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_infer_start() {
+        let input = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...";
+        assert_eq!(Ok((Coord { row: 2, col: 0 }, 'F')), infer_start(input));
+        // three neighbours join on, so the loop is traced to pick two
+        let input = ".F7\nFSJ\n...";
+        assert_eq!(Ok((Coord { row: 1, col: 1 }, 'L')), infer_start(input));
+        let error = infer_start("...\n.S-\n...").unwrap_err();
+        assert_eq!(
+            "line 2, col 2: no pipe at the start 2,2 forms a loop",
+            error.to_string()
+        );
+        assert_eq!((2, 2), (error.line, error.column));
+        assert!(infer_start("F7.\nLS7\n.LJ").is_err());
+    }
+
+    #[test]
+    fn test_parse_sketch() {
+        assert_eq!(
+            4,
+            parse_sketch(".S7\n.LJ")
+                .map(|maze| maze.path().len())
+                .unwrap()
+        );
+        let error = parse_sketch("F7.\nLS7\n.LJ").unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+        assert_eq!(
+            "line 2, col 2: the start 2,2 could be any of ['J', 'F']",
+            error.to_string()
+        );
+        assert_eq!(
+            "line 1, col 1: no pipe at the start 1,1 forms a loop",
+            parse_sketch("S-7\n|.|\nL-.").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, col 3: expected a pipe, '.' or 'S', found 'x'",
+            parse_sketch("...\n.Sx").unwrap_err().to_string()
+        );
+    }
+}
@@ -1,7 +1,11 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    let (start, map) = parse_input(_input);
-    traverse(map, start)
+use crate::maze::infer_start;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(_input))]
+pub fn process(_input: &str) -> Result<usize, ParseError> {
+    let (start, start_pipe) = infer_start(_input)?;
+    let (start, map) = parse_input(_input, Coord::new(start.row, start.col), start_pipe);
+    Ok(traverse(map, start))
 }
 
 fn traverse(pipe_grid: PipeGrid, start: Coord) -> usize {
@@ -38,16 +42,19 @@ struct PipeGrid {
     grid: Vec<Vec<Option<Vec<Coord>>>>,
 }
 
-fn parse_input(input: &str) -> (Coord, PipeGrid) {
-    let mut start_pos = Coord { row: 0, col: 0 };
+fn parse_input(input: &str, start_pos: Coord, start_pipe: char) -> (Coord, PipeGrid) {
     let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
+    let width = input.lines().next().expect("The sketch isn't empty").len();
 
     let mut grid: Vec<Vec<Option<Vec<Coord>>>> = vec![vec![None; width]; height];
     input
         .lines()
         .enumerate()
         .flat_map(|(row, x)| x.chars().enumerate().map(move |(col, x)| (row, col, x)))
+        .map(|(row, col, x)| match x {
+            'S' => (row, col, start_pipe),
+            _ => (row, col, x),
+        })
         .filter(|(_row, _col, x)| x != &'.')
         .map(|(row, col, x)| (x, Coord::new(row, col)))
         .for_each(|(symbol, coord)| {
@@ -112,68 +119,7 @@ fn parse_input(input: &str) -> (Coord, PipeGrid) {
                     }
                     vec
                 }
-                //is the starting position of the animal;
-                //there is a pipe on this tile, but your sketch doesn't show what shape the pipe has.
-                'S' => {
-                    start_pos = Coord {
-                        row: coord.row,
-                        col: coord.col,
-                    };
-                    if coord.row > 0 {
-                        if let Some(north) = input
-                            .lines()
-                            .nth(coord.row - 1)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col)
-                        {
-                            if north == '|' || north == 'F' || north == '7' {
-                                vec.push(coord.neighbour(Direction::North));
-                            }
-                        }
-                    }
-                    if coord.row < (height - 1) {
-                        if let Some(south) = input
-                            .lines()
-                            .nth(coord.row + 1)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col)
-                        {
-                            if south == '|' || south == 'J' || south == 'L' {
-                                vec.push(coord.neighbour(Direction::South));
-                            }
-                        }
-                    }
-                    if coord.col < (width - 1) {
-                        if let Some(east) = input
-                            .lines()
-                            .nth(coord.row)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col + 1)
-                        {
-                            if east == '-' || east == 'J' || east == '7' {
-                                vec.push(coord.neighbour(Direction::East));
-                            }
-                        }
-                    }
-                    if coord.col > 0 {
-                        if let Some(west) = input
-                            .lines()
-                            .nth(coord.row)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col - 1)
-                        {
-                            if west == '-' || west == 'L' || west == 'F' {
-                                vec.push(coord.neighbour(Direction::West))
-                            }
-                        }
-                    }
-                    vec
-                }
-                _ => panic!("Unexpected symbol detected {}", symbol),
+                _ => unreachable!("The start has been replaced by its pipe {}", symbol),
             };
 
             grid[coord.row][coord.col] = Some(neighbours);
@@ -228,7 +174,7 @@ mod tests {
 .|.|.
 .L-J.
 .....";
-        assert_eq!(4, process(input));
+        assert_eq!(Ok(4), process(input));
     }
 
     #[test]
@@ -238,6 +184,6 @@ mod tests {
 SJ.L7
 |F--J
 LJ...";
-        assert_eq!(8, process(input));
+        assert_eq!(Ok(8), process(input));
     }
 }
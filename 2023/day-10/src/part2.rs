@@ -1,9 +1,13 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (start, map) = parse_input(input);
+use crate::maze::infer_start;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (start, start_pipe) = infer_start(input)?;
+    let (start, map) = parse_input(input, Coord::new(start.row, start.col), start_pipe);
     let visited = traverse(&map, start);
 
-    input
+    let inside = input
         .lines()
         .enumerate()
         .map(|(row, line)| {
@@ -14,6 +18,10 @@ pub fn process(input: &str) -> usize {
             let mut pipe_count = 0;
 
             line.chars().enumerate().for_each(|(col, char)| {
+                let char = match char {
+                    'S' => start_pipe,
+                    _ => char,
+                };
                 let coord = Coord::new(row, col);
                 match visited[coord.row][coord.col] {
                     true => match char {
@@ -25,7 +33,9 @@ pub fn process(input: &str) -> usize {
             });
             inside_count
         })
-        .sum()
+        .sum();
+
+    Ok(inside)
 }
 
 fn traverse(pipe_grid: &PipeGrid, start: Coord) -> Vec<Vec<bool>> {
@@ -60,16 +70,19 @@ struct PipeGrid {
     grid: Vec<Vec<Option<Vec<Coord>>>>,
 }
 
-fn parse_input(input: &str) -> (Coord, PipeGrid) {
-    let mut start_pos = Coord { row: 0, col: 0 };
+fn parse_input(input: &str, start_pos: Coord, start_pipe: char) -> (Coord, PipeGrid) {
     let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
+    let width = input.lines().next().expect("The sketch isn't empty").len();
 
     let mut grid: Vec<Vec<Option<Vec<Coord>>>> = vec![vec![None; width]; height];
     input
         .lines()
         .enumerate()
         .flat_map(|(row, x)| x.chars().enumerate().map(move |(col, x)| (row, col, x)))
+        .map(|(row, col, x)| match x {
+            'S' => (row, col, start_pipe),
+            _ => (row, col, x),
+        })
         .filter(|(_row, _col, x)| x != &'.')
         .map(|(row, col, x)| (x, Coord::new(row, col)))
         .for_each(|(symbol, coord)| {
@@ -134,68 +147,7 @@ fn parse_input(input: &str) -> (Coord, PipeGrid) {
                     }
                     vec
                 }
-                //is the starting position of the animal;
-                //there is a pipe on this tile, but your sketch doesn't show what shape the pipe has.
-                'S' => {
-                    start_pos = Coord {
-                        row: coord.row,
-                        col: coord.col,
-                    };
-                    if coord.row > 0 {
-                        if let Some(north) = input
-                            .lines()
-                            .nth(coord.row - 1)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col)
-                        {
-                            if north == '|' || north == 'F' || north == '7' {
-                                vec.push(coord.neighbour(Direction::North));
-                            }
-                        }
-                    }
-                    if coord.row < (height - 1) {
-                        if let Some(south) = input
-                            .lines()
-                            .nth(coord.row + 1)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col)
-                        {
-                            if south == '|' || south == 'J' || south == 'L' {
-                                vec.push(coord.neighbour(Direction::South));
-                            }
-                        }
-                    }
-                    if coord.col < (width - 1) {
-                        if let Some(east) = input
-                            .lines()
-                            .nth(coord.row)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col + 1)
-                        {
-                            if east == '-' || east == 'J' || east == '7' {
-                                vec.push(coord.neighbour(Direction::East));
-                            }
-                        }
-                    }
-                    if coord.col > 0 {
-                        if let Some(west) = input
-                            .lines()
-                            .nth(coord.row)
-                            .unwrap()
-                            .chars()
-                            .nth(coord.col - 1)
-                        {
-                            if west == '-' || west == 'L' || west == 'F' {
-                                vec.push(coord.neighbour(Direction::West))
-                            }
-                        }
-                    }
-                    vec
-                }
-                _ => panic!("Unexpected symbol detected {}", symbol),
+                _ => unreachable!("The start has been replaced by its pipe {}", symbol),
             };
 
             grid[coord.row][coord.col] = Some(neighbours);
@@ -254,7 +206,7 @@ mod tests {
 .|..|.|..|.
 .L--J.L--J.
 ...........";
-        assert_eq!(4, process(input));
+        assert_eq!(Ok(4), process(input));
     }
 
     #[test]
@@ -269,7 +221,7 @@ L--J.L7...LJS7F-7L7.
 .....|FJLJ|FJ|F7|.LJ
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
-        assert_eq!(8, process(input));
+        assert_eq!(Ok(8), process(input));
     }
 
     #[test]
@@ -284,6 +236,6 @@ L---JF-JLJ.||-FJLJJ7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
-        assert_eq!(10, process(input));
+        assert_eq!(Ok(10), process(input));
     }
 }
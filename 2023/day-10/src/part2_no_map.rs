@@ -1,20 +1,24 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> isize {
-    let (start, initial_dir, map) = parse_input(_input);
-    let (perimeter, corners) = traverse(map, start, initial_dir);
+use crate::maze::{self, infer_start, locate_error, MazeError};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(_input))]
+pub fn process(_input: &str) -> Result<isize, ParseError> {
+    let (start, initial_dir, map) = parse_input(_input)?;
+    let (perimeter, corners) =
+        traverse(map, start, initial_dir).map_err(|at| broken_loop(_input, at))?;
     let area = shoelace(corners);
-    area + 1 - (perimeter / 2) as isize
+    Ok(area + 1 - (perimeter / 2) as isize)
 }
 
+// fails with the last tile on the loop when it doesn't lead back to the start
 fn traverse(
     pipe_grid: Vec<Vec<char>>,
     start: Coord,
     initial_dir: Direction,
-) -> (usize, Vec<Coord>) {
+) -> Result<(usize, Vec<Coord>), Coord> {
     let mut pos = start;
     let mut dir = initial_dir;
-    pos = pos.travel(&dir);
-    let mut perimeter = 1;
+    let mut perimeter = 0;
 
     let mut corners = Vec::new();
 
@@ -22,19 +26,26 @@ fn traverse(
         corners.push(start);
     }
 
-    while pos != start {
-        let new_pipe = pipe_grid[pos.row][pos.col];
-        if new_pipe == 'F' || new_pipe == '7' || new_pipe == 'L' || new_pipe == 'J' {
-            corners.push(pos);
+    loop {
+        let next = pos.travel(&dir).ok_or(pos)?;
+        perimeter += 1;
+        if next == start {
+            break;
         }
-        dir = new_dir(&dir, new_pipe);
-        pos = pos.travel(&dir);
-        perimeter += 1
+        let new_pipe = *pipe_grid
+            .get(next.row)
+            .and_then(|line| line.get(next.col))
+            .ok_or(pos)?;
+        if is_corner(new_pipe) {
+            corners.push(next);
+        }
+        dir = new_dir(&dir, new_pipe).ok_or(pos)?;
+        pos = next;
     }
 
     corners.push(corners[0]);
 
-    (perimeter, corners)
+    Ok((perimeter, corners))
 }
 
 fn is_corner(c: char) -> bool {
@@ -51,87 +62,35 @@ fn shoelace(coords: Vec<Coord>) -> isize {
     isize::abs(total)
 }
 
-fn parse_input(input: &str) -> (Coord, Direction, Vec<Vec<char>>) {
-    let mut start_pos = Coord { row: 0, col: 0 };
+fn parse_input(input: &str) -> Result<(Coord, Direction, Vec<Vec<char>>), ParseError> {
+    let (start, start_pipe) = infer_start(input)?;
+    let start_pos = Coord {
+        row: start.row,
+        col: start.col,
+    };
 
     let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    let height = grid.len();
-    let width = grid[0].len();
-
-    for i in 0..height {
-        for j in 0..width {
-            if grid[i][j] == 'S' {
-                start_pos = Coord { row: i, col: j };
-
-                break;
-            }
-        }
-    }
-
-    let (initial_dir, tile) = handle_start(&start_pos, &grid, height, width);
-    grid[start_pos.row][start_pos.col] = tile;
-
-    (start_pos, initial_dir, grid)
-}
-
-fn handle_start(
-    start_pos: &Coord,
-    grid: &[Vec<char>],
-    height: usize,
-    width: usize,
-) -> (Direction, char) {
-    let north_neighbour = if start_pos.row == 0 {
-        '.'
-    } else {
-        grid[start_pos.row - 1][start_pos.col]
-    };
-    let south_neighbour = if start_pos.row == height - 1 {
-        '.'
-    } else {
-        grid[start_pos.row + 1][start_pos.col]
-    };
-    let east_neighbour = if start_pos.col == 0 {
-        '.'
-    } else {
-        grid[start_pos.row][start_pos.col - 1]
-    };
-    let west_neighbour = if start_pos.col == width - 1 {
-        '.'
-    } else {
-        grid[start_pos.row][start_pos.col + 1]
+    grid[start_pos.row][start_pos.col] = start_pipe;
+    let initial_dir = match start_pipe {
+        '|' | 'L' | 'J' => Direction::North,
+        '-' | 'F' => Direction::East,
+        _ => Direction::South,
     };
 
-    let valid_north = valid_neighbour(Direction::North, north_neighbour);
-    let valid_south = valid_neighbour(Direction::South, south_neighbour);
-    let valid_east = valid_neighbour(Direction::East, east_neighbour);
-    let valid_west = valid_neighbour(Direction::West, west_neighbour);
-
-    match (valid_north, valid_south, valid_east, valid_west) {
-        (true, true, false, false) => (Direction::North, '|'),
-        (true, false, true, false) => (Direction::North, 'L'),
-        (true, false, false, true) => (Direction::North, 'J'),
-        (false, true, true, false) => (Direction::South, 'F'),
-        (false, true, false, true) => (Direction::South, '7'),
-        (false, false, true, true) => (Direction::East, '-'),
-        _ => panic!("Failed to identify what the start pipe should be!"),
-    }
+    Ok((start_pos, initial_dir, grid))
 }
 
-fn valid_neighbour(dir: Direction, c: char) -> bool {
-    match (dir, c) {
-        (Direction::North, '|' | 'F' | '7') => true,
-        (Direction::North, _) => false,
-        (Direction::South, '|' | 'L' | 'J') => true,
-        (Direction::South, _) => false,
-        (Direction::East, '-' | 'F' | 'L') => true,
-        (Direction::East, _) => false,
-        (Direction::West, '-' | '7' | 'J') => true,
-        (Direction::West, _) => false,
-    }
+// the loop only goes as far as the tile at `at`
+fn broken_loop(input: &str, at: Coord) -> ParseError {
+    let at = maze::Coord {
+        row: at.row,
+        col: at.col,
+    };
+    locate_error(input, MazeError::BrokenLoop(at))
 }
 
-fn new_dir(dir: &Direction, c: char) -> Direction {
-    match (dir, c) {
+fn new_dir(dir: &Direction, c: char) -> Option<Direction> {
+    Some(match (dir, c) {
         (Direction::North, '|') => Direction::North,
         (Direction::North, 'F') => Direction::East,
         (Direction::North, '7') => Direction::West,
@@ -144,8 +103,8 @@ fn new_dir(dir: &Direction, c: char) -> Direction {
         (Direction::West, '-') => Direction::West,
         (Direction::West, 'L') => Direction::North,
         (Direction::West, 'F') => Direction::South,
-        (_, _) => panic!("Invalid dir char combo {:?} {}", dir, c),
-    }
+        _ => return None,
+    })
 }
 
 #[derive(Debug)]
@@ -163,10 +122,11 @@ struct Coord {
 }
 
 impl Coord {
-    fn travel(&self, direction: &Direction) -> Coord {
-        match direction {
+    // off the bottom or right is caught by the grid lookup after
+    fn travel(&self, direction: &Direction) -> Option<Coord> {
+        Some(match direction {
             Direction::North => Coord {
-                row: self.row - 1,
+                row: self.row.checked_sub(1)?,
                 col: self.col,
             },
             Direction::South => Coord {
@@ -179,9 +139,9 @@ impl Coord {
             },
             Direction::West => Coord {
                 row: self.row,
-                col: self.col - 1,
+                col: self.col.checked_sub(1)?,
             },
-        }
+        })
     }
 }
 
@@ -200,7 +160,7 @@ mod tests {
 .|..|.|..|.
 .L--J.L--J.
 ...........";
-        assert_eq!(4, process(input));
+        assert_eq!(Ok(4), process(input));
     }
 
     #[test]
@@ -215,7 +175,7 @@ L--J.L7...LJS7F-7L7.
 .....|FJLJ|FJ|F7|.LJ
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
-        assert_eq!(8, process(input));
+        assert_eq!(Ok(8), process(input));
     }
 
     #[test]
@@ -230,6 +190,17 @@ L---JF-JLJ.||-FJLJJ7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
-        assert_eq!(10, process(input));
+        assert_eq!(Ok(10), process(input));
+    }
+
+    #[test]
+    fn test_broken_start() {
+        // the only pipe that fits the start doesn't close the loop
+        let error = process("S-7\n|.|\nL-.").unwrap_err();
+        assert_eq!(
+            "line 2, col 3: the loop is broken after 2,3",
+            error.to_string()
+        );
+        assert!(process("F7.\nLS7\n.LJ").is_err());
     }
 }
@@ -1,21 +1,45 @@
+use aoc_common::generate::bench_input;
 use day_11::*;
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
+fn input() -> String {
+    bench_input(env!("CARGO_MANIFEST_DIR"), || {
+        generate::generate(0, 140).input
+    })
+}
+
+#[divan::bench]
+fn part1(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part1::process(divan::black_box(&input)).expect("Valid input"));
+}
+
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",)));
+fn part2(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",)));
+fn part2_parallel(bencher: divan::Bencher) {
+    let input = input();
+    bencher.bench(|| part2_parallel::process(divan::black_box(&input)).expect("Valid input"));
 }
 
 #[divan::bench]
-fn part2_parallel() {
-    part2_parallel::process(divan::black_box(include_str!("../input.txt",)));
+fn nearest_galaxies() {
+    let input = input();
+    let map = galaxy::GalaxyMap::parse(divan::black_box(&input)).expect("Valid image");
+    let expansion = galaxy::Expansion::uniform(1_000_000);
+    (0..map.len()).for_each(|galaxy| {
+        divan::black_box(map.nearest(galaxy, expansion));
+    });
 }
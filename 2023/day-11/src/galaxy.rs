@@ -0,0 +1,178 @@
+use aoc_common::{parse, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expansion {
+    // how many rows/cols each empty row/col becomes
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Expansion {
+    pub fn new(rows: usize, cols: usize) -> Expansion {
+        if rows == 0 || cols == 0 {
+            panic!("Expansion factors must be at least 1: {}x{}", rows, cols);
+        }
+        Expansion { rows, cols }
+    }
+
+    pub fn uniform(factor: usize) -> Expansion {
+        Expansion::new(factor, factor)
+    }
+}
+
+pub struct GalaxyMap {
+    // (row, col) before expansion, in reading order
+    galaxies: Vec<(usize, usize)>,
+    // number of empty rows/cols strictly before each index
+    empty_rows_before: Vec<usize>,
+    empty_cols_before: Vec<usize>,
+}
+
+impl GalaxyMap {
+    pub fn parse(input: &str) -> Result<GalaxyMap, ParseError> {
+        let width = parse::grid(input, ".#", "'.' or '#'")?;
+        let lines: Vec<&str> = input.lines().collect();
+
+        let mut galaxies = Vec::new();
+        let mut row_has_galaxy = vec![false; lines.len()];
+        let mut col_has_galaxy = vec![false; width];
+        for (row, line) in lines.iter().enumerate() {
+            // the columns are indexed by the first row's width
+            if line.len() != width {
+                let expected = format!("a row of width {}", width);
+                return Err(ParseError::expected(line, &expected));
+            }
+            for (col, &c) in line.as_bytes().iter().enumerate() {
+                if c == b'#' {
+                    galaxies.push((row, col));
+                    row_has_galaxy[row] = true;
+                    col_has_galaxy[col] = true;
+                }
+            }
+        }
+
+        Ok(GalaxyMap {
+            galaxies,
+            empty_rows_before: count_empty_before(&row_has_galaxy),
+            empty_cols_before: count_empty_before(&col_has_galaxy),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.galaxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.galaxies.is_empty()
+    }
+
+    pub fn position(&self, galaxy: usize, expansion: Expansion) -> (usize, usize) {
+        let (row, col) = self.galaxies[galaxy];
+        (
+            row + (expansion.rows - 1) * self.empty_rows_before[row],
+            col + (expansion.cols - 1) * self.empty_cols_before[col],
+        )
+    }
+
+    pub fn distance(&self, a: usize, b: usize, expansion: Expansion) -> usize {
+        let a = self.position(a, expansion);
+        let b = self.position(b, expansion);
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    // sum of the distances between every pair, each axis is handled separately
+    // by sorting and using the running total of the coordinates before it
+    pub fn total_distance(&self, expansion: Expansion) -> usize {
+        let (mut rows, mut cols): (Vec<usize>, Vec<usize>) = (0..self.len())
+            .map(|galaxy| self.position(galaxy, expansion))
+            .unzip();
+        rows.sort_unstable();
+        cols.sort_unstable();
+
+        sorted_pairwise_distance(&rows) + sorted_pairwise_distance(&cols)
+    }
+
+    // (galaxy, distance) of the closest other galaxy, ties go to the earliest in reading order
+    pub fn nearest(&self, galaxy: usize, expansion: Expansion) -> Option<(usize, usize)> {
+        (0..self.len())
+            .filter(|&other| other != galaxy)
+            .map(|other| (other, self.distance(galaxy, other, expansion)))
+            .min_by_key(|&(other, distance)| (distance, other))
+    }
+}
+
+fn count_empty_before(has_galaxy: &[bool]) -> Vec<usize> {
+    has_galaxy
+        .iter()
+        .scan(0, |empty, &has_galaxy| {
+            let before = *empty;
+            if !has_galaxy {
+                *empty += 1;
+            }
+            Some(before)
+        })
+        .collect()
+}
+
+fn sorted_pairwise_distance(coords: &[usize]) -> usize {
+    let mut total = 0;
+    let mut previous = 0;
+
+    for (i, value) in coords.iter().enumerate() {
+        total += i * value - previous;
+        previous += value;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_total_distance() {
+        let map = GalaxyMap::parse(INPUT).expect("Valid image");
+        assert_eq!(374, map.total_distance(Expansion::uniform(2)));
+        assert_eq!(1030, map.total_distance(Expansion::uniform(10)));
+        assert_eq!(8410, map.total_distance(Expansion::uniform(100)));
+    }
+
+    #[test]
+    fn test_per_axis_expansion() {
+        let map = GalaxyMap::parse(INPUT).expect("Valid image");
+        let expansion = Expansion::new(3, 7);
+        let brute_force: usize = (0..map.len())
+            .flat_map(|a| (a + 1..map.len()).map(move |b| (a, b)))
+            .map(|(a, b)| map.distance(a, b, expansion))
+            .sum();
+        assert_eq!(brute_force, map.total_distance(expansion));
+    }
+
+    #[test]
+    fn test_queries() {
+        let map = GalaxyMap::parse(INPUT).expect("Valid image");
+        let expansion = Expansion::uniform(2);
+        // galaxies 5 and 9 in the puzzle's numbering
+        assert_eq!(9, map.distance(4, 8, expansion));
+        assert_eq!((11, 5), map.position(8, expansion));
+        assert_eq!(Some((1, 6)), map.nearest(0, expansion));
+    }
+
+    #[test]
+    fn test_invalid_image() {
+        let error = GalaxyMap::parse("..#\n.#\n...").err().expect("Ragged rows");
+        assert_eq!("expected a row of width 3, found '.#'", error.to_string());
+    }
+}
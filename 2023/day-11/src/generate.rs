@@ -0,0 +1,74 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the width and height of the image. around one row and column in
+// fourteen is left blank, like the real thing, and the answers come from checking
+// every pair against the expanded coordinates
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let blank_rows: Vec<bool> = (0..size).map(|_| rng.usize(..14) == 0).collect();
+    let blank_cols: Vec<bool> = (0..size).map(|_| rng.usize(..14) == 0).collect();
+
+    let mut input = String::with_capacity(size * (size + 1));
+    let mut galaxies = Vec::new();
+    for (row, &blank_row) in blank_rows.iter().enumerate() {
+        for (col, &blank_col) in blank_cols.iter().enumerate() {
+            if !blank_row && !blank_col && rng.usize(..45) == 0 {
+                galaxies.push((row, col));
+                input.push('#');
+            } else {
+                input.push('.');
+            }
+        }
+        input.push('\n');
+    }
+
+    // other rows and columns can end up empty by chance too
+    let mut empty_rows = vec![true; size];
+    let mut empty_cols = vec![true; size];
+    for &(row, col) in &galaxies {
+        empty_rows[row] = false;
+        empty_cols[col] = false;
+    }
+
+    let total = |factor: usize| {
+        let expand = |empty: &[bool], at: usize| {
+            at + (factor - 1) * empty[..at].iter().filter(|&&empty| empty).count()
+        };
+        let expanded: Vec<(usize, usize)> = galaxies
+            .iter()
+            .map(|&(row, col)| (expand(&empty_rows, row), expand(&empty_cols, col)))
+            .collect();
+        let mut sum = 0;
+        for (i, a) in expanded.iter().enumerate() {
+            for b in &expanded[i + 1..] {
+                sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            }
+        }
+        sum
+    };
+
+    Generated {
+        input,
+        part1: Some(total(2)),
+        part2: Some(total(1_000_000)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, part2_parallel};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 60);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(
+                generated.part2,
+                part2_parallel::process(&generated.input).ok()
+            );
+        }
+    }
+}
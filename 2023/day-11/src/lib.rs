@@ -2,3 +2,6 @@ pub mod part1;
 pub mod part2;
 
 pub mod part2_parallel;
+
+pub mod galaxy;
+pub mod generate;
@@ -1,57 +1,10 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (galaxy_rows, galaxy_cols) = parse_input(input);
+use crate::galaxy::{Expansion, GalaxyMap};
+use aoc_common::ParseError;
 
-    distances(&galaxy_rows) + distances(&galaxy_cols)
-}
-
-fn distances(coord: &[usize]) -> usize {
-    let mut total = 0;
-    let mut previous = 0;
-
-    for (i, value) in coord.iter().enumerate() {
-        total += i * value - previous;
-        previous += value;
-    }
-
-    total
-}
-
-fn parse_input(input: &str) -> (Vec<usize>, Vec<usize>) {
-    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    let mut galaxy_rows = Vec::new();
-    let mut galaxy_cols = Vec::new();
-
-    let mut empty_row_count = 0;
-    for row in 0..grid.len() {
-        let mut row_empty = true;
-        for col in 0..grid[0].len() {
-            if grid[row][col] == '#' {
-                row_empty = false;
-                galaxy_rows.push(row + empty_row_count);
-            }
-        }
-        if row_empty {
-            empty_row_count += 1;
-        }
-    }
-
-    let mut empty_col_count = 0;
-    for col in 0..grid[0].len() {
-        let mut col_empty = true;
-        for row in &grid {
-            if row[col] == '#' {
-                galaxy_cols.push(col + empty_col_count);
-                col_empty = false;
-            }
-        }
-        if col_empty {
-            empty_col_count += 1;
-        }
-    }
-
-    (galaxy_rows, galaxy_cols)
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let map = GalaxyMap::parse(input).map_err(|err| err.locate(input))?;
+    Ok(map.total_distance(Expansion::uniform(2)))
 }
 
 #[cfg(test)]
@@ -70,6 +23,13 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(374, process(input));
+        assert_eq!(Ok(374), process(input));
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let error = process("#..\n...\n.#\n..#").unwrap_err();
+        assert_eq!((3, 1), (error.line, error.column));
+        assert!(process("#..\n...#").is_err());
     }
 }
@@ -1,61 +1,14 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    solve(input, 1_000_000)
-}
-
-fn solve(input: &str, scaling_factor: usize) -> usize {
-    let (galaxy_rows, galaxy_cols) = parse_input(input, scaling_factor);
-
-    distances(&galaxy_rows) + distances(&galaxy_cols)
-}
-
-fn distances(coord: &[usize]) -> usize {
-    let mut total = 0;
-    let mut previous = 0;
-
-    for (i, value) in coord.iter().enumerate() {
-        total += (i) * value - previous;
-        previous += value;
-    }
+use crate::galaxy::{Expansion, GalaxyMap};
+use aoc_common::ParseError;
 
-    total
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    solve(input, 1_000_000)
 }
 
-fn parse_input(input: &str, scaling_factor: usize) -> (Vec<usize>, Vec<usize>) {
-    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    let mut galaxy_rows = Vec::new();
-    let mut galaxy_cols = Vec::new();
-
-    let mut empty_row_count = 0;
-    for row in 0..grid.len() {
-        let mut row_empty = true;
-        for col in 0..grid[0].len() {
-            if grid[row][col] == '#' {
-                row_empty = false;
-                galaxy_rows.push(row + (scaling_factor - 1) * empty_row_count);
-            }
-        }
-        if row_empty {
-            empty_row_count += 1;
-        }
-    }
-
-    let mut empty_col_count = 0;
-    for col in 0..grid[0].len() {
-        let mut col_empty = true;
-        for row in &grid {
-            if row[col] == '#' {
-                galaxy_cols.push(col + (scaling_factor - 1) * empty_col_count);
-                col_empty = false;
-            }
-        }
-        if col_empty {
-            empty_col_count += 1;
-        }
-    }
-
-    (galaxy_rows, galaxy_cols)
+fn solve(input: &str, scaling_factor: usize) -> Result<usize, ParseError> {
+    let map = GalaxyMap::parse(input).map_err(|err| err.locate(input))?;
+    Ok(map.total_distance(Expansion::uniform(scaling_factor)))
 }
 
 #[cfg(test)]
@@ -74,7 +27,7 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(1030, solve(input, 10));
+        assert_eq!(Ok(1030), solve(input, 10));
     }
 
     #[test]
@@ -89,6 +42,6 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(8410, solve(input, 100));
+        assert_eq!(Ok(8410), solve(input, 100));
     }
 }
@@ -1,14 +1,15 @@
+use aoc_common::{parse, ParseError};
 use rayon::prelude::*;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
     solve(input, 1_000_000)
 }
 
-fn solve(input: &str, scaling_factor: usize) -> usize {
-    let (galaxies, empty_rows, empty_cols) = parse_input(input);
+fn solve(input: &str, scaling_factor: usize) -> Result<usize, ParseError> {
+    let (galaxies, empty_rows, empty_cols) = parse_input(input).map_err(|err| err.locate(input))?;
 
-    (0..galaxies.len())
+    let total = (0..galaxies.len())
         .into_par_iter()
         .flat_map(|x| {
             ((x + 1)..galaxies.len())
@@ -23,7 +24,9 @@ fn solve(input: &str, scaling_factor: usize) -> usize {
                 acc + distance(source, target, &empty_rows, &empty_cols, scaling_factor)
             },
         )
-        .sum()
+        .sum();
+
+    Ok(total)
 }
 
 fn distance(
@@ -48,7 +51,10 @@ fn distance(
     x_distance + y_distance
 }
 
-fn parse_input(input: &str) -> (Vec<(usize, usize)>, Vec<usize>, Vec<usize>) {
+type ParseOutput = (Vec<(usize, usize)>, Vec<usize>, Vec<usize>);
+
+fn parse_input(input: &str) -> Result<ParseOutput, ParseError> {
+    parse::grid(input, ".#", "'.' or '#'")?;
     let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
 
     let mut galaxies = Vec::new();
@@ -83,7 +89,7 @@ fn parse_input(input: &str) -> (Vec<(usize, usize)>, Vec<usize>, Vec<usize>) {
         empty_cols.push(empty_col_count);
     }
 
-    (galaxies, empty_rows, empty_cols)
+    Ok((galaxies, empty_rows, empty_cols))
 }
 
 #[cfg(test)]
@@ -102,7 +108,7 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(1030, solve(input, 10));
+        assert_eq!(Ok(1030), solve(input, 10));
     }
 
     #[test]
@@ -117,6 +123,6 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(8410, solve(input, 100));
+        assert_eq!(Ok(8410), solve(input, 100));
     }
 }
@@ -0,0 +1,83 @@
+use aoc_common::generate::{Generated, Rng};
+
+const MAX_UNKNOWN: usize = 14;
+
+// `size` is the number of rows. each row starts out as a real arrangement and then
+// has some springs hidden, at most 14 per row so part 1 can be brute forced
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut input = String::new();
+    let mut part1 = 0;
+
+    for _ in 0..size {
+        let len = rng.usize(6..=20);
+        let mut springs = vec![b'.'; len];
+        let mut at = rng.usize(..3);
+        while at < len {
+            let run = rng.usize(1..=6).min(len - at);
+            springs[at..at + run].fill(b'#');
+            at += run + rng.usize(1..=4);
+        }
+        let damaged = groups(&springs);
+
+        let mut unknown = 0;
+        for spring in springs.iter_mut() {
+            if unknown < MAX_UNKNOWN && rng.usize(..10) < 6 {
+                *spring = b'?';
+                unknown += 1;
+            }
+        }
+        part1 += arrangements(&mut springs, &damaged);
+
+        let damaged: Vec<String> = damaged.iter().map(usize::to_string).collect();
+        input.push_str(&format!(
+            "{} {}\n",
+            String::from_utf8(springs).expect("Ascii"),
+            damaged.join(",")
+        ));
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: None,
+    }
+}
+
+fn groups(springs: &[u8]) -> Vec<usize> {
+    springs
+        .split(|&spring| spring != b'#')
+        .filter(|run| !run.is_empty())
+        .map(<[u8]>::len)
+        .collect()
+}
+
+// tries every way of filling in the unknown springs
+fn arrangements(springs: &mut [u8], damaged: &[usize]) -> usize {
+    match springs.iter().position(|&spring| spring == b'?') {
+        None => usize::from(groups(springs) == damaged),
+        Some(unknown) => {
+            let mut count = 0;
+            for spring in [b'.', b'#'] {
+                springs[unknown] = spring;
+                count += arrangements(springs, damaged);
+            }
+            springs[unknown] = b'?';
+            count
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part1;
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..3 {
+            let generated = generate(seed, 50);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+        }
+    }
+}
@@ -1,20 +1,33 @@
+use aoc_common::{parse, ParseError};
 use rayon::prelude::*;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let lines:Vec<&str> = input.lines().collect();
-    lines.into_par_iter()
-        .flat_map(|line| line.split_once(' '))
-        .map(|(chars, numbers)| {
-            let springs = chars.chars().collect();
-            let damaged = numbers
-                .split(',')
-                .map(|x| x.parse().expect("It's a number"))
-                .collect();
-
-            Record::new(springs, damaged).check()
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let records = tracing::info_span!("parse")
+        .in_scope(|| {
+            input
+                .lines()
+                .map(parse_record)
+                .collect::<Result<Vec<(&str, Vec<u8>)>, ParseError>>()
         })
-        .sum()
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
+    Ok(records
+        .into_par_iter()
+        .map(|(springs, damaged)| Record::new(springs.chars().collect(), damaged).check())
+        .sum())
+}
+
+fn parse_record(line: &str) -> Result<(&str, Vec<u8>), ParseError> {
+    let (springs, numbers) = parse::split_once(line, " ")?;
+    if let Some(col) = springs.find(|c| !"?.#".contains(c)) {
+        return Err(ParseError::expected(&springs[col..], "'.', '#' or '?'"));
+    }
+    let damaged = numbers
+        .split(',')
+        .map(parse::number)
+        .collect::<Result<Vec<u8>, ParseError>>()?;
+    Ok((springs, damaged))
 }
 
 struct Record {
@@ -117,42 +130,42 @@ mod tests {
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
-        assert_eq!(21, process(input));
+        assert_eq!(Ok(21), process(input));
     }
 
     #[test]
     fn test_input_cases_1() {
         let input = "???.### 1,1,3";
-        assert_eq!(1, process(input));
+        assert_eq!(Ok(1), process(input));
     }
 
     #[test]
     fn test_input_cases_2() {
         let input = ".??..??...?##. 1,1,3";
-        assert_eq!(4, process(input));
+        assert_eq!(Ok(4), process(input));
     }
 
     #[test]
     fn test_input_cases_3() {
         let input = "?#?#?#?#?#?#?#? 1,3,1,6";
-        assert_eq!(1, process(input));
+        assert_eq!(Ok(1), process(input));
     }
 
     #[test]
     fn test_input_cases_4() {
         let input = "????.#...#... 4,1,1";
-        assert_eq!(1, process(input));
+        assert_eq!(Ok(1), process(input));
     }
 
     #[test]
     fn test_input_cases_5() {
         let input = "????.######..#####. 1,6,5";
-        assert_eq!(4, process(input));
+        assert_eq!(Ok(4), process(input));
     }
 
     #[test]
     fn test_input_cases_6() {
         let input = "?###???????? 3,2,1";
-        assert_eq!(10, process(input));
+        assert_eq!(Ok(10), process(input));
     }
 }
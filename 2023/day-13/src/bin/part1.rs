@@ -1,13 +1,14 @@
+use aoc_common::{read_input, ParseError};
 use day_13::part1::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
 
-    let result = process(file);
+    let result = process(&file)?;
     println!("{}", result);
 
-    let result = day_13::part1_hash::process(file);
+    let result = day_13::part1_hash::process(&file)?;
     println!("{}", result);
 
     Ok(())
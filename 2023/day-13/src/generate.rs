@@ -0,0 +1,117 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of patterns. each one is drawn symmetric about a column line
+// and a row line at once, then one tile is flipped where only the row line sees it.
+// that leaves the column line as the clean reflection and the row line one smudge off
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut patterns = Vec::with_capacity(size);
+    let (mut part1, mut part2) = (0, 0);
+
+    while patterns.len() < size {
+        let Some((mut grid, col_line, row_line)) = pattern(&mut rng) else {
+            continue;
+        };
+        let (mut clean, mut smudged) = (col_line, 100 * row_line);
+        if rng.bool() {
+            grid = transpose(&grid);
+            (clean, smudged) = (100 * col_line, row_line);
+        }
+        part1 += clean;
+        part2 += smudged;
+
+        let lines: Vec<String> = grid
+            .into_iter()
+            .map(|line| String::from_utf8(line).expect("Ascii"))
+            .collect();
+        patterns.push(lines.join("\n"));
+    }
+
+    Generated {
+        input: patterns.join("\n\n") + "\n",
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+// (grid, columns left of the clean line, rows above the smudged line)
+fn pattern(rng: &mut Rng) -> Option<(Vec<Vec<u8>>, usize, usize)> {
+    let (height, width) = (rng.usize(5..=17), rng.usize(5..=17));
+    let (col_line, row_line) = (rng.usize(1..width), rng.usize(1..height));
+
+    // tiles that have to match are filled in from the same random choice
+    let mirror = |at: usize, line: usize| (at >= line && at < 2 * line).then(|| 2 * line - 1 - at);
+    let mut grid = vec![vec![0; width]; height];
+    for row in 0..height {
+        for col in 0..width {
+            grid[row][col] = if let Some(mirror_row) = mirror(row, row_line) {
+                grid[mirror_row][col]
+            } else if let Some(mirror_col) = mirror(col, col_line) {
+                grid[row][mirror_col]
+            } else if rng.bool() {
+                b'#'
+            } else {
+                b'.'
+            };
+        }
+    }
+
+    // a tile beyond the reach of the column line, inside the reach of the row line
+    let cols: Vec<usize> = (0..width)
+        .filter(|&col| col >= 2 * col_line || col + width < 2 * col_line)
+        .collect();
+    let rows: Vec<usize> = (0..height)
+        .filter(|&row| row < 2 * row_line && 2 * row_line - 1 - row < height)
+        .collect();
+    if cols.is_empty() {
+        return None;
+    }
+    let col = cols[rng.usize(..cols.len())];
+    let row = rows[rng.usize(..rows.len())];
+    grid[row][col] = if grid[row][col] == b'#' { b'.' } else { b'#' };
+
+    // no other line can come close to reflecting
+    let columns = transpose(&grid);
+    let close = |grid: &[Vec<u8>], line: usize, (expected, smudges): (usize, usize)| match line
+        == expected
+    {
+        true => mismatches(grid, line) == smudges,
+        false => mismatches(grid, line) > 1,
+    };
+    let lines_ok = (1..height).all(|line| close(&grid, line, (row_line, 1)))
+        && (1..width).all(|line| close(&columns, line, (col_line, 0)));
+    lines_ok.then_some((grid, col_line, row_line))
+}
+
+// differences across a reflection between rows line - 1 and line
+fn mismatches(grid: &[Vec<u8>], line: usize) -> usize {
+    let above = grid[..line].iter().rev();
+    let below = grid[line..].iter();
+    above
+        .zip(below)
+        .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+        .sum()
+}
+
+fn transpose(grid: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    (0..grid[0].len())
+        .map(|col| grid.iter().map(|line| line[col]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_hash, part2, part2_hash};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 50);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part1, part1_hash::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2_hash::process(&generated.input).ok());
+        }
+    }
+}
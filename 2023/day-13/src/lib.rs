@@ -3,3 +3,6 @@ pub mod part2;
 
 pub mod part1_hash;
 pub mod part2_hash;
+
+pub mod mirror;
+pub mod generate;
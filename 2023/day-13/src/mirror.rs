@@ -0,0 +1,225 @@
+use aoc_common::{parse, ParseError};
+
+pub struct Pattern {
+    // bit c of rows[r] and bit r of cols[c] are set when (r, c) is a rock
+    rows: Vec<u64>,
+    cols: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    // a vertical line of reflection sits between two columns
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub row: usize,
+    pub col: usize,
+}
+
+// the two cells disagree, flipping either one of them fixes the reflection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Smudge {
+    pub cell: Cell,
+    pub reflected: Cell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    // the line sits just before this column/row
+    pub index: usize,
+    pub smudges: Vec<Smudge>,
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Vertical => self.index,
+            Axis::Horizontal => 100 * self.index,
+        }
+    }
+}
+
+impl Pattern {
+    pub fn parse(s: &str) -> Result<Pattern, ParseError> {
+        check_pattern(s)?;
+        let rows: Vec<u64> = s.lines().map(to_bitmask).collect();
+        let width = s.lines().next().map_or(0, str::len);
+
+        let cols = (0..width)
+            .map(|col| {
+                rows.iter()
+                    .enumerate()
+                    .filter(|(_, row)| *row & (1 << col) != 0)
+                    .fold(0, |acc, (r, _)| acc | 1 << r)
+            })
+            .collect();
+
+        Ok(Pattern { rows, cols })
+    }
+
+    // every line of reflection with exactly `smudges` mismatched pairs of cells
+    pub fn reflections(&self, smudges: usize) -> Vec<Reflection> {
+        let vertical = (1..self.cols.len())
+            .filter(|&index| mismatches(&self.cols, index, smudges) == smudges)
+            .map(|index| Reflection {
+                axis: Axis::Vertical,
+                index,
+                smudges: smudge_cells(&self.cols, index, |col, row| Cell { row, col }),
+            });
+
+        let horizontal = (1..self.rows.len())
+            .filter(|&index| mismatches(&self.rows, index, smudges) == smudges)
+            .map(|index| Reflection {
+                axis: Axis::Horizontal,
+                index,
+                smudges: smudge_cells(&self.rows, index, |row, col| Cell { row, col }),
+            });
+
+        vertical.chain(horizontal).collect()
+    }
+}
+
+fn to_bitmask(line: &str) -> u64 {
+    line.bytes()
+        .enumerate()
+        .filter(|(_, c)| *c == b'#')
+        .fold(0, |acc, (i, _)| acc | 1 << i)
+}
+
+// stops counting once it's past the limit as we only care about exact matches
+fn mismatches(lines: &[u64], index: usize, limit: usize) -> usize {
+    let mut total = 0;
+    for (lo, hi) in (0..index).rev().zip(index..lines.len()) {
+        total += (lines[lo] ^ lines[hi]).count_ones() as usize;
+        if total > limit {
+            break;
+        }
+    }
+    total
+}
+
+fn smudge_cells(lines: &[u64], index: usize, cell: impl Fn(usize, usize) -> Cell) -> Vec<Smudge> {
+    let mut smudges = Vec::new();
+    for (lo, hi) in (0..index).rev().zip(index..lines.len()) {
+        let mut diff = lines[lo] ^ lines[hi];
+        while diff != 0 {
+            let other = diff.trailing_zeros() as usize;
+            smudges.push(Smudge {
+                cell: cell(lo, other),
+                reflected: cell(hi, other),
+            });
+            diff &= diff - 1;
+        }
+    }
+    smudges
+}
+
+// a rectangle of '.' and '#' that fits in the bitmasks
+pub fn check_pattern(s: &str) -> Result<(), ParseError> {
+    let width = parse::grid(s, ".#", "'.' or '#'")?;
+    if width > 64 || s.lines().count() > 64 {
+        return Err(ParseError::expected(s, "a pattern no bigger than 64x64"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.";
+
+    const SECOND: &str = "#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_exact_reflections() {
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Vertical,
+                index: 5,
+                smudges: vec![],
+            }],
+            Pattern::parse(FIRST).expect("Valid pattern").reflections(0)
+        );
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                index: 4,
+                smudges: vec![],
+            }],
+            Pattern::parse(SECOND)
+                .expect("Valid pattern")
+                .reflections(0)
+        );
+    }
+
+    #[test]
+    fn test_smudged_reflections() {
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                index: 3,
+                smudges: vec![Smudge {
+                    cell: Cell { row: 0, col: 0 },
+                    reflected: Cell { row: 5, col: 0 },
+                }],
+            }],
+            Pattern::parse(FIRST).expect("Valid pattern").reflections(1)
+        );
+        assert_eq!(
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                index: 1,
+                smudges: vec![Smudge {
+                    cell: Cell { row: 0, col: 4 },
+                    reflected: Cell { row: 1, col: 4 },
+                }],
+            }],
+            Pattern::parse(SECOND)
+                .expect("Valid pattern")
+                .reflections(1)
+        );
+    }
+
+    #[test]
+    fn test_many_smudges() {
+        let reflections = Pattern::parse(SECOND)
+            .expect("Valid pattern")
+            .reflections(3);
+        assert!(reflections.iter().all(|r| r.smudges.len() == 3));
+        assert!(reflections.contains(&Reflection {
+            axis: Axis::Vertical,
+            index: 1,
+            smudges: vec![
+                Smudge {
+                    cell: Cell { row: 0, col: 0 },
+                    reflected: Cell { row: 0, col: 1 },
+                },
+                Smudge {
+                    cell: Cell { row: 1, col: 0 },
+                    reflected: Cell { row: 1, col: 1 },
+                },
+                Smudge {
+                    cell: Cell { row: 6, col: 0 },
+                    reflected: Cell { row: 6, col: 1 },
+                },
+            ],
+        }));
+    }
+}
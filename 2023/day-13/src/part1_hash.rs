@@ -1,12 +1,15 @@
+use crate::mirror::check_pattern;
+use aoc_common::{blocks, ParseError};
 use rayon::prelude::*;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let puzzles: Vec<&str> = input.split("\n\n").collect();
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let puzzles: Vec<&str> = blocks(input).collect();
 
     puzzles
         .par_iter()
         .map(|puzzle| {
+            check_pattern(puzzle)?;
             let grid: Vec<Vec<char>> = puzzle.lines().map(|line| line.chars().collect()).collect();
             let rows: Vec<u64> = grid.iter().map(|line| hash(line)).collect();
 
@@ -20,26 +23,28 @@ pub fn process(input: &str) -> usize {
             }
 
             find_symmetry(&MirrorMaze { rows, cols })
+                .ok_or_else(|| ParseError::new(puzzle, "a pattern with a line of reflection"))
         })
-        .sum()
+        .sum::<Result<usize, ParseError>>()
+        .map_err(|err| err.locate(input))
 }
 
-fn find_symmetry(mirrors: &MirrorMaze) -> usize {
+fn find_symmetry(mirrors: &MirrorMaze) -> Option<usize> {
     // find horizontal symmetry
     for i in 1..mirrors.cols.len() {
         if is_symmetric(&mirrors.cols, i) {
-            return i;
+            return Some(i);
         }
     }
 
     // find vertical symmetry
     for j in 1..mirrors.rows.len() {
         if is_symmetric(&mirrors.rows, j) {
-            return 100 * j;
+            return Some(100 * j);
         }
     }
 
-    panic!("Not symmetric")
+    None
 }
 
 fn is_symmetric(array: &[u64], index: usize) -> bool {
@@ -92,6 +97,6 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        assert_eq!(405, process(input));
+        assert_eq!(Ok(405), process(input));
     }
 }
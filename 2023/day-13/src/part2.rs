@@ -1,81 +1,20 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    let puzzles = parse_input(_input);
-    puzzles.iter().map(find_near_symmetry).sum()
-}
-
-fn find_near_symmetry(mirrors: &MirrorMaze) -> usize {
-    // find near horizontal symmetry
-    for i in 1..mirrors.col_based.len() {
-        if is_nearly_symmetric(&mirrors.col_based, i) {
-            return i;
-        }
-    }
-
-    // find near vertical symmetry
-    for j in 1..mirrors.row_based.len() {
-        if is_nearly_symmetric(&mirrors.row_based, j) {
-            return 100 * j;
-        }
-    }
-
-    panic!("No alternate found")
-}
-
-fn is_nearly_symmetric(array: &[Vec<char>], index: usize) -> bool {
-    let mut hi = index;
-    let mut lo = index;
-    let mut diffs = 0;
-
-    while hi < array.len() && lo > 0 && diffs < 2 {
-        let line_diffs = count_diffs(&array[hi], &array[lo - 1]);
-        if line_diffs > 1 {
-            return false;
-        }
-        diffs += line_diffs;
-        hi += 1;
-        lo -= 1;
-    }
-    diffs == 1
-}
-
-fn count_diffs(a: &[char], b: &[char]) -> usize {
-    let mut diffs = 0;
-    for i in 0..a.len() {
-        if a[i] != b[i] {
-            diffs += 1
-        }
-    }
-    diffs
-}
-
-struct MirrorMaze {
-    row_based: Vec<Vec<char>>,
-    col_based: Vec<Vec<char>>,
-}
-
-fn parse_input(input: &str) -> Vec<MirrorMaze> {
-    input
-        .split("\n\n")
-        .map(|puzzle| {
-            let row_based: Vec<Vec<char>> =
-                puzzle.lines().map(|line| line.chars().collect()).collect();
-
-            let mut col_based = Vec::with_capacity(row_based[0].len());
-            for col in 0..row_based[0].len() {
-                let mut new_col = Vec::with_capacity(row_based.len());
-                for row in &row_based {
-                    new_col.push(row[col]);
-                }
-                col_based.push(new_col);
-            }
-
-            MirrorMaze {
-                row_based,
-                col_based,
-            }
+use crate::mirror::{Pattern, Reflection};
+use aoc_common::{blocks, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    blocks(input)
+        .map(|block| {
+            Pattern::parse(block)?
+                .reflections(1)
+                .first()
+                .map(Reflection::summary)
+                .ok_or_else(|| {
+                    ParseError::new(block, "a pattern with a smudged line of reflection")
+                })
         })
-        .collect()
+        .sum::<Result<usize, ParseError>>()
+        .map_err(|err| err.locate(input))
 }
 
 #[cfg(test)]
@@ -99,6 +38,6 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        assert_eq!(400, process(input));
+        assert_eq!(Ok(400), process(input));
     }
 }
@@ -1,15 +1,16 @@
+use aoc_common::{read_input, ParseError};
 use day_14::*;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = part2::process(file);
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = part2::process(&file)?;
     println!("{}", result);
 
-    let result = part2_mut::process(file);
+    let result = part2_mut::process(&file)?;
     println!("{}", result);
 
-    let result = part2_hash::process(file);
+    let result = part2_hash::process(&file)?;
     println!("{}", result);
     Ok(())
 }
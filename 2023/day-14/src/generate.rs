@@ -0,0 +1,63 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the width and height of the platform, scattered with round and cube
+// rocks in about the real proportions. part 1 is scored column by column: round
+// rocks pile up under the nearest cube rock above them
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let grid: Vec<Vec<u8>> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| match rng.usize(..100) {
+                    0..=19 => b'O',
+                    20..=36 => b'#',
+                    _ => b'.',
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut part1 = 0;
+    for col in 0..size {
+        let mut next_gap = 0;
+        for (row, line) in grid.iter().enumerate() {
+            match line[col] {
+                b'#' => next_gap = row + 1,
+                b'O' => {
+                    part1 += size - next_gap;
+                    next_gap += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in grid {
+        input.push_str(&String::from_utf8(line).expect("Ascii"));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, part2_hash, part2_mut};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..3 {
+            let generated = generate(seed, 30);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            let part2 = part2::process(&generated.input);
+            assert_eq!(part2, part2_mut::process(&generated.input));
+            assert_eq!(part2, part2_hash::process(&generated.input));
+        }
+    }
+}
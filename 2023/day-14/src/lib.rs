@@ -3,3 +3,4 @@ pub mod part2;
 
 pub mod part2_mut;
 pub mod part2_hash;
+pub mod generate;
@@ -1,10 +1,15 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let grid = parse_grid(input);
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let grid = tracing::info_span!("parse")
+        .in_scope(|| parse_grid(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
 
     let grid = slide_north(&grid);
 
-    score(&grid)
+    Ok(score(&grid))
 }
 
 fn slide_north(old_grid: &Grid) -> Grid {
@@ -57,16 +62,16 @@ struct Grid {
     grid: Vec<Vec<char>>,
 }
 
-fn parse_grid(input: &str) -> Grid {
+fn parse_grid(input: &str) -> Result<Grid, ParseError> {
+    let width = parse::grid(input, "O#.", "'O', '#' or '.'")?;
     let grid: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
     let height = grid.len();
-    let width = grid[0].len();
 
-    Grid {
+    Ok(Grid {
         width,
         height,
         grid,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -85,6 +90,6 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(136, process(input));
+        assert_eq!(Ok(136), process(input));
     }
 }
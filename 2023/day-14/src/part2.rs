@@ -1,6 +1,8 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let grid = parse_grid(input);
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(input).map_err(|err| err.locate(input))?;
 
     let mut states = States::new(grid);
 
@@ -8,7 +10,7 @@ pub fn process(input: &str) -> usize {
     let period = states.find_loop_period();
     let index = (1_000_000_000 - start) % period + start;
 
-    states.scores[index].0
+    Ok(states.scores[index].0)
 }
 
 #[derive(Debug, Clone)]
@@ -264,16 +266,16 @@ struct Grid {
     grid: Vec<Vec<char>>,
 }
 
-fn parse_grid(input: &str) -> Grid {
+fn parse_grid(input: &str) -> Result<Grid, ParseError> {
+    let width = parse::grid(input, "O#.", "'O', '#' or '.'")?;
     let grid: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
     let height = grid.len();
-    let width = grid[0].len();
 
-    Grid {
+    Ok(Grid {
         width,
         height,
         grid,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -292,7 +294,7 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(64, process(input));
+        assert_eq!(Ok(64), process(input));
     }
 
     #[test]
@@ -319,9 +321,13 @@ O.#..O.#.#
 ......OOOO
 #...O###..
 #..OO#....",
-        );
+        )
+        .expect("Valid grid");
 
-        assert_eq!(output, spin_cycle(1, &parse_grid(input)));
+        assert_eq!(
+            output,
+            spin_cycle(1, &parse_grid(input).expect("Valid grid"))
+        );
     }
 
     #[test]
@@ -337,7 +343,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -350,7 +357,8 @@ O.#..O.#.#
 .......OOO
 #..OO###..
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
 
         assert_eq!(output, spin_cycle(2, &input));
     }
@@ -368,7 +376,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -381,7 +390,8 @@ O.#..O.#.#
 .......OOO
 #...O###.O
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
 
         assert_eq!(output, spin_cycle(3, &input));
     }
@@ -1,8 +1,12 @@
+use aoc_common::{parse, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let grid = &mut parse_grid(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let grid = &mut tracing::info_span!("parse")
+        .in_scope(|| parse_grid(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
     let mut seen: HashMap<Grid, usize> = HashMap::with_capacity(200);
 
     let mut idx = 0;
@@ -12,9 +16,11 @@ pub fn process(input: &str) -> usize {
     let start = loop {
         idx += 1;
         spin_cycle(grid);
-        if let Some(previous) = seen.insert(grid.clone(), idx) {
+        // keep the first index, the one the cycle maps back to
+        if let Some(&previous) = seen.get(grid) {
             break previous;
         }
+        seen.insert(grid.clone(), idx);
     };
 
     let period = idx - start;
@@ -22,7 +28,7 @@ pub fn process(input: &str) -> usize {
     let index = (1_000_000_000 - start) % period + start;
 
     let grid = seen.iter().find(|(_, &i)| i == index).unwrap().0;
-    score(grid)
+    Ok(score(grid))
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -168,16 +174,16 @@ struct Grid {
     grid: Vec<Vec<char>>,
 }
 
-fn parse_grid(input: &str) -> Grid {
+fn parse_grid(input: &str) -> Result<Grid, ParseError> {
+    let width = parse::grid(input, "O#.", "'O', '#' or '.'")?;
     let grid: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
     let height = grid.len();
-    let width = grid[0].len();
 
-    Grid {
+    Ok(Grid {
         width,
         height,
         grid,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -196,7 +202,7 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(64, process(input));
+        assert_eq!(Ok(64), process(input));
     }
 
     #[test]
@@ -212,7 +218,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -225,7 +232,8 @@ O.#..O.#.#
 ......OOOO
 #...O###..
 #..OO#....",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
 
         assert_eq!(output, input);
@@ -244,7 +252,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -257,7 +266,8 @@ O.#..O.#.#
 .......OOO
 #..OO###..
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
         spin_cycle(&mut input);
 
@@ -277,7 +287,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -290,7 +301,8 @@ O.#..O.#.#
 .......OOO
 #...O###.O
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
         spin_cycle(&mut input);
         spin_cycle(&mut input);
@@ -1,6 +1,8 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let grid = parse_grid(input);
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(input).map_err(|err| err.locate(input))?;
 
     let mut states = States::new(grid);
 
@@ -8,7 +10,7 @@ pub fn process(input: &str) -> usize {
     let period = states.find_loop_period();
     let index = (1_000_000_000 - start) % period + start;
 
-    states.scores[index].0
+    Ok(states.scores[index].0)
 }
 
 #[derive(Debug, Clone)]
@@ -226,16 +228,16 @@ struct Grid {
     grid: Vec<Vec<char>>,
 }
 
-fn parse_grid(input: &str) -> Grid {
+fn parse_grid(input: &str) -> Result<Grid, ParseError> {
+    let width = parse::grid(input, "O#.", "'O', '#' or '.'")?;
     let grid: Vec<Vec<char>> = input.lines().map(|x| x.chars().collect()).collect();
     let height = grid.len();
-    let width = grid[0].len();
 
-    Grid {
+    Ok(Grid {
         width,
         height,
         grid,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -254,7 +256,7 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(64, process(input));
+        assert_eq!(Ok(64), process(input));
     }
 
     #[test]
@@ -270,7 +272,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -283,7 +286,8 @@ O.#..O.#.#
 ......OOOO
 #...O###..
 #..OO#....",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
 
         assert_eq!(output, input);
@@ -302,7 +306,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -315,7 +320,8 @@ O.#..O.#.#
 .......OOO
 #..OO###..
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
         spin_cycle(&mut input);
 
@@ -335,7 +341,8 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....",
-        );
+        )
+        .expect("Valid grid");
 
         let output = parse_grid(
             ".....#....
@@ -348,7 +355,8 @@ O.#..O.#.#
 .......OOO
 #...O###.O
 #.OOO#...O",
-        );
+        )
+        .expect("Valid grid");
         spin_cycle(&mut input);
         spin_cycle(&mut input);
         spin_cycle(&mut input);
@@ -0,0 +1,69 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of steps. labels come from a pool a few times smaller than
+// that so lenses get replaced and removed, and the boxes are tracked alongside
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let labels: Vec<String> = (0..size / 8 + 1)
+        .map(|_| (0..rng.usize(2..=6)).map(|_| rng.lowercase()).collect())
+        .collect();
+
+    let mut steps = Vec::with_capacity(size);
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::new(); 256];
+    for _ in 0..size {
+        let label = labels[rng.usize(..labels.len())].as_str();
+        let lenses = &mut boxes[hash(label)];
+        let slot = lenses.iter().position(|&(other, _)| other == label);
+        if rng.usize(..3) == 0 {
+            steps.push(format!("{label}-"));
+            if let Some(slot) = slot {
+                lenses.remove(slot);
+            }
+        } else {
+            let focal = rng.usize(1..=9);
+            steps.push(format!("{label}={focal}"));
+            match slot {
+                Some(slot) => lenses[slot].1 = focal,
+                None => lenses.push((label, focal)),
+            }
+        }
+    }
+
+    let part1 = steps.iter().map(|step| hash(step)).sum();
+    let part2 = boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(number, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, (_, focal))| (number + 1) * (slot + 1) * focal)
+        })
+        .sum();
+
+    Generated {
+        input: steps.join(",") + "\n",
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+fn hash(step: &str) -> usize {
+    step.bytes()
+        .fold(0, |value, byte| (value + byte as usize) * 17 % 256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 500);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
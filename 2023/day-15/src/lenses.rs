@@ -0,0 +1,341 @@
+use aoc_common::{parse, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+pub fn hash(input: &str) -> usize {
+    input.bytes().fold(0, |mut total, c| {
+        total += usize::from(c);
+        total *= 17;
+        total %= 256;
+        total
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step<'a> {
+    Remove(&'a str),
+    Insert(&'a str, usize),
+}
+
+impl<'a> Step<'a> {
+    pub fn parse(instruction: &'a str) -> Result<Step<'a>, ParseError> {
+        if let Some(label) = instruction.strip_suffix('-') {
+            return Ok(Step::Remove(label));
+        }
+        let (label, focal) = parse::split_once(instruction, "=")?;
+        Ok(Step::Insert(label, parse::number(focal)?))
+    }
+}
+
+pub fn parse_steps(input: &str) -> Result<Vec<Step<'_>>, ParseError> {
+    input.trim().split(',').map(Step::parse).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lens<'a> {
+    pub label: &'a str,
+    pub focal: usize,
+}
+
+// the 256 boxes, with the focusing power kept up to date as lenses come and go.
+// a box's lenses stay in their slots when one is taken out, leaving a gap, and the box is
+// packed down once half of it is gaps. a lens's place in its box, and the focal lengths
+// behind it, come from running sums over the slots.
+// inserting, replacing and removing are O(log n) in the lenses in the box, not O(1)
+// amortised. taking a lens out lowers the power by the focal lengths behind it, so keeping
+// the total exact after every step means keeping prefix sums over the box, and those can't
+// be kept in O(1) an update. packing is O(n) once every n / 2 removals, so O(1) amortised
+#[derive(Debug, Clone)]
+pub struct Boxes<'a> {
+    boxes: Vec<LensBox<'a>>,
+    slot_of: HashMap<&'a str, usize>,
+    power: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LensBox<'a> {
+    slots: Vec<Option<Lens<'a>>>,
+    live: usize,
+    // 1 for each slot with a lens in it
+    lenses: Sums,
+    focals: Sums,
+}
+
+impl<'a> Boxes<'a> {
+    pub fn new() -> Boxes<'a> {
+        Boxes {
+            boxes: vec![LensBox::default(); 256],
+            slot_of: HashMap::new(),
+            power: 0,
+        }
+    }
+
+    pub fn apply(&mut self, step: Step<'a>) {
+        match step {
+            Step::Remove(label) => {
+                self.remove(label);
+            }
+            Step::Insert(label, focal) => self.insert(label, focal),
+        }
+    }
+
+    // replaces the lens with the same label where it is, or puts it at the back of its box
+    pub fn insert(&mut self, label: &'a str, focal: usize) {
+        let number = hash(label);
+        let lens_box = &mut self.boxes[number];
+        match self.slot_of.get(label) {
+            Some(&slot) => {
+                let lens = lens_box.slots[slot].as_mut().expect("Indexed lens");
+                let place = (number + 1) * lens_box.lenses.prefix(slot + 1);
+                self.power = self.power + place * focal - place * lens.focal;
+                lens_box.focals.add(slot, focal.wrapping_sub(lens.focal));
+                lens.focal = focal;
+            }
+            None => {
+                self.slot_of.insert(label, lens_box.slots.len());
+                lens_box.slots.push(Some(Lens { label, focal }));
+                lens_box.lenses.push(1);
+                lens_box.focals.push(focal);
+                lens_box.live += 1;
+                self.power += (number + 1) * lens_box.live * focal;
+            }
+        }
+    }
+
+    // takes the lens out, moving the ones behind it forward. returns its focal length
+    pub fn remove(&mut self, label: &str) -> Option<usize> {
+        let slot = self.slot_of.remove(label)?;
+        let number = hash(label);
+        let lens_box = &mut self.boxes[number];
+        let lens = lens_box.slots[slot].take().expect("Indexed lens");
+        let place = lens_box.lenses.prefix(slot + 1);
+        let behind =
+            lens_box.focals.prefix(lens_box.slots.len()) - lens_box.focals.prefix(slot + 1);
+        self.power -= (number + 1) * (place * lens.focal + behind);
+        lens_box.lenses.add(slot, 1usize.wrapping_neg());
+        lens_box.focals.add(slot, lens.focal.wrapping_neg());
+        lens_box.live -= 1;
+
+        if lens_box.live * 2 < lens_box.slots.len() {
+            lens_box.pack();
+            for (slot, lens) in lens_box.slots.iter().flatten().enumerate() {
+                self.slot_of.insert(lens.label, slot);
+            }
+        }
+        Some(lens.focal)
+    }
+
+    pub fn power(&self) -> usize {
+        self.power
+    }
+
+    pub fn lenses(&self, number: usize) -> impl Iterator<Item = &Lens<'a>> {
+        self.boxes[number].slots.iter().flatten()
+    }
+}
+
+impl Default for Boxes<'_> {
+    fn default() -> Self {
+        Boxes::new()
+    }
+}
+
+impl LensBox<'_> {
+    fn pack(&mut self) {
+        self.slots.retain(Option::is_some);
+        self.lenses = Sums::default();
+        self.focals = Sums::default();
+        for lens in self.slots.iter().flatten() {
+            self.lenses.push(1);
+            self.focals.push(lens.focal);
+        }
+    }
+}
+
+// a fenwick tree that grows at the end, O(log n) to add to a slot or sum a prefix. the sums
+// wrap, so taking away is adding the wrapped negative, and they come out right as long as
+// the true sum isn't negative
+#[derive(Debug, Clone, Default)]
+struct Sums(Vec<usize>);
+
+impl Sums {
+    fn push(&mut self, value: usize) {
+        // node i, counting from 1, sums the slots (i - lowest bit of i, i]
+        let node = self.0.len() + 1;
+        let covered = self
+            .prefix(node - 1)
+            .wrapping_sub(self.prefix(node - (node & node.wrapping_neg())));
+        self.0.push(value.wrapping_add(covered));
+    }
+
+    fn add(&mut self, slot: usize, delta: usize) {
+        let mut node = slot + 1;
+        while node <= self.0.len() {
+            self.0[node - 1] = self.0[node - 1].wrapping_add(delta);
+            node += node & node.wrapping_neg();
+        }
+    }
+
+    // the sum of the slots before `end`
+    fn prefix(&self, end: usize) -> usize {
+        let mut node = end;
+        let mut sum = 0usize;
+        while node > 0 {
+            sum = sum.wrapping_add(self.0[node - 1]);
+            node &= node - 1;
+        }
+        sum
+    }
+}
+
+// the boxes with a lens in them, as the puzzle shows them
+impl fmt::Display for Boxes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for number in 0..self.boxes.len() {
+            let mut lenses = self.lenses(number).peekable();
+            if lenses.peek().is_none() {
+                continue;
+            }
+            write!(f, "Box {number}:")?;
+            for lens in lenses {
+                write!(f, " [{} {}]", lens.label, lens.focal)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Step<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Remove(label) => write!(f, "{label}-"),
+            Step::Insert(label, focal) => write!(f, "{label}={focal}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<'a> {
+    pub step: Step<'a>,
+    pub boxes: String,
+    pub power: usize,
+}
+
+// the boxes and their focusing power after each step
+pub fn replay<'a>(steps: &[Step<'a>]) -> Vec<Snapshot<'a>> {
+    let mut boxes = Boxes::new();
+    steps
+        .iter()
+        .map(|&step| {
+            boxes.apply(step);
+            Snapshot {
+                step,
+                boxes: boxes.to_string(),
+                power: boxes.power(),
+            }
+        })
+        .collect()
+}
+
+// the HASH algorithm as a std Hasher, so a map keyed by `Label` puts each label in the
+// bucket of the box it goes in. other keys hash too, but more than their bytes get written.
+// the map picks a bucket from the low bits and tags the entry with the top 7, so the box
+// number is copied into every byte of the hash, otherwise every tag would be 0
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashHasher(u8);
+
+impl Hasher for HashHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.wrapping_add(byte).wrapping_mul(17);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        u64::from(self.0) * 0x0101_0101_0101_0101
+    }
+}
+
+pub type BuildHashHasher = BuildHasherDefault<HashHasher>;
+
+// a str writes a terminator after its bytes when it's hashed, this only writes the bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label<'a>(pub &'a str);
+
+impl Hash for Label<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.0.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::generate::Rng;
+    use std::hash::BuildHasher;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_replay() {
+        let steps = parse_steps(EXAMPLE).unwrap();
+        let history = replay(&steps);
+        let shown: Vec<String> = history
+            .iter()
+            .map(|snapshot| format!("After \"{}\":\n{}", snapshot.step, snapshot.boxes))
+            .collect();
+        assert_eq!("After \"rn=1\":\nBox 0: [rn 1]\n", shown[0]);
+        assert_eq!("After \"qp-\":\nBox 0: [rn 1] [cm 2]\n", shown[4]);
+        assert_eq!(
+            "After \"ot=7\":\nBox 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n",
+            shown[10]
+        );
+        assert_eq!(Some(145), history.last().map(|snapshot| snapshot.power));
+    }
+
+    #[test]
+    fn test_running_power() {
+        // checks the running total against working it out from the boxes after every step,
+        // with enough lenses in a box to pack it down a few times
+        let labels = ["rn", "cm", "qp", "pc", "ot", "ab", "a", "ab1", "hm", "lk"];
+        let mut boxes = Boxes::new();
+        let mut rng = Rng::with_seed(7);
+        for _ in 0..2000 {
+            let label = labels[rng.usize(..labels.len())];
+            if rng.usize(..3) == 0 {
+                boxes.remove(label);
+            } else {
+                boxes.insert(label, rng.usize(1..10));
+            }
+            let expected: usize = (0..256)
+                .flat_map(|number| {
+                    boxes
+                        .lenses(number)
+                        .enumerate()
+                        .map(move |(slot, lens)| (number + 1) * (slot + 1) * lens.focal)
+                })
+                .sum();
+            assert_eq!(expected, boxes.power());
+        }
+        assert_eq!(None, Boxes::new().remove("rn"));
+    }
+
+    #[test]
+    fn test_hasher() {
+        let build = BuildHashHasher::default();
+        for label in ["rn", "qp", "pc", "HASH"] {
+            let hashed = build.hash_one(Label(label));
+            assert_eq!(hash(label) as u64, hashed & 0xff);
+            assert_eq!(hash(label) as u64 >> 1, hashed >> 57);
+        }
+
+        let mut focals: HashMap<Label, usize, BuildHashHasher> = HashMap::default();
+        focals.insert(Label("rn"), 1);
+        focals.insert(Label("cm"), 2);
+        focals.insert(Label("rn"), 3);
+        assert_eq!(Some(&3), focals.get(&Label("rn")));
+        assert_eq!(2, focals.len());
+    }
+}
@@ -1,15 +1,9 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    input.trim().split(',').map(hash).sum()
-}
+use crate::lenses::hash;
+use aoc_common::ParseError;
 
-fn hash(input: &str) -> usize {
-    input.bytes().fold(0, |mut total, c| {
-        total += usize::from(c);
-        total *= 17;
-        total %= 256;
-        total
-    })
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    Ok(input.trim().split(',').map(hash).sum())
 }
 
 #[cfg(test)]
@@ -31,12 +25,12 @@ mod tests {
     #[case("pc=6", 214)]
     #[case("ot=7", 231)]
     fn test_hash(#[case] input: &str, #[case] expected: usize) {
-        assert_eq!(expected, process(input));
+        assert_eq!(Ok(expected), process(input));
     }
 
     #[test]
     fn test_process() {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!(1320, process(input));
+        assert_eq!(Ok(1320), process(input));
     }
 }
@@ -0,0 +1,364 @@
+use aoc_common::{parse, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+// a beam coming in from outside the grid onto an edge tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub row: usize,
+    pub col: usize,
+    pub heading: Direction,
+}
+
+// the energised tiles, one bit each
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cells {
+    width: usize,
+    bits: Vec<u64>,
+}
+
+impl Cells {
+    fn new(width: usize, height: usize) -> Cells {
+        Cells {
+            width,
+            bits: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, cell: usize) {
+        self.bits[cell / 64] |= 1 << (cell % 64);
+    }
+
+    fn union_with(&mut self, other: &Cells) {
+        for (bits, other) in self.bits.iter_mut().zip(&other.bits) {
+            *bits |= other;
+        }
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        let cell = row * self.width + col;
+        col < self.width
+            && self
+                .bits
+                .get(cell / 64)
+                .is_some_and(|bits| bits & (1 << (cell % 64)) != 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+}
+
+// the contraption as a graph between its splitters. a beam only branches at a splitter hit
+// side on, and it goes the same two ways whichever side that is, so each splitter is a node
+// with an edge for each half of the split beam, to the splitter that half runs into next.
+// splitters that feed each other round a loop are collapsed into one component, and each
+// component knows every tile lit from it, so any beam is lit tiles up to its first split
+// plus that splitter's component
+pub struct Beams {
+    width: usize,
+    height: usize,
+    tiles: Vec<u8>,
+    node_of: Vec<Option<usize>>,
+    component_of: Vec<usize>,
+    lit: Vec<Cells>,
+}
+
+impl Beams {
+    pub fn parse(input: &str) -> Result<Beams, ParseError> {
+        let width = parse::grid(input, "\\/|-.", "a mirror, a splitter or '.'")?;
+        let tiles: Vec<u8> = input.lines().flat_map(str::bytes).collect();
+        let height = tiles.len() / width;
+
+        let mut splitters = Vec::new();
+        let mut node_of = vec![None; tiles.len()];
+        for (cell, &tile) in tiles.iter().enumerate() {
+            if tile == b'|' || tile == b'-' {
+                node_of[cell] = Some(splitters.len());
+                splitters.push(cell);
+            }
+        }
+        let mut beams = Beams {
+            width,
+            height,
+            tiles,
+            node_of,
+            component_of: Vec::new(),
+            lit: Vec::new(),
+        };
+
+        // the tiles each splitter lights itself, before its beams reach another splitter
+        let mut own = Vec::with_capacity(splitters.len());
+        let mut edges = Vec::with_capacity(splitters.len());
+        for &cell in &splitters {
+            let mut lit = Cells::new(width, height);
+            lit.insert(cell);
+            let headings = match beams.tiles[cell] {
+                b'|' => [Direction::Up, Direction::Down],
+                _ => [Direction::Left, Direction::Right],
+            };
+            let next = headings
+                .into_iter()
+                .filter_map(|heading| {
+                    let next = beams.step(cell, heading)?;
+                    beams.follow(next, heading, &mut lit)
+                })
+                .collect::<Vec<usize>>();
+            own.push(lit);
+            edges.push(next);
+        }
+
+        // components come out of tarjan's after everything they reach
+        let (component_of, count) = components(&edges);
+        let mut members = vec![Vec::new(); count];
+        for (node, &component) in component_of.iter().enumerate() {
+            members[component].push(node);
+        }
+        let mut lit: Vec<Cells> = Vec::with_capacity(count);
+        for nodes in &members {
+            let mut cells = Cells::new(width, height);
+            for &node in nodes {
+                cells.union_with(&own[node]);
+                for &next in &edges[node] {
+                    if component_of[next] != lit.len() {
+                        cells.union_with(&lit[component_of[next]]);
+                    }
+                }
+            }
+            lit.push(cells);
+        }
+        beams.component_of = component_of;
+        beams.lit = lit;
+        Ok(beams)
+    }
+
+    // every way in from the edge, along the rows then down the columns
+    pub fn entries(&self) -> Vec<Entry> {
+        let (last_row, last_col) = (self.height - 1, self.width - 1);
+        let rows = (0..self.height)
+            .flat_map(|row| [(row, 0, Direction::Right), (row, last_col, Direction::Left)]);
+        let cols = (0..self.width)
+            .flat_map(|col| [(0, col, Direction::Down), (last_row, col, Direction::Up)]);
+        rows.chain(cols)
+            .map(|(row, col, heading)| Entry { row, col, heading })
+            .collect()
+    }
+
+    pub fn lit(&self, entry: Entry) -> Cells {
+        let mut cells = Cells::new(self.width, self.height);
+        let cell = entry.row * self.width + entry.col;
+        if let Some(splitter) = self.follow(cell, entry.heading, &mut cells) {
+            cells.union_with(&self.lit[self.component_of[splitter]]);
+        }
+        cells
+    }
+
+    pub fn energy(&self, entry: Entry) -> usize {
+        self.lit(entry).count()
+    }
+
+    // the entries whose beam reaches the tile
+    pub fn lighting(&self, row: usize, col: usize) -> Vec<Entry> {
+        self.entries()
+            .into_iter()
+            .filter(|&entry| self.lit(entry).contains(row, col))
+            .collect()
+    }
+
+    // lights the tiles of a beam arriving on `cell` until it's split, returning the splitter,
+    // or until it leaves the grid. a beam that isn't split can only ever come back round to
+    // where it started, as no two tiles with a heading lead on to the same one
+    fn follow(&self, start: usize, heading: Direction, lit: &mut Cells) -> Option<usize> {
+        let (mut cell, mut heading) = (start, heading);
+        let start_heading = heading;
+        loop {
+            lit.insert(cell);
+            heading = match (self.tiles[cell], heading) {
+                (b'|', Direction::Left | Direction::Right)
+                | (b'-', Direction::Up | Direction::Down) => return self.node_of[cell],
+                (b'\\', Direction::Up) => Direction::Left,
+                (b'\\', Direction::Right) => Direction::Down,
+                (b'\\', Direction::Down) => Direction::Right,
+                (b'\\', Direction::Left) => Direction::Up,
+                (b'/', Direction::Up) => Direction::Right,
+                (b'/', Direction::Right) => Direction::Up,
+                (b'/', Direction::Down) => Direction::Left,
+                (b'/', Direction::Left) => Direction::Down,
+                _ => heading,
+            };
+            cell = self.step(cell, heading)?;
+            if cell == start && heading == start_heading {
+                return None;
+            }
+        }
+    }
+
+    fn step(&self, cell: usize, heading: Direction) -> Option<usize> {
+        let (row, col) = (cell / self.width, cell % self.width);
+        match heading {
+            Direction::Up => (row > 0).then(|| cell - self.width),
+            Direction::Right => (col + 1 < self.width).then(|| cell + 1),
+            Direction::Down => (row + 1 < self.height).then(|| cell + self.width),
+            Direction::Left => (col > 0).then(|| cell - 1),
+        }
+    }
+}
+
+// tarjan's strongly connected components, without recursion. returns each node's
+// component and how many there are, numbered in the order they're finished
+fn components(edges: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let unseen = usize::MAX;
+    let mut index = vec![unseen; edges.len()];
+    let mut low = vec![0; edges.len()];
+    let mut component = vec![unseen; edges.len()];
+    let mut stack = Vec::new();
+    let mut next = 0;
+    let mut count = 0;
+
+    for root in 0..edges.len() {
+        if index[root] != unseen {
+            continue;
+        }
+        let mut calls = vec![(root, 0)];
+        index[root] = next;
+        low[root] = next;
+        next += 1;
+        stack.push(root);
+
+        while let Some((node, edge)) = calls.last_mut() {
+            let node = *node;
+            if let Some(&to) = edges[node].get(*edge) {
+                *edge += 1;
+                if index[to] == unseen {
+                    index[to] = next;
+                    low[to] = next;
+                    next += 1;
+                    stack.push(to);
+                    calls.push((to, 0));
+                } else if component[to] == unseen {
+                    // still on the stack
+                    low[node] = low[node].min(index[to]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] == index[node] {
+                while let Some(member) = stack.pop() {
+                    component[member] = count;
+                    if member == node {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+    (component, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ".|...\\....
+|.-.\\.....
+.....|-...
+........|.
+..........
+.........\\
+..../.\\\\..
+.-.-/..|..
+.|....-|.\\
+..//.|....";
+
+    #[test]
+    fn test_energy() {
+        let beams = Beams::parse(EXAMPLE).unwrap();
+        let entry = |row, col, heading| Entry { row, col, heading };
+        assert_eq!(46, beams.energy(entry(0, 0, Direction::Right)));
+        assert_eq!(51, beams.energy(entry(0, 3, Direction::Down)));
+        assert_eq!(
+            Some(51),
+            beams.entries().into_iter().map(|e| beams.energy(e)).max()
+        );
+        assert_eq!(40, beams.entries().len());
+    }
+
+    #[test]
+    fn test_lighting() {
+        let beams = Beams::parse(EXAMPLE).unwrap();
+        let top_left = Entry {
+            row: 0,
+            col: 0,
+            heading: Direction::Right,
+        };
+        let lit = beams.lit(top_left);
+        let expected = "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#..";
+        for (row, line) in expected.lines().enumerate() {
+            for (col, tile) in line.chars().enumerate() {
+                assert_eq!(tile == '#', lit.contains(row, col), "{row}, {col}");
+            }
+        }
+
+        assert!(!beams.lighting(0, 6).contains(&top_left));
+        let lighting = beams.lighting(0, 9);
+        for heading in [Direction::Left, Direction::Down] {
+            let entry = Entry {
+                row: 0,
+                col: 9,
+                heading,
+            };
+            assert!(lighting.contains(&entry));
+        }
+    }
+
+    #[test]
+    fn test_loops() {
+        let entry = |row, col, heading| Entry { row, col, heading };
+        // the two splitters feed each other
+        let beams = Beams::parse("/.\\\n-.|\n\\./").unwrap();
+        assert_eq!(9, beams.energy(entry(1, 0, Direction::Right)));
+        assert_eq!(3, beams.energy(entry(0, 1, Direction::Down)));
+        // half the split beam comes round through the splitter's end and goes round again
+        let beams = Beams::parse("/-\\\n...\n\\./").unwrap();
+        assert_eq!(9, beams.energy(entry(2, 1, Direction::Up)));
+        assert_eq!(3, beams.energy(entry(1, 0, Direction::Right)));
+        assert_eq!(1, components(&[vec![1], vec![0]]).1);
+        assert_eq!(
+            (vec![1, 1, 0], 2),
+            components(&[vec![1], vec![0, 2], vec![]])
+        );
+    }
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..20 {
+            let generated = crate::generate::generate(seed, 30);
+            let beams = Beams::parse(&generated.input).unwrap();
+            let best = beams.entries().into_iter().map(|e| beams.energy(e)).max();
+            assert_eq!(generated.part2, best, "seed {seed}");
+        }
+    }
+}
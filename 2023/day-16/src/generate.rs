@@ -0,0 +1,98 @@
+use aoc_common::generate::{Generated, Rng};
+
+const UP: usize = 0;
+const RIGHT: usize = 1;
+const DOWN: usize = 2;
+const LEFT: usize = 3;
+
+// `size` is the width and height of the contraption, about one tile in ten being a
+// mirror or splitter. the answers come from tracing beams tile by tile
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let grid: Vec<Vec<u8>> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| match rng.usize(..40) {
+                    0 => b'\\',
+                    1 => b'/',
+                    2 => b'|',
+                    3 => b'-',
+                    _ => b'.',
+                })
+                .collect()
+        })
+        .collect();
+
+    let part1 = energised(&grid, (0, 0, RIGHT));
+    let part2 = (0..size)
+        .flat_map(|at| {
+            [
+                (at, 0, RIGHT),
+                (at, size - 1, LEFT),
+                (0, at, DOWN),
+                (size - 1, at, UP),
+            ]
+        })
+        .map(|entry| energised(&grid, entry))
+        .max();
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in grid {
+        input.push_str(&String::from_utf8(line).expect("Ascii"));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2,
+    }
+}
+
+fn energised(grid: &[Vec<u8>], entry: (usize, usize, usize)) -> usize {
+    let size = grid.len();
+    let mut seen = vec![vec![[false; 4]; size]; size];
+    let mut beams = vec![entry];
+    while let Some((row, col, heading)) = beams.pop() {
+        if std::mem::replace(&mut seen[row][col][heading], true) {
+            continue;
+        }
+        let headings = match (grid[row][col], heading) {
+            (b'\\', _) => vec![[LEFT, DOWN, RIGHT, UP][heading]],
+            (b'/', _) => vec![[RIGHT, UP, LEFT, DOWN][heading]],
+            (b'|', LEFT | RIGHT) => vec![UP, DOWN],
+            (b'-', UP | DOWN) => vec![LEFT, RIGHT],
+            _ => vec![heading],
+        };
+        for heading in headings {
+            let next = match heading {
+                UP => row.checked_sub(1).map(|row| (row, col)),
+                RIGHT => (col + 1 < size).then_some((row, col + 1)),
+                DOWN => (row + 1 < size).then_some((row + 1, col)),
+                _ => col.checked_sub(1).map(|col| (row, col)),
+            };
+            if let Some((row, col)) = next {
+                beams.push((row, col, heading));
+            }
+        }
+    }
+    seen.iter()
+        .flatten()
+        .filter(|headings| headings.contains(&true))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 40);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
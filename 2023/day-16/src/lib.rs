@@ -1,2 +1,6 @@
 pub mod part1;
 pub mod part2;
+
+pub mod part2_graph;
+pub mod beams;
+pub mod generate;
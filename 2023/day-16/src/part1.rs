@@ -1,5 +1,8 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    parse::grid(input, "\\/|-.", "a mirror, a splitter or '.'").map_err(|err| err.locate(input))?;
     let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
 
     let max_row = grid.len();
@@ -13,7 +16,7 @@ pub fn process(input: &str) -> usize {
 
     reflect(laser, &mut to_visit, mirror);
 
-    compute_energy(&grid, max_row, max_col, &mut to_visit)
+    Ok(compute_energy(&grid, max_row, max_col, &mut to_visit))
 }
 
 fn compute_energy(
@@ -192,6 +195,6 @@ mod tests {
 .-.-/..|..
 .|....-|.\\
 ..//.|....";
-        assert_eq!(46, process(input));
+        assert_eq!(Ok(46), process(input));
     }
 }
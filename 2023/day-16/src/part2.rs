@@ -1,7 +1,9 @@
+use aoc_common::{parse, ParseError};
 use rayon::prelude::*;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    parse::grid(input, "\\/|-.", "a mirror, a splitter or '.'").map_err(|err| err.locate(input))?;
     let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
 
     let max_row = grid.len();
@@ -18,7 +20,7 @@ pub fn process(input: &str) -> usize {
 
     let positions: Vec<(usize, usize, Direction)> = row_entries.chain(col_entries).collect();
 
-    positions
+    let energy = positions
         .into_par_iter()
         .map(|(row, col, dir)| {
             let mut to_visit = Vec::new();
@@ -29,7 +31,9 @@ pub fn process(input: &str) -> usize {
             compute_energy(&grid, max_row, max_col, &mut to_visit)
         })
         .max()
-        .expect("max value exists")
+        .expect("max value exists");
+
+    Ok(energy)
 }
 
 fn compute_energy(
@@ -208,6 +212,6 @@ mod tests {
 .-.-/..|..
 .|....-|.\\
 ..//.|....";
-        assert_eq!(51, process(input));
+        assert_eq!(Ok(51), process(input));
     }
 }
@@ -0,0 +1,34 @@
+use crate::beams::Beams;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let beams = Beams::parse(input).map_err(|err| err.locate(input))?;
+    let energy = beams
+        .entries()
+        .into_iter()
+        .map(|entry| beams.energy(entry))
+        .max()
+        .expect("max value exists");
+    Ok(energy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = ".|...\\....
+|.-.\\.....
+.....|-...
+........|.
+..........
+.........\\
+..../.\\\\..
+.-.-/..|..
+.|....-|.\\
+..//.|....";
+        assert_eq!(Ok(51), process(input));
+    }
+}
@@ -0,0 +1,76 @@
+use aoc_common::generate::{Generated, Rng};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// `size` is the width and height of the city. every block loses 1 to 9 heat, and the
+// answers come from a search where each move is a whole straight run followed by a turn
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let grid: Vec<Vec<usize>> = (0..size)
+        .map(|_| (0..size).map(|_| rng.usize(1..=9)).collect())
+        .collect();
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in &grid {
+        input.extend(line.iter().map(|&loss| char::from(b'0' + loss as u8)));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: least_loss(&grid, 1, 3),
+        part2: least_loss(&grid, 4, 10),
+    }
+}
+
+fn least_loss(grid: &[Vec<usize>], min: usize, max: usize) -> Option<usize> {
+    let size = grid.len();
+    // one state per block and axis of the run that ended there
+    let mut best = vec![vec![[usize::MAX; 2]; size]; size];
+    let mut queue = BinaryHeap::from([Reverse((0, 0, 0, 0)), Reverse((0, 0, 0, 1))]);
+    while let Some(Reverse((loss, row, col, axis))) = queue.pop() {
+        if (row, col) == (size - 1, size - 1) {
+            return Some(loss);
+        }
+        if loss > best[row][col][axis] {
+            continue;
+        }
+        for sign in [-1, 1] {
+            let mut total = loss;
+            for run in 1..=max as isize {
+                let (next_row, next_col) = match axis {
+                    0 => (row as isize + sign * run, col as isize),
+                    _ => (row as isize, col as isize + sign * run),
+                };
+                if !(0..size as isize).contains(&next_row)
+                    || !(0..size as isize).contains(&next_col)
+                {
+                    break;
+                }
+                let (next_row, next_col) = (next_row as usize, next_col as usize);
+                total += grid[next_row][next_col];
+                // the next run goes along the other axis
+                if run as usize >= min && total < best[next_row][next_col][1 - axis] {
+                    best[next_row][next_col][1 - axis] = total;
+                    queue.push(Reverse((total, next_row, next_col, 1 - axis)));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 40);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
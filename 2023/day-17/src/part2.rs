@@ -1,8 +1,12 @@
+use aoc_common::{parse, ParseError};
 use std::{cmp::Reverse, collections::BinaryHeap};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let grid = parse_grid(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let grid = tracing::info_span!("parse")
+        .in_scope(|| parse_grid(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
 
     let mut distance_map = FakeMap::new(grid.len(), grid[0].len());
 
@@ -25,7 +29,7 @@ pub fn process(input: &str) -> usize {
 
     while let Some(Reverse(node)) = priority_q.pop() {
         if node.pos == target {
-            return node.minimum;
+            return Ok(node.minimum);
         }
 
         let (row, col) = node.pos;
@@ -39,18 +43,20 @@ pub fn process(input: &str) -> usize {
         node.add_neighbours(&grid, &mut distance_map, &mut priority_q);
     }
 
-    panic!("couldn't get to the end");
+    Err(ParseError::new(input, "no path reaches the end").locate(input))
 }
 
-fn parse_grid(input: &str) -> Vec<Vec<usize>> {
-    input
+fn parse_grid(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    parse::grid(input, "0123456789", "a digit")?;
+    let grid = input
         .lines()
         .map(|line| {
             line.chars()
                 .map(|char| char.to_digit(10).expect("It's a number") as usize)
                 .collect()
         })
-        .collect()
+        .collect();
+    Ok(grid)
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -215,7 +221,7 @@ mod tests {
 1224686865563
 2546548887735
 4322674655533";
-        assert_eq!(94, process(input));
+        assert_eq!(Ok(94), process(input));
     }
 
     #[test]
@@ -225,6 +231,6 @@ mod tests {
 999999999991
 999999999991
 999999999991";
-        assert_eq!(71, process(input));
+        assert_eq!(Ok(71), process(input));
     }
 }
@@ -0,0 +1,98 @@
+use aoc_common::generate::{outline, region, Generated, Rng};
+
+// `size` is the width and height, in cells, of a random region whose outline is the
+// trench. each part stretches the cells to its own random row heights and column
+// widths, so both plans dig the same shape at different scales, and the lagoon holds
+// the area inside the outline plus the half of the trench that sits outside it
+pub fn generate(seed: u64, size: usize) -> Generated<isize> {
+    let mut rng = Rng::with_seed(seed);
+    let size = size.max(2);
+    let region = region(&mut rng, size, size, size * size * 3 / 5);
+    let corners = outline(&region);
+
+    // the colour's five hex digits have to hold a whole side
+    let small = stretch(&mut rng, size, 6);
+    let large = stretch(&mut rng, size, 0xfffff / size as isize);
+
+    let mut input = String::new();
+    for (i, &from) in corners.iter().enumerate() {
+        let to = corners[(i + 1) % corners.len()];
+        let ((direction, distance), (code, hex_distance)) =
+            (side(&small, from, to), side(&large, from, to));
+        let code = match code {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            _ => 3,
+        };
+        let colour = format!("{hex_distance:05x}{code}");
+        input.push_str(&format!("{direction} {distance} (#{colour})\n"));
+    }
+
+    Generated {
+        input,
+        part1: Some(lagoon(&small, &corners)),
+        part2: Some(lagoon(&large, &corners)),
+    }
+}
+
+// where each grid line of the region ends up, for rows and for columns
+struct Stretch {
+    rows: Vec<isize>,
+    cols: Vec<isize>,
+}
+
+fn stretch(rng: &mut Rng, size: usize, max: isize) -> Stretch {
+    let mut lines = || {
+        let mut at = 0;
+        (0..=size)
+            .map(|_| {
+                let line = at;
+                at += rng.isize(1..=max);
+                line
+            })
+            .collect()
+    };
+    Stretch {
+        rows: lines(),
+        cols: lines(),
+    }
+}
+
+fn side(stretch: &Stretch, from: (usize, usize), to: (usize, usize)) -> (char, isize) {
+    let (from_row, from_col) = (stretch.rows[from.0], stretch.cols[from.1]);
+    let (to_row, to_col) = (stretch.rows[to.0], stretch.cols[to.1]);
+    match (to_row - from_row, to_col - from_col) {
+        (0, distance) if distance > 0 => ('R', distance),
+        (0, distance) => ('L', -distance),
+        (distance, _) if distance > 0 => ('D', distance),
+        (distance, _) => ('U', -distance),
+    }
+}
+
+fn lagoon(stretch: &Stretch, corners: &[(usize, usize)]) -> isize {
+    let (mut twice_area, mut trench) = (0, 0);
+    for (i, &from) in corners.iter().enumerate() {
+        let to = corners[(i + 1) % corners.len()];
+        let (from_row, from_col) = (stretch.rows[from.0], stretch.cols[from.1]);
+        let (to_row, to_col) = (stretch.rows[to.0], stretch.cols[to.1]);
+        twice_area += from_col * to_row - to_col * from_row;
+        trench += side(stretch, from, to).1;
+    }
+    twice_area.abs() / 2 + trench / 2 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 20);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
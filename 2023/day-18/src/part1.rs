@@ -1,6 +1,8 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> isize {
-    let commands = parse_commands(input);
+use aoc_common::{parse, ParseError};
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<isize, ParseError> {
+    let commands = parse_commands(input).map_err(|err| err.locate(input))?;
     let mut corners: Vec<Point> = Vec::new();
     let mut pos = Point { x: 0, y: 0 };
     let mut perimeter: isize = 0;
@@ -10,29 +12,29 @@ pub fn process(input: &str) -> isize {
         perimeter += command.distance;
         corners.push(pos);
     });
-    shoelace_formula(&corners) + perimeter / 2 + 1
+    Ok(shoelace_formula(&corners) + perimeter / 2 + 1)
+}
+
+fn parse_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    input.lines().map(parse_command).collect()
 }
 
-fn parse_commands(input: &str) -> Vec<Command> {
-    input
-        .lines()
-        .map(|line| {
-            let parts: Vec<&str> = line.split_ascii_whitespace().collect();
-            let direction = match parts[0] {
-                "U" => Direction::Up,
-                "L" => Direction::Left,
-                "D" => Direction::Down,
-                "R" => Direction::Right,
-                _ => panic!("Invalid direction str: {}", parts[0]),
-            };
-            let distance = parts[1].parse::<isize>().expect("It's a number");
+fn parse_command(line: &str) -> Result<Command, ParseError> {
+    let (direction, rest) = parse::split_once(line, " ")?;
+    let (distance, _colour) = parse::split_once(rest, " ")?;
+    let direction = match direction {
+        "U" => Direction::Up,
+        "L" => Direction::Left,
+        "D" => Direction::Down,
+        "R" => Direction::Right,
+        _ => return Err(ParseError::expected(direction, "'U', 'L', 'D' or 'R'")),
+    };
+    let distance = parse::number::<isize>(distance)?;
 
-            Command {
-                direction,
-                distance,
-            }
-        })
-        .collect()
+    Ok(Command {
+        direction,
+        distance,
+    })
 }
 
 fn shoelace_formula(corners: &[Point]) -> isize {
@@ -110,6 +112,6 @@ R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
-        assert_eq!(62, process(input));
+        assert_eq!(Ok(62), process(input));
     }
 }
@@ -1,6 +1,8 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> isize {
-    let commands = parse_commands(input);
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<isize, ParseError> {
+    let commands = parse_commands(input).map_err(|err| err.locate(input))?;
     let mut corners: Vec<Point> = Vec::new();
     let mut pos = Point { x: 0, y: 0 };
     let mut perimeter:isize = 0;
@@ -10,30 +12,37 @@ pub fn process(input: &str) -> isize {
         perimeter += command.distance;
         corners.push(pos);
     });
-    shoelace_formula(&corners) + perimeter / 2 + 1
+    Ok(shoelace_formula(&corners) + perimeter / 2 + 1)
+}
+
+fn parse_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    input.lines().map(parse_command).collect()
 }
 
-fn parse_commands(input: &str) -> Vec<Command> {
-    input
-        .lines()
-        .map(|line| {
-            let parts: Vec<&str> = line.split_ascii_whitespace().collect();
-            let color = parts[2];
-            let distance = isize::from_str_radix(&color[2..7], 16).expect("It's a hex number");
-            let direction = match &color[7..8] {
-                "0" => Direction::Right,
-                "1" => Direction::Up,
-                "2" => Direction::Left,
-                "3" => Direction::Down,
-                _ => panic!("Unexpected direction char: {}", &color[7..8])
-            };
+fn parse_command(line: &str) -> Result<Command, ParseError> {
+    let color = line
+        .split_ascii_whitespace()
+        .nth(2)
+        .ok_or_else(|| ParseError::expected(line, "a direction, distance and color"))?;
+    let hex = color
+        .strip_prefix("(#")
+        .and_then(|hex| hex.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.is_ascii())
+        .ok_or_else(|| ParseError::expected(color, "a color like '(#70c710)'"))?;
+    let distance = isize::from_str_radix(&hex[..5], 16)
+        .map_err(|_| ParseError::expected(&hex[..5], "a hex number"))?;
+    let direction = match &hex[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Up,
+        "2" => Direction::Left,
+        "3" => Direction::Down,
+        _ => return Err(ParseError::expected(&hex[5..], "a direction from 0 to 3")),
+    };
 
-            Command {
-                direction,
-                distance,
-            }
-        })
-        .collect()
+    Ok(Command {
+        direction,
+        distance,
+    })
 }
 
 fn shoelace_formula(corners: &[Point]) -> isize {
@@ -110,6 +119,6 @@ R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
-        assert_eq!(952408144115, process(input));
+        assert_eq!(Ok(952408144115), process(input));
     }
 }
@@ -0,0 +1,125 @@
+use aoc_common::generate::{Generated, Rng};
+use std::collections::{HashSet, VecDeque};
+
+const FIELDS: [char; 4] = ['x', 'm', 'a', 's'];
+
+// the ratings each field can still have, inclusive
+type Ranges = [(usize, usize); 4];
+
+// `size` is the number of workflows, with a part for every three of them. the
+// workflows form a tree from `in`, and each rule splits off part of the ratings that
+// reach it, so every accepted box of ratings is known as the tree is built
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut names = HashSet::from([String::from("in")]);
+    let mut queue = VecDeque::from([(String::from("in"), [(1, 4000); 4])]);
+    let mut workflows = Vec::new();
+    let mut accepted = Vec::new();
+
+    while let Some((name, mut ranges)) = queue.pop_front() {
+        let mut rules = Vec::new();
+        for _ in 0..rng.usize(1..=4) {
+            let field = rng.usize(..4);
+            let (low, high) = ranges[field];
+            if low == high {
+                continue;
+            }
+            // the rule takes one side of the split, the rest carries on down the list
+            let mut taken = ranges;
+            let condition = if rng.bool() {
+                let value = rng.usize(low + 1..=high);
+                taken[field] = (low, value - 1);
+                ranges[field] = (value, high);
+                format!("{}<{value}", FIELDS[field])
+            } else {
+                let value = rng.usize(low..high);
+                taken[field] = (value + 1, high);
+                ranges[field] = (low, value);
+                format!("{}>{value}", FIELDS[field])
+            };
+            let send_to = target(&mut rng, size, &mut names, &mut queue, &mut accepted, taken);
+            rules.push(format!("{condition}:{send_to}"));
+        }
+        rules.push(target(
+            &mut rng,
+            size,
+            &mut names,
+            &mut queue,
+            &mut accepted,
+            ranges,
+        ));
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    rng.shuffle(&mut workflows);
+
+    let mut parts = Vec::new();
+    let mut part1 = 0;
+    for _ in 0..size / 3 + 1 {
+        let ratings: [usize; 4] = std::array::from_fn(|_| rng.usize(1..=4000));
+        let inside = |ranges: &Ranges| {
+            (0..4).all(|field| (ranges[field].0..=ranges[field].1).contains(&ratings[field]))
+        };
+        if accepted.iter().any(inside) {
+            part1 += ratings.iter().sum::<usize>();
+        }
+        let [x, m, a, s] = ratings;
+        parts.push(format!("{{x={x},m={m},a={a},s={s}}}"));
+    }
+
+    let part2 = accepted
+        .iter()
+        .map(|ranges| {
+            ranges
+                .iter()
+                .map(|(low, high)| high - low + 1)
+                .product::<usize>()
+        })
+        .sum();
+
+    Generated {
+        input: format!("{}\n\n{}\n", workflows.join("\n"), parts.join("\n")),
+        part1: Some(part1),
+        part2: Some(part2),
+    }
+}
+
+// either a new workflow, queued up to handle these ratings, or straight to A or R
+fn target(
+    rng: &mut Rng,
+    size: usize,
+    names: &mut HashSet<String>,
+    queue: &mut VecDeque<(String, Ranges)>,
+    accepted: &mut Vec<Ranges>,
+    ranges: Ranges,
+) -> String {
+    if names.len() < size && rng.usize(..4) != 0 {
+        let name = loop {
+            let name: String = (0..rng.usize(2..=3)).map(|_| rng.lowercase()).collect();
+            if names.insert(name.clone()) {
+                break name;
+            }
+        };
+        queue.push_back((name.clone(), ranges));
+        name
+    } else if rng.bool() {
+        accepted.push(ranges);
+        String::from("A")
+    } else {
+        String::from("R")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 100);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -1,128 +1,149 @@
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (workflows, parts) = parse_input(input);
-
-    parts
-        .iter()
-        .fold(0, |acc, part| {
-            let mut workflow_id = "in";
-            while workflow_id != "R" && workflow_id != "A" {
-                let workflow = workflows.get(&workflow_id).expect("It's there");
-                let mut condition_matched = false;
-                for condition in &workflow.conditions {
-                    if let Some(send_to) = condition.apply(part) {
-                        workflow_id = send_to;
-                        condition_matched = true;
-                        break;
-                    }
-                }
-                if !condition_matched {
-                    workflow_id = workflow.send_to;
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (workflows, parts) = parse_input(input).map_err(|err| err.locate(input))?;
+
+    let total = parts.iter().fold(0, |acc, part| {
+        let mut workflow_id = "in";
+        while workflow_id != "R" && workflow_id != "A" {
+            let workflow = workflows.get(&workflow_id).expect("It's there");
+            let mut condition_matched = false;
+            for condition in &workflow.conditions {
+                if let Some(send_to) = condition.apply(part) {
+                    workflow_id = send_to;
+                    condition_matched = true;
+                    break;
                 }
             }
-
-            if workflow_id == "A" {
-                acc + part.x + part.m + part.a + part.s
-            } else {
-                acc
+            if !condition_matched {
+                workflow_id = workflow.send_to;
             }
-        })
+        }
+
+        if workflow_id == "A" {
+            acc + part.x + part.m + part.a + part.s
+        } else {
+            acc
+        }
+    });
+
+    Ok(total)
 }
 
-fn parse_input(input: &str) -> (HashMap<&str, Workflow>, Vec<Part>) {
-    let halves = input.split_once("\n\n").expect("Unix endings");
+type ParseOutput<'a> = (HashMap<&'a str, Workflow<'a>>, Vec<Part>);
+
+fn parse_input(input: &str) -> Result<ParseOutput<'_>, ParseError> {
+    let (workflows, parts) = two_blocks(input)?;
 
-    let workflows = parse_workflows(halves.0);
-    let parts = parse_parts(halves.1);
+    let workflows = parse_workflows(workflows)?;
+    let parts = parts
+        .lines()
+        .map(parse_part)
+        .collect::<Result<Vec<Part>, ParseError>>()?;
 
-    (workflows, parts)
+    Ok((workflows, parts))
 }
 
-fn parse_workflows(input: &str) -> HashMap<&str, Workflow> {
-    let mut workflow_map = HashMap::new();
-
-    input.lines().for_each(|line| {
-        let mut id = "";
-        let mut conditions = Vec::new();
-        let mut send_to = "";
-        line.split(['{', '}', ','])
-            .enumerate()
-            .for_each(|(i, chunk)| {
-                if chunk == "" {
-                    return;
-                }
-                if i == 0 {
-                    id = chunk;
-                }
-                if chunk.contains(':') {
-                    conditions.push(parse_condition(chunk));
-                } else {
-                    send_to = chunk;
-                }
-            });
-        workflow_map.insert(
+fn parse_workflows(input: &str) -> Result<HashMap<&str, Workflow<'_>>, ParseError> {
+    let mut workflows = Vec::new();
+
+    for line in input.lines() {
+        let (id, rules) = parse::split_once(line, "{")?;
+        let rules = rules
+            .strip_suffix('}')
+            .ok_or_else(|| ParseError::expected(rules, "rules ending in '}'"))?;
+        let (conditions, send_to) = match rules.rsplit_once(',') {
+            Some((conditions, send_to)) => (
+                conditions
+                    .split(',')
+                    .map(parse_condition)
+                    .collect::<Result<Vec<Condition>, ParseError>>()?,
+                send_to,
+            ),
+            None => (Vec::new(), rules),
+        };
+        workflows.push((
             id,
             Workflow {
                 conditions,
                 send_to,
             },
-        );
-    });
+        ));
+    }
 
-    workflow_map
+    let workflow_map: HashMap<&str, Workflow> = workflows.iter().cloned().collect();
+    if !workflow_map.contains_key("in") {
+        return Err(ParseError::new(input, "there's no workflow called 'in'"));
+    }
+    for (_, workflow) in &workflows {
+        let targets = workflow
+            .conditions
+            .iter()
+            .map(|condition| condition.send_to);
+        for target in targets.chain([workflow.send_to]) {
+            if target != "A" && target != "R" && !workflow_map.contains_key(target) {
+                return Err(ParseError::new(
+                    target,
+                    format!("unknown workflow '{}'", target),
+                ));
+            }
+        }
+    }
+
+    Ok(workflow_map)
 }
 
-fn parse_condition(chunk: &str) -> Condition {
-    let parts = chunk.split_once(':').expect("has a colon");
-    let field = match &parts.0[0..1] {
-        "x" => Field::X,
-        "m" => Field::M,
-        "a" => Field::A,
-        "s" => Field::S,
-        _ => panic!("Invalid field char: {}", &parts.0[0..1]),
+fn parse_condition(chunk: &str) -> Result<Condition<'_>, ParseError> {
+    let (test, send_to) = parse::split_once(chunk, ":")?;
+    let field = match test.get(0..1) {
+        Some("x") => Field::X,
+        Some("m") => Field::M,
+        Some("a") => Field::A,
+        Some("s") => Field::S,
+        _ => return Err(ParseError::expected(test, "'x', 'm', 'a' or 's'")),
     };
-    let comparison = match &parts.0[1..2] {
-        ">" => Comparison::GT,
-        "<" => Comparison::LT,
-        _ => panic!("Unknown comparison: {}", &parts.0[1..2]),
+    let comparison = match test.get(1..2) {
+        Some(">") => Comparison::GT,
+        Some("<") => Comparison::LT,
+        Some(other) => {
+            let message = format!("unknown comparison '{}'", other);
+            return Err(ParseError::new(&test[1..], message));
+        }
+        None => return Err(ParseError::expected(&test[1..], "'<' or '>'")),
     };
 
-    let value = usize::from_str_radix(&parts.0[2..], 10).expect("It's a number");
+    let value = parse::number::<usize>(&test[2..])?;
 
-    let send_to = parts.1;
-
-    Condition {
+    Ok(Condition {
         field,
         comparison,
         value,
         send_to,
-    }
+    })
 }
 
-fn parse_parts(input: &str) -> Vec<Part> {
-    input
-        .lines()
-        .map(|line| {
-            let mut sections = line[1..line.len() - 1].split(",");
-            let x =
-                usize::from_str_radix(&sections.next().unwrap()[2..], 10).expect("It's a number");
-            let m =
-                usize::from_str_radix(&sections.next().unwrap()[2..], 10).expect("It's a number");
-            let a =
-                usize::from_str_radix(&sections.next().unwrap()[2..], 10).expect("It's a number");
-            let s =
-                usize::from_str_radix(&sections.next().unwrap()[2..], 10).expect("It's a number");
-
-            Part {
-                x,
-                m,
-                a,
-                s,
-            }
+fn parse_part(line: &str) -> Result<Part, ParseError> {
+    let ratings = line
+        .strip_prefix('{')
+        .and_then(|line| line.strip_suffix('}'))
+        .ok_or_else(|| ParseError::expected(line, "a part like '{x=1,m=2,a=3,s=4}'"))?;
+    let values = ratings
+        .split(',')
+        .zip(["x=", "m=", "a=", "s="])
+        .map(|(section, name)| {
+            let value = section
+                .strip_prefix(name)
+                .ok_or_else(|| ParseError::expected(section, &format!("'{}'", name)))?;
+            parse::number::<usize>(value)
         })
-        .collect()
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    let [x, m, a, s] = values[..] else {
+        return Err(ParseError::expected(ratings, "four ratings"));
+    };
+
+    Ok(Part { x, m, a, s })
 }
 
 #[derive(Clone, Debug)]
@@ -247,6 +268,22 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        assert_eq!(19114, process(input));
+        assert_eq!(Ok(19114), process(input));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let input = "px{a<2006:qkq,m>2090:A,rfg}
+rfg{s<537:gd,x>2440:R,A}
+in{s=1351:px,gd}
+gd{a>3333:R,R}
+
+{x=787,m=2655,a=1222,s=2876}";
+        let error = process(input).unwrap_err();
+        assert_eq!("line 3, col 5: unknown comparison '='", error.to_string());
+
+        let input = "in{s<1351:px,A}\n\n{x=787,m=2655,a=1222,s=2876}";
+        let error = process(input).unwrap_err();
+        assert_eq!("line 1, col 11: unknown workflow 'px'", error.to_string());
     }
 }
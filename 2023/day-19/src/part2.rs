@@ -1,8 +1,9 @@
+use aoc_common::{parse, two_blocks, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let workflows = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let workflows = parse_input(input).map_err(|err| err.locate(input))?;
 
     let mut parts = Vec::new();
     parts.push((
@@ -40,76 +41,91 @@ pub fn process(input: &str) -> usize {
         }
         parts = new_parts;
     }
-    total
+    Ok(total)
 }
 
-fn parse_input(input: &str) -> HashMap<&str, Workflow> {
-    let halves = input.split_once("\n\n").expect("Unix endings");
+fn parse_input(input: &str) -> Result<HashMap<&str, Workflow<'_>>, ParseError> {
+    let (workflows, _parts) = two_blocks(input)?;
 
-    let workflows = parse_workflows(halves.0);
-
-    workflows
+    parse_workflows(workflows)
 }
 
-fn parse_workflows(input: &str) -> HashMap<&str, Workflow> {
-    let mut workflow_map = HashMap::new();
-
-    input.lines().for_each(|line| {
-        let mut id = "";
-        let mut conditions = Vec::new();
-        let mut send_to = "";
-        line.split(['{', '}', ','])
-            .enumerate()
-            .for_each(|(i, chunk)| {
-                if chunk == "" {
-                    return;
-                }
-                if i == 0 {
-                    id = chunk;
-                }
-                if chunk.contains(':') {
-                    conditions.push(parse_condition(chunk));
-                } else {
-                    send_to = chunk;
-                }
-            });
-        workflow_map.insert(
+fn parse_workflows(input: &str) -> Result<HashMap<&str, Workflow<'_>>, ParseError> {
+    let mut workflows = Vec::new();
+
+    for line in input.lines() {
+        let (id, rules) = parse::split_once(line, "{")?;
+        let rules = rules
+            .strip_suffix('}')
+            .ok_or_else(|| ParseError::expected(rules, "rules ending in '}'"))?;
+        let (conditions, send_to) = match rules.rsplit_once(',') {
+            Some((conditions, send_to)) => (
+                conditions
+                    .split(',')
+                    .map(parse_condition)
+                    .collect::<Result<Vec<Condition>, ParseError>>()?,
+                send_to,
+            ),
+            None => (Vec::new(), rules),
+        };
+        workflows.push((
             id,
             Workflow {
                 conditions,
                 send_to,
             },
-        );
-    });
+        ));
+    }
+
+    let workflow_map: HashMap<&str, Workflow> = workflows.iter().cloned().collect();
+    if !workflow_map.contains_key("in") {
+        return Err(ParseError::new(input, "there's no workflow called 'in'"));
+    }
+    for (_, workflow) in &workflows {
+        let targets = workflow
+            .conditions
+            .iter()
+            .map(|condition| condition.send_to);
+        for target in targets.chain([workflow.send_to]) {
+            if target != "A" && target != "R" && !workflow_map.contains_key(target) {
+                return Err(ParseError::new(
+                    target,
+                    format!("unknown workflow '{}'", target),
+                ));
+            }
+        }
+    }
 
-    workflow_map
+    Ok(workflow_map)
 }
 
-fn parse_condition(chunk: &str) -> Condition {
-    let parts = chunk.split_once(':').expect("has a colon");
-    let field = match &parts.0[0..1] {
-        "x" => Field::X,
-        "m" => Field::M,
-        "a" => Field::A,
-        "s" => Field::S,
-        _ => panic!("Invalid field char: {}", &parts.0[0..1]),
+fn parse_condition(chunk: &str) -> Result<Condition<'_>, ParseError> {
+    let (test, send_to) = parse::split_once(chunk, ":")?;
+    let field = match test.get(0..1) {
+        Some("x") => Field::X,
+        Some("m") => Field::M,
+        Some("a") => Field::A,
+        Some("s") => Field::S,
+        _ => return Err(ParseError::expected(test, "'x', 'm', 'a' or 's'")),
     };
-    let comparison = match &parts.0[1..2] {
-        ">" => Comparison::GT,
-        "<" => Comparison::LT,
-        _ => panic!("Unknown comparison: {}", &parts.0[1..2]),
+    let comparison = match test.get(1..2) {
+        Some(">") => Comparison::GT,
+        Some("<") => Comparison::LT,
+        Some(other) => {
+            let message = format!("unknown comparison '{}'", other);
+            return Err(ParseError::new(&test[1..], message));
+        }
+        None => return Err(ParseError::expected(&test[1..], "'<' or '>'")),
     };
 
-    let value = usize::from_str_radix(&parts.0[2..], 10).expect("It's a number");
-
-    let send_to = parts.1;
+    let value = parse::number::<usize>(&test[2..])?;
 
-    Condition {
+    Ok(Condition {
         field,
         comparison,
         value,
         send_to,
-    }
+    })
 }
 
 #[derive(Clone, Debug)]
@@ -225,6 +241,6 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        assert_eq!(167409079868000, process(input));
+        assert_eq!(Ok(167409079868000), process(input));
     }
 }
@@ -1,12 +1,13 @@
+use aoc_common::{read_input, ParseError};
 use day_20::part1::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = process(&file)?;
     println!("{}", result);
 
-    let result = day_20::part1_no_map::process(file);
+    let result = day_20::part1_no_map::process(&file)?;
     println!("{}", result);
 
     Ok(())
@@ -0,0 +1,10 @@
+use aoc_common::{read_input, ParseError};
+use day_20::part2_no_map::process;
+
+#[tracing::instrument]
+fn main() -> Result<(), ParseError> {
+    let file = read_input(env!("CARGO_MANIFEST_DIR"));
+    let result = process(&file)?;
+    println!("{}", result);
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use aoc_common::generate::{Generated, Rng};
+use std::collections::HashSet;
+
+const BITS: usize = 12;
+
+// `size` is the number of counters the broadcaster drives, four in the real thing.
+// each is a chain of flip-flops counting presses in binary, with a conjunction that
+// sees the bits set in a prime and resets the chain once the count reaches it. the
+// counters' conjunctions feed rx through inverters and one last conjunction, so rx
+// first gets a low pulse after the product of the primes
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng| loop {
+        let name: String = (0..2).map(|_| rng.lowercase()).collect();
+        if name != "rx" && names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let mut primes = Vec::new();
+    while primes.len() < size.max(1) {
+        let prime = rng.usize(1 << (BITS - 1)..1 << BITS);
+        if is_prime(prime) && !primes.contains(&prime) {
+            primes.push(prime);
+        }
+    }
+
+    let last = name(&mut rng);
+    let mut lines = vec![format!("&{last} -> rx")];
+    let mut starts = Vec::new();
+    for &prime in &primes {
+        let chain: Vec<String> = (0..BITS).map(|_| name(&mut rng)).collect();
+        let (hub, inverter) = (name(&mut rng), name(&mut rng));
+
+        let mut hub_outputs = vec![inverter.clone()];
+        for (bit, flip_flop) in chain.iter().enumerate() {
+            let mut outputs = Vec::new();
+            if let Some(next) = chain.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            // the hub watches the bits that are set and flips the rest to reset
+            if prime & 1 << bit != 0 {
+                outputs.push(hub.clone());
+            }
+            if prime & 1 << bit == 0 || bit == 0 {
+                hub_outputs.push(flip_flop.clone());
+            }
+            rng.shuffle(&mut outputs);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        rng.shuffle(&mut hub_outputs);
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{inverter} -> {last}"));
+        starts.push(chain[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    rng.shuffle(&mut lines);
+
+    Generated {
+        input: lines.join("\n") + "\n",
+        part1: None,
+        part2: Some(primes.iter().product()),
+    }
+}
+
+fn is_prime(number: usize) -> bool {
+    number > 1
+        && (2..)
+            .take_while(|i| i * i <= number)
+            .all(|i| !number.is_multiple_of(i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_no_map, part2, part2_no_map};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 4);
+            assert_eq!(
+                part1::process(&generated.input),
+                part1_no_map::process(&generated.input)
+            );
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+            assert_eq!(
+                generated.part2,
+                part2_no_map::process(&generated.input).ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rx_without_conjunction() {
+        for input in [
+            "broadcaster -> rx",
+            "broadcaster -> a\n%a -> rx",
+            "broadcaster -> a, b\n%a -> rx\n%b -> rx",
+        ] {
+            let expected = "rx has to be fed by a single conjunction";
+            assert_eq!(expected, part2::process(input).unwrap_err().message);
+            assert_eq!(expected, part2_no_map::process(input).unwrap_err().message);
+        }
+    }
+}
@@ -3,3 +3,4 @@ pub mod part2;
 
 pub mod part1_no_map;
 pub mod part2_no_map;
+pub mod generate;
@@ -1,10 +1,11 @@
 use std::collections::VecDeque;
 
+use aoc_common::{parse, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (mut modules, mut input_map) = parse_modules(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (mut modules, mut input_map) = parse_modules(input).map_err(|err| err.locate(input))?;
     let mut lo_pulses = 0;
     let mut hi_pulses = 0;
 
@@ -71,19 +72,19 @@ pub fn process(input: &str) -> usize {
         }
     }
 
-    lo_pulses * hi_pulses
+    Ok(lo_pulses * hi_pulses)
 }
 
-fn parse_modules<'a>(
-    input: &'a str,
-) -> (
+type ParseOutput<'a> = (
     HashMap<&'a str, Module<'a>>,
-    HashMap<&'a str, Vec<(&str, bool)>>,
-) {
-    let module_map: HashMap<&'a str, Module<'a>> = input
+    HashMap<&'a str, Vec<(&'a str, bool)>>,
+);
+
+fn parse_modules(input: &str) -> Result<ParseOutput<'_>, ParseError> {
+    let module_map: HashMap<&str, Module> = input
         .lines()
         .map(|line| {
-            let (start, targets) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, targets) = split_module(line)?;
             let outputs: Vec<&str> = targets.split(", ").collect();
             let module_type = match &start[0..1] {
                 "%" => ModuleType::FlipFlop,
@@ -97,18 +98,18 @@ fn parse_modules<'a>(
                 &start[1..]
             };
 
-            (
+            Ok((
                 id,
                 Module {
                     module_type,
                     outputs,
                     state: false,
                 },
-            )
+            ))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    let mut input_map: HashMap<&'a str, Vec<(&str, bool)>> = HashMap::new();
+    let mut input_map: HashMap<&str, Vec<(&str, bool)>> = HashMap::new();
     module_map.iter().for_each(|(id, module)| {
         for output in module.outputs.iter() {
             let previous = input_map.get(output);
@@ -121,7 +122,15 @@ fn parse_modules<'a>(
             }
         }
     });
-    (module_map, input_map)
+    Ok((module_map, input_map))
+}
+
+fn split_module(line: &str) -> Result<(&str, &str), ParseError> {
+    let (start, targets) = parse::split_once(line, " -> ")?;
+    if start.get(0..1).is_none() {
+        return Err(ParseError::expected(line, "a module name"));
+    }
+    Ok((start, targets))
 }
 
 #[derive(Debug)]
@@ -173,7 +182,7 @@ mod tests {
 %b -> c
 %c -> inv
 &inv -> a";
-        assert_eq!(32000000, process(input));
+        assert_eq!(Ok(32000000), process(input));
     }
 
     #[test]
@@ -183,6 +192,6 @@ mod tests {
 &inv -> b
 %b -> con
 &con -> output";
-        assert_eq!(11687500, process(input));
+        assert_eq!(Ok(11687500), process(input));
     }
 }
@@ -1,10 +1,14 @@
 use std::collections::VecDeque;
 
+use aoc_common::{parse, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (mut modules, mut input_map, broadcaster_idx) = parse_modules(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (mut modules, mut input_map, broadcaster_idx) = tracing::info_span!("parse")
+        .in_scope(|| parse_modules(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
     let mut lo_pulses = 0;
     let mut hi_pulses = 0;
 
@@ -66,15 +70,17 @@ pub fn process(input: &str) -> usize {
         }
     }
 
-    lo_pulses * hi_pulses
+    Ok(lo_pulses * hi_pulses)
 }
 
-fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize) {
+type ParseOutput = (Vec<Module>, Vec<Vec<(usize, bool)>>, usize);
+
+fn parse_modules(input: &str) -> Result<ParseOutput, ParseError> {
     let mut id_to_index: HashMap<&str, usize> = input
         .lines()
         .enumerate()
         .map(|(idx, line)| {
-            let (start, _) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, _) = split_module(line)?;
             let module_type = match &start[0..1] {
                 "%" => ModuleType::FlipFlop,
                 "&" => ModuleType::Conjunction,
@@ -86,14 +92,14 @@ fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize) {
             } else {
                 &start[1..]
             };
-            (id, idx)
+            Ok((id, idx))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     let module_map: Vec<Module> = input
         .lines()
         .map(|line| {
-            let (start, targets) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, targets) = split_module(line).expect("Checked when indexing");
             let outputs: Vec<usize> = targets
                 .split(", ")
                 .map(|x| {
@@ -131,13 +137,18 @@ fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize) {
             previous.push((idx, false));
         }
     });
-    (
-        module_map,
-        input_map,
-        *id_to_index
-            .get("broadcaster")
-            .expect("contains broadcaster"),
-    )
+    let broadcaster = *id_to_index
+        .get("broadcaster")
+        .ok_or_else(|| ParseError::new(input, "there's no broadcaster module"))?;
+    Ok((module_map, input_map, broadcaster))
+}
+
+fn split_module(line: &str) -> Result<(&str, &str), ParseError> {
+    let (start, targets) = parse::split_once(line, " -> ")?;
+    if start.get(0..1).is_none() {
+        return Err(ParseError::expected(line, "a module name"));
+    }
+    Ok((start, targets))
 }
 
 #[derive(Debug)]
@@ -189,7 +200,7 @@ mod tests {
 %b -> c
 %c -> inv
 &inv -> a";
-        assert_eq!(32000000, process(input));
+        assert_eq!(Ok(32000000), process(input));
     }
 
     #[test]
@@ -199,6 +210,6 @@ mod tests {
 &inv -> b
 %b -> con
 &con -> output";
-        assert_eq!(11687500, process(input));
+        assert_eq!(Ok(11687500), process(input));
     }
 }
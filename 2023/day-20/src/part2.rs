@@ -1,16 +1,28 @@
 use std::collections::VecDeque;
 
+use aoc_common::{parse, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (mut modules, mut input_map) = parse_modules(input);
-    let mut loops: Vec<(&str, usize)> = input_map
-        .get(input_map.get("rx").unwrap()[0].0)
-        .unwrap()
-        .iter()
-        .map(|(id, _)| (*id, 0))
-        .collect();
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (mut modules, mut input_map) = parse_modules(input).map_err(|err| err.locate(input))?;
+    let rx_inputs = input_map
+        .get("rx")
+        .ok_or_else(|| ParseError::new(input, "no module sends pulses to rx").locate(input))?;
+    // rx goes low once every input of the conjunction before it has sent a high pulse, and
+    // each of those does so on its own cycle
+    let feeder_inputs = rx_inputs
+        .first()
+        .filter(|_| rx_inputs.len() == 1)
+        .filter(|(feeder, _)| {
+            modules
+                .get(feeder)
+                .is_some_and(|module| module.module_type == ModuleType::Conjunction)
+        })
+        .and_then(|(feeder, _)| input_map.get(feeder))
+        .filter(|inputs| !inputs.is_empty())
+        .ok_or_else(|| ParseError::new(input, "rx has to be fed by a single conjunction"))?;
+    let mut loops: Vec<(&str, usize)> = feeder_inputs.iter().map(|(id, _)| (*id, 0)).collect();
 
     let mut values = 0;
     let mut i: usize = 0;
@@ -75,7 +87,7 @@ pub fn process(input: &str) -> usize {
                             }
                         }
                         if values == loops.len() {
-                            return lcm_arr(loops);
+                            return Ok(lcm_arr(loops));
                         }
                     }
                 }
@@ -107,16 +119,16 @@ fn gcd(first: usize, second: usize) -> usize {
     }
 }
 
-fn parse_modules<'a>(
-    input: &'a str,
-) -> (
+type ParseOutput<'a> = (
     HashMap<&'a str, Module<'a>>,
-    HashMap<&'a str, Vec<(&str, bool)>>,
-) {
-    let module_map: HashMap<&'a str, Module<'a>> = input
+    HashMap<&'a str, Vec<(&'a str, bool)>>,
+);
+
+fn parse_modules(input: &str) -> Result<ParseOutput<'_>, ParseError> {
+    let module_map: HashMap<&str, Module> = input
         .lines()
         .map(|line| {
-            let (start, targets) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, targets) = split_module(line)?;
             let outputs: Vec<&str> = targets.split(", ").collect();
             let module_type = match &start[0..1] {
                 "%" => ModuleType::FlipFlop,
@@ -130,18 +142,18 @@ fn parse_modules<'a>(
                 &start[1..]
             };
 
-            (
+            Ok((
                 id,
                 Module {
                     module_type,
                     outputs,
                     state: false,
                 },
-            )
+            ))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    let mut input_map: HashMap<&'a str, Vec<(&str, bool)>> = HashMap::new();
+    let mut input_map: HashMap<&str, Vec<(&str, bool)>> = HashMap::new();
     module_map.iter().for_each(|(id, module)| {
         for output in module.outputs.iter() {
             let previous = input_map.get(output);
@@ -154,7 +166,15 @@ fn parse_modules<'a>(
             }
         }
     });
-    (module_map, input_map)
+    Ok((module_map, input_map))
+}
+
+fn split_module(line: &str) -> Result<(&str, &str), ParseError> {
+    let (start, targets) = parse::split_once(line, " -> ")?;
+    if start.get(0..1).is_none() {
+        return Err(ParseError::expected(line, "a module name"));
+    }
+    Ok((start, targets))
 }
 
 #[derive(Debug)]
@@ -1,17 +1,24 @@
 use std::collections::VecDeque;
 
+use aoc_common::{parse, ParseError};
 use hashbrown::HashMap;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (mut modules, mut input_map, broadcaster_idx, rx_idx) = parse_modules(input);
-    // rx idx
-    let mut loops: Vec<(usize, usize)> = input_map
-        .get(input_map.get(rx_idx).unwrap()[0].0)
-        .unwrap()
-        .iter()
-        .map(|(id, _)| (*id, 0))
-        .collect();
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (mut modules, mut input_map, broadcaster_idx, rx_idx) = tracing::info_span!("parse")
+        .in_scope(|| parse_modules(input))
+        .map_err(|err| err.locate(input))?;
+    let _solve = tracing::info_span!("solve").entered();
+    // rx goes low once every input of the conjunction before it has sent a high pulse
+    let rx_inputs = &input_map[rx_idx];
+    let feeder_inputs = rx_inputs
+        .first()
+        .filter(|_| rx_inputs.len() == 1)
+        .filter(|(feeder, _)| modules[*feeder].module_type == ModuleType::Conjunction)
+        .map(|(feeder, _)| &input_map[*feeder])
+        .filter(|inputs| !inputs.is_empty())
+        .ok_or_else(|| ParseError::new(input, "rx has to be fed by a single conjunction"))?;
+    let mut loops: Vec<(usize, usize)> = feeder_inputs.iter().map(|(id, _)| (*id, 0)).collect();
 
     let mut values = 0;
     let mut i: usize = 0;
@@ -74,7 +81,7 @@ pub fn process(input: &str) -> usize {
                             }
                         }
                         if values == loops.len() {
-                            return lcm_arr(loops);
+                            return Ok(lcm_arr(loops));
                         }
                     }
                 }
@@ -106,12 +113,14 @@ fn gcd(first: usize, second: usize) -> usize {
     }
 }
 
-fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize, usize) {
+type ParseOutput = (Vec<Module>, Vec<Vec<(usize, bool)>>, usize, usize);
+
+fn parse_modules(input: &str) -> Result<ParseOutput, ParseError> {
     let mut id_to_index: HashMap<&str, usize> = input
         .lines()
         .enumerate()
         .map(|(idx, line)| {
-            let (start, _) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, _) = split_module(line)?;
             let module_type = match &start[0..1] {
                 "%" => ModuleType::FlipFlop,
                 "&" => ModuleType::Conjunction,
@@ -123,14 +132,14 @@ fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize, u
             } else {
                 &start[1..]
             };
-            (id, idx)
+            Ok((id, idx))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     let module_map: Vec<Module> = input
         .lines()
         .map(|line| {
-            let (start, targets) = line.split_once(" -> ").expect("It's got an arrow");
+            let (start, targets) = split_module(line).expect("Checked when indexing");
             let outputs: Vec<usize> = targets
                 .split(", ")
                 .map(|x| {
@@ -168,16 +177,21 @@ fn parse_modules(input: &str) -> (Vec<Module>, Vec<Vec<(usize, bool)>>, usize, u
             previous.push((idx, false));
         }
     });
-    (
-        module_map,
-        input_map,
-        *id_to_index
-            .get("broadcaster")
-            .expect("contains broadcaster"),
-        *id_to_index
-            .get("rx")
-            .expect("contains rx")
-    )
+    let broadcaster = *id_to_index
+        .get("broadcaster")
+        .ok_or_else(|| ParseError::new(input, "there's no broadcaster module"))?;
+    let rx = *id_to_index
+        .get("rx")
+        .ok_or_else(|| ParseError::new(input, "no module sends pulses to rx"))?;
+    Ok((module_map, input_map, broadcaster, rx))
+}
+
+fn split_module(line: &str) -> Result<(&str, &str), ParseError> {
+    let (start, targets) = parse::split_once(line, " -> ")?;
+    if start.get(0..1).is_none() {
+        return Err(ParseError::expected(line, "a module name"));
+    }
+    Ok((start, targets))
 }
 
 #[derive(Debug)]
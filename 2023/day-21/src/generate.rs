@@ -0,0 +1,94 @@
+use aoc_common::generate::{Generated, Rng};
+use std::collections::VecDeque;
+
+// `size` is the width and height of the garden, rounded up to odd so the start sits in
+// the middle. the middle row and column and the border are left clear like the real
+// thing, and plots the rocks wall off are filled in. part 1 comes from a walk over the
+// one tile; the infinite garden of part 2 is left to the solutions
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let size = size.max(5) | 1;
+    let middle = size / 2;
+    let mut grid: Vec<Vec<u8>> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| {
+                    let clear = row == middle
+                        || col == middle
+                        || row % (size - 1) == 0
+                        || col % (size - 1) == 0;
+                    if !clear && rng.usize(..100) < 12 {
+                        b'#'
+                    } else {
+                        b'.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let steps = distances(&grid, middle);
+    for (line, steps) in grid.iter_mut().zip(&steps) {
+        for (tile, steps) in line.iter_mut().zip(steps) {
+            if steps.is_none() {
+                *tile = b'#';
+            }
+        }
+    }
+    grid[middle][middle] = b'S';
+
+    let part1 = steps
+        .iter()
+        .flatten()
+        .flatten()
+        .filter(|&&steps| steps <= 64 && steps % 2 == 0)
+        .count();
+
+    let mut input = String::with_capacity(size * (size + 1));
+    for line in grid {
+        input.push_str(&String::from_utf8(line).expect("Ascii"));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(part1),
+        part2: None,
+    }
+}
+
+fn distances(grid: &[Vec<u8>], middle: usize) -> Vec<Vec<Option<usize>>> {
+    let size = grid.len();
+    let mut steps = vec![vec![None; size]; size];
+    steps[middle][middle] = Some(0);
+    let mut queue = VecDeque::from([(middle, middle, 0)]);
+    while let Some((row, col, taken)) = queue.pop_front() {
+        let neighbours = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for (row, col) in neighbours {
+            if row < size && col < size && grid[row][col] == b'.' && steps[row][col].is_none() {
+                steps[row][col] = Some(taken + 1);
+                queue.push_back((row, col, taken + 1));
+            }
+        }
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part1;
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 131);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+        }
+    }
+}
@@ -1,11 +1,17 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (grid, start) = parse_input(input);
+use aoc_common::{parse, ParseError};
 
-    count_positions(&grid, start, 64)
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (grid, start) = parse_input(input).map_err(|err| err.locate(input))?;
+
+    Ok(count_positions(&grid, start, 64))
 }
 
-fn parse_input(input: &str) -> (Grid, Coord) {
+fn parse_input(input: &str) -> Result<(Grid, Coord), ParseError> {
+    parse::grid(input, ".#S", "'.', '#' or 'S'")?;
+    if !input.contains('S') {
+        return Err(ParseError::new(input, "there's no starting position 'S'"));
+    }
     let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
     let height = grid.len();
     let width = grid[0].len();
@@ -22,14 +28,14 @@ fn parse_input(input: &str) -> (Grid, Coord) {
         }
     }
 
-    (
+    Ok((
         Grid {
             grid,
             height,
             width,
         },
         start,
-    )
+    ))
 }
 
 fn count_positions(grid: &Grid, start: Coord, iterations: usize) -> usize {
@@ -127,7 +133,8 @@ mod tests {
 .##.#.####.
 .##..##.##.
 ...........",
-        );
+        )
+        .expect("Valid garden");
 
         let count = count_positions(&grid, start, 6);
         assert_eq!(16, count);
@@ -1,14 +1,19 @@
+use aoc_common::{parse, ParseError};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> usize {
-    let (grid, start) = parse_input(input);
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let (grid, start) = parse_input(input).map_err(|err| err.locate(input))?;
 
     let ans = count_positions(&grid, start, 26501365);
 
-    ans
+    Ok(ans)
 }
 
-fn parse_input(input: &str) -> (Grid, Coord) {
+fn parse_input(input: &str) -> Result<(Grid, Coord), ParseError> {
+    parse::grid(input, ".#S", "'.', '#' or 'S'")?;
+    if !input.contains('S') {
+        return Err(ParseError::new(input, "there's no starting position 'S'"));
+    }
     let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
     let height = grid.len();
     let width = grid[0].len();
@@ -25,14 +30,14 @@ fn parse_input(input: &str) -> (Grid, Coord) {
         }
     }
 
-    (
+    Ok((
         Grid {
             grid,
             height,
             width,
         },
         start,
-    )
+    ))
 }
 
 fn count_positions(grid: &Grid, start: Coord, iterations: usize) -> usize {
@@ -175,7 +180,8 @@ mod tests {
 .##.#.####.
 .##..##.##.
 ...........",
-        );
+        )
+        .expect("Valid garden");
 
         let count = count_positions_using_interpolation(&grid, vec![start], iterations);
         assert_eq!(expected, count);
@@ -1,16 +1,22 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
-    todo!("day 01 - part 1");
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<String, ParseError> {
+    Err(ParseError::new(input, "day-23 part 1 isn't solved yet"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::{answer, normalise};
 
     #[test]
-    fn test_process() {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input));
+    #[ignore = "fill in the answer next to part1: in examples/expected"]
+    fn test_example() {
+        let expected = answer(include_str!("../examples/expected"), "part1")
+            .expect("The example's answer is in examples/expected");
+        let input = normalise(include_str!("../examples/example1.txt"));
+        let result = process(&input).map(|answer| answer.to_string());
+        assert_eq!(Ok(expected.to_string()), result);
     }
 }
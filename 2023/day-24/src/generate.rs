@@ -0,0 +1,47 @@
+use aoc_common::generate::{Generated, Rng};
+
+// `size` is the number of hailstones. a rock is thrown first and each hailstone is
+// placed so that it meets the rock at its own time, which pins down the part 2
+// answer. part 1's crossings are left to the solution
+pub fn generate(seed: u64, size: usize) -> Generated<usize, i128> {
+    let mut rng = Rng::with_seed(seed);
+    let position: [i128; 3] =
+        std::array::from_fn(|_| rng.i128(200_000_000_000_000..=400_000_000_000_000));
+    let velocity: [i128; 3] = std::array::from_fn(|_| rng.i128(-250..=250));
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let time = rng.i128(10_000_000_000..=500_000_000_000);
+        let hail_velocity = loop {
+            let hail_velocity: [i128; 3] = std::array::from_fn(|_| rng.i128(-300..=300));
+            if hail_velocity != velocity {
+                break hail_velocity;
+            }
+        };
+        let hail_position: [i128; 3] =
+            std::array::from_fn(|i| position[i] + (velocity[i] - hail_velocity[i]) * time);
+        let [x, y, z] = hail_position;
+        let [dx, dy, dz] = hail_velocity;
+        input.push_str(&format!("{x}, {y}, {z} @ {dx}, {dy}, {dz}\n"));
+    }
+
+    Generated {
+        input,
+        part1: None,
+        part2: Some(position.iter().sum()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2;
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 300);
+            assert_eq!(generated.part2, part2::process(&generated.input).ok());
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use crate::linear_system::{self, LinearSystemError};
+use crate::rational::{gcd, Rational};
+use aoc_common::{parse, ParseError};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vec3 {
+    pub x: i128,
+    pub y: i128,
+    pub z: i128,
+}
+
+impl Vec3 {
+    pub fn new(x: i128, y: i128, z: i128) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn cross(&self, other: &Vec3) -> Vec3 {
+        Vec3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.x == 0 && self.y == 0 && self.z == 0
+    }
+
+    fn reduced(&self) -> Vec3 {
+        let divisor = gcd(gcd(self.x, self.y), self.z);
+        Vec3::new(self.x / divisor, self.y / divisor, self.z / divisor)
+    }
+
+    fn components(&self) -> [i128; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<i128> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: i128) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathIntersection {
+    Crossing {
+        x: Rational,
+        y: Rational,
+        time_a: Rational,
+        time_b: Rational,
+    },
+    Parallel,
+    Identical,
+}
+
+impl Hailstone {
+    pub fn relative_to(&self, other: &Hailstone) -> Hailstone {
+        Hailstone {
+            position: self.position - other.position,
+            velocity: self.velocity - other.velocity,
+        }
+    }
+
+    // ignores the z axis entirely, as part 1 asks
+    pub fn path_intersection_xy(&self, other: &Hailstone) -> PathIntersection {
+        let (a, b) = (self, other);
+        let offset = b.position - a.position;
+        let det = cross_xy(&a.velocity, &b.velocity);
+
+        if det == 0 {
+            return match cross_xy(&offset, &a.velocity) {
+                0 => PathIntersection::Identical,
+                _ => PathIntersection::Parallel,
+            };
+        }
+
+        let time_a = Rational::new(cross_xy(&offset, &b.velocity), det);
+        let time_b = Rational::new(cross_xy(&offset, &a.velocity), det);
+        PathIntersection::Crossing {
+            x: Rational::from(a.position.x) + time_a * Rational::from(a.velocity.x),
+            y: Rational::from(a.position.y) + time_a * Rational::from(a.velocity.y),
+            time_a,
+            time_b,
+        }
+    }
+}
+
+fn cross_xy(a: &Vec3, b: &Vec3) -> i128 {
+    a.x * b.y - a.y * b.x
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IntersectionSummary {
+    pub inside: usize,
+    pub outside: usize,
+    pub in_the_past: usize,
+    pub parallel: usize,
+    pub identical: usize,
+}
+
+pub fn count_intersections(hailstones: &[Hailstone], min: i128, max: i128) -> IntersectionSummary {
+    let (min, max) = (Rational::from(min), Rational::from(max));
+    let mut summary = IntersectionSummary::default();
+
+    for (i, a) in hailstones.iter().enumerate() {
+        for b in &hailstones[i + 1..] {
+            match a.path_intersection_xy(b) {
+                PathIntersection::Parallel => summary.parallel += 1,
+                PathIntersection::Identical => summary.identical += 1,
+                PathIntersection::Crossing { time_a, time_b, .. }
+                    if time_a.signum() < 0 || time_b.signum() < 0 =>
+                {
+                    summary.in_the_past += 1
+                }
+                PathIntersection::Crossing { x, y, .. } => {
+                    match min <= x && x <= max && min <= y && y <= max {
+                        true => summary.inside += 1,
+                        false => summary.outside += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    summary
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rock {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrajectoryError {
+    TooFewHailstones,
+    NoUniqueTrajectory,
+    NonIntegral,
+    Inconsistent,
+}
+
+impl fmt::Display for TrajectoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrajectoryError::TooFewHailstones => write!(f, "it takes three hailstones to aim"),
+            TrajectoryError::NoUniqueTrajectory => {
+                write!(f, "the hailstones don't pin down a single throw")
+            }
+            TrajectoryError::NonIntegral => write!(f, "the throw isn't at whole numbers"),
+            TrajectoryError::Inconsistent => write!(f, "no throw hits every hailstone"),
+        }
+    }
+}
+
+// Working relative to the first hailstone pins it at the origin, so the rock's
+// path has to go through the origin. It also lies in the plane through the
+// origin spanned by every other hailstone's path, so the direction is the
+// intersection of two such planes. That keeps every value well inside i128,
+// unlike eliminating the full 6x6 system directly.
+pub fn find_rock(hailstones: &[Hailstone]) -> Result<Rock, TrajectoryError> {
+    let Some((reference, rest)) = hailstones.split_first() else {
+        return Err(TrajectoryError::TooFewHailstones);
+    };
+    if rest.len() < 2 {
+        return Err(TrajectoryError::TooFewHailstones);
+    }
+
+    let relative: Vec<Hailstone> = rest.iter().map(|h| h.relative_to(reference)).collect();
+    let normals: Vec<Vec3> = relative
+        .iter()
+        .map(|h| h.position.cross(&h.velocity))
+        .collect();
+
+    // a pair that doesn't pin down a rock is skipped rather than failing the search. if none
+    // do, a pair that was inconsistent is reported ahead of one that wasn't integral
+    let mut failure = TrajectoryError::NoUniqueTrajectory;
+    for i in 0..relative.len() {
+        for j in i + 1..relative.len() {
+            if normals[i].is_zero() || normals[j].is_zero() {
+                continue;
+            }
+            let direction = normals[i].cross(&normals[j]);
+            if direction.is_zero() {
+                continue;
+            }
+            let direction = direction.reduced();
+
+            let (time_a, hit_a) = match meet_line(&relative[i], &direction) {
+                Ok(hit) => hit,
+                Err(LinearSystemError::Underdetermined) => continue,
+                Err(LinearSystemError::Inconsistent) => {
+                    failure = TrajectoryError::Inconsistent;
+                    continue;
+                }
+            };
+            let (time_b, hit_b) = match meet_line(&relative[j], &direction) {
+                Ok(hit) => hit,
+                Err(LinearSystemError::Underdetermined) => continue,
+                Err(LinearSystemError::Inconsistent) => {
+                    failure = TrajectoryError::Inconsistent;
+                    continue;
+                }
+            };
+            if time_a == time_b {
+                continue;
+            }
+
+            let elapsed = time_b - time_a;
+            let velocity: Vec<Rational> = (0..3).map(|k| (hit_b[k] - hit_a[k]) / elapsed).collect();
+            let position: Vec<Rational> = (0..3).map(|k| hit_a[k] - time_a * velocity[k]).collect();
+
+            let (Some(velocity), Some(position)) = (to_vec3(&velocity), to_vec3(&position)) else {
+                if failure == TrajectoryError::NoUniqueTrajectory {
+                    failure = TrajectoryError::NonIntegral;
+                }
+                continue;
+            };
+            let rock = Rock {
+                position: position + reference.position,
+                velocity: velocity + reference.velocity,
+            };
+
+            match hailstones.iter().all(|h| rock.hits(h)) {
+                true => return Ok(rock),
+                false => failure = TrajectoryError::Inconsistent,
+            }
+        }
+    }
+
+    Err(failure)
+}
+
+impl Rock {
+    pub fn hits(&self, hailstone: &Hailstone) -> bool {
+        let offset = self.position - hailstone.position;
+        let closing = self.velocity - hailstone.velocity;
+        if !offset.cross(&closing).is_zero() {
+            return false;
+        }
+        // collinear, so the collision time is offset / -closing on any non-zero axis
+        match closing
+            .components()
+            .iter()
+            .zip(offset.components())
+            .find(|(v, _)| **v != 0)
+        {
+            Some((v, p)) => p % v == 0 && -p / v >= 0,
+            None => offset.is_zero(),
+        }
+    }
+}
+
+// where (and when) the hailstone crosses the line through the origin with the given direction
+fn meet_line(
+    hailstone: &Hailstone,
+    direction: &Vec3,
+) -> Result<(Rational, [Rational; 3]), LinearSystemError> {
+    let p = hailstone.position.components();
+    let v = hailstone.velocity.components();
+    let d = direction.components();
+    // p + t * v = s * d
+    let rows = (0..3)
+        .map(|k| {
+            vec![
+                Rational::from(v[k]),
+                Rational::from(-d[k]),
+                Rational::from(-p[k]),
+            ]
+        })
+        .collect();
+
+    let solution = linear_system::solve(rows)?;
+    let time = solution[0];
+    let hit = [0, 1, 2].map(|k| Rational::from(p[k]) + time * Rational::from(v[k]));
+    Ok((time, hit))
+}
+
+fn to_vec3(values: &[Rational]) -> Option<Vec3> {
+    Some(Vec3::new(
+        values[0].to_integer()?,
+        values[1].to_integer()?,
+        values[2].to_integer()?,
+    ))
+}
+
+pub fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input.lines().map(parse_hailstone).collect()
+}
+
+fn parse_hailstone(line: &str) -> Result<Hailstone, ParseError> {
+    let (position, velocity) = parse::split_once(line, "@")?;
+    Ok(Hailstone {
+        position: parse_vec3(position)?,
+        velocity: parse_vec3(velocity)?,
+    })
+}
+
+fn parse_vec3(s: &str) -> Result<Vec3, ParseError> {
+    let nums = s
+        .split(',')
+        .map(|x| parse::number::<i128>(x.trim()))
+        .collect::<Result<Vec<i128>, ParseError>>()?;
+    let [x, y, z] = nums[..] else {
+        return Err(ParseError::expected(s, "3 components"));
+    };
+    Ok(Vec3::new(x, y, z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_path_intersection() {
+        let hailstones = parse_hailstones(INPUT).expect("Valid hailstones");
+        assert_eq!(
+            PathIntersection::Crossing {
+                x: Rational::new(43, 3),
+                y: Rational::new(46, 3),
+                time_a: Rational::new(7, 3),
+                time_b: Rational::new(11, 3),
+            },
+            hailstones[0].path_intersection_xy(&hailstones[1])
+        );
+        assert_eq!(
+            PathIntersection::Parallel,
+            hailstones[1].path_intersection_xy(&hailstones[2])
+        );
+        let doubled = Hailstone {
+            position: hailstones[0].position + hailstones[0].velocity * 5,
+            velocity: hailstones[0].velocity * 2,
+        };
+        assert_eq!(
+            PathIntersection::Identical,
+            hailstones[0].path_intersection_xy(&doubled)
+        );
+    }
+
+    #[test]
+    fn test_count_intersections() {
+        let hailstones = parse_hailstones(INPUT).expect("Valid hailstones");
+        assert_eq!(
+            IntersectionSummary {
+                inside: 2,
+                outside: 3,
+                in_the_past: 4,
+                parallel: 1,
+                identical: 0,
+            },
+            count_intersections(&hailstones, 7, 27)
+        );
+    }
+
+    #[test]
+    fn test_find_rock() {
+        let hailstones = parse_hailstones(INPUT).expect("Valid hailstones");
+        assert_eq!(
+            Ok(Rock {
+                position: Vec3::new(24, 13, 10),
+                velocity: Vec3::new(-3, 1, 2),
+            }),
+            find_rock(&hailstones)
+        );
+        assert_eq!(
+            Err(TrajectoryError::TooFewHailstones),
+            find_rock(&hailstones[..2])
+        );
+    }
+
+    #[test]
+    fn test_no_rock() {
+        // every pair gives a rock that misses the last hailstone, so each is tried and the
+        // search fails at the end
+        let input = format!("{INPUT}\n1, 1, 1 @ 1, 1, 1");
+        let hailstones = parse_hailstones(&input).expect("Valid hailstones");
+        assert_eq!(Err(TrajectoryError::Inconsistent), find_rock(&hailstones));
+    }
+}
@@ -1,3 +1,7 @@
 pub mod part1;
 pub mod part2;
 
+pub mod hailstone;
+pub mod linear_system;
+pub mod rational;
+pub mod generate;
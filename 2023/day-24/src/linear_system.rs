@@ -0,0 +1,83 @@
+use crate::rational::Rational;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LinearSystemError {
+    Inconsistent,
+    Underdetermined,
+}
+
+// each row holds the coefficients followed by the right hand side
+pub fn solve(mut rows: Vec<Vec<Rational>>) -> Result<Vec<Rational>, LinearSystemError> {
+    let unknowns = match rows.first() {
+        Some(row) => row.len() - 1,
+        None => return Err(LinearSystemError::Underdetermined),
+    };
+
+    let mut pivot_row = 0;
+    for col in 0..unknowns {
+        let Some(pivot) = (pivot_row..rows.len()).find(|&row| !rows[row][col].is_zero()) else {
+            return Err(LinearSystemError::Underdetermined);
+        };
+        rows.swap(pivot_row, pivot);
+
+        let pivot_value = rows[pivot_row][col];
+        rows[pivot_row]
+            .iter_mut()
+            .for_each(|value| *value = *value / pivot_value);
+
+        let pivot_values = rows[pivot_row].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == pivot_row || row[col].is_zero() {
+                continue;
+            }
+            let factor = row[col];
+            row.iter_mut()
+                .zip(&pivot_values)
+                .skip(col)
+                .for_each(|(value, pivot)| *value = *value - *pivot * factor);
+        }
+        pivot_row += 1;
+    }
+
+    // any leftover equations have been reduced to 0 = rhs
+    if rows[pivot_row..].iter().any(|row| !row[unknowns].is_zero()) {
+        return Err(LinearSystemError::Inconsistent);
+    }
+
+    Ok(rows[..unknowns].iter().map(|row| row[unknowns]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(rows: &[&[i128]]) -> Vec<Vec<Rational>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&x| Rational::from(x)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let rows = system(&[&[2, 1, 5], &[1, -1, 1], &[3, 0, 6]]);
+        assert_eq!(Ok(vec![Rational::from(2), Rational::from(1)]), solve(rows));
+    }
+
+    #[test]
+    fn test_solve_fractions() {
+        let rows = system(&[&[2, 0, 1], &[0, 3, 1]]);
+        assert_eq!(
+            Ok(vec![Rational::new(1, 2), Rational::new(1, 3)]),
+            solve(rows)
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let rows = system(&[&[1, 1, 2], &[2, 2, 4]]);
+        assert_eq!(Err(LinearSystemError::Underdetermined), solve(rows));
+
+        let rows = system(&[&[1, 0, 2], &[0, 1, 4], &[1, 1, 7]]);
+        assert_eq!(Err(LinearSystemError::Inconsistent), solve(rows));
+    }
+}
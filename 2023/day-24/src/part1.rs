@@ -1,6 +1,14 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
-    todo!("day 01 - part 1");
+use crate::hailstone::{count_intersections, parse_hailstones};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    process_in_area(input, 200_000_000_000_000, 400_000_000_000_000)
+}
+
+pub fn process_in_area(input: &str, min: i128, max: i128) -> Result<usize, ParseError> {
+    let hailstones = parse_hailstones(input).map_err(|err| err.locate(input))?;
+    Ok(count_intersections(&hailstones, min, max).inside)
 }
 
 #[cfg(test)]
@@ -9,8 +17,11 @@ mod tests {
 
     #[test]
     fn test_process() {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input));
+        let input = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+        assert_eq!(Ok(2), process_in_area(input, 7, 27));
     }
 }
@@ -1,6 +1,12 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
-    todo!("day 01 - part 2");
+use crate::hailstone::{find_rock, parse_hailstones};
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<i128, ParseError> {
+    let hailstones = parse_hailstones(input).map_err(|err| err.locate(input))?;
+    let rock = find_rock(&hailstones)
+        .map_err(|err| ParseError::new(input, err.to_string()).locate(input))?;
+    Ok(rock.position.x + rock.position.y + rock.position.z)
 }
 
 #[cfg(test)]
@@ -9,8 +15,20 @@ mod tests {
 
     #[test]
     fn test_process() {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input));
+        let input = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+        assert_eq!(Ok(47), process(input));
+    }
+
+    #[test]
+    fn test_no_rock() {
+        let error = process("19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1, -2").unwrap_err();
+        assert_eq!("it takes three hailstones to aim", error.message);
+        // parallel hailstones don't say which way to throw
+        let error = process("0, 0, 0 @ 1, 0, 0\n0, 1, 0 @ 1, 0, 0\n0, 2, 0 @ 1, 0, 0").unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
     }
 }
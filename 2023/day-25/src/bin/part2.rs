@@ -1,9 +0,0 @@
-use day_25::part2::process;
-
-#[tracing::instrument]
-fn main() -> Result<(), ()> {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
-    println!("{}", result);
-    Ok(())
-}
@@ -0,0 +1,71 @@
+use aoc_common::generate::{Generated, Rng};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+// `size` is the number of components. they're split into two groups, each wired
+// so that every component joins at least four others already in its group, and
+// then three wires are added across. cutting those is the only way to split it
+pub fn generate(seed: u64, size: usize) -> Generated<usize> {
+    let mut rng = Rng::with_seed(seed);
+    let size = size.max(12);
+    let mut seen = HashSet::new();
+    let names: Vec<String> = (0..size)
+        .map(|_| loop {
+            let name: String = (0..3).map(|_| rng.lowercase()).collect();
+            if seen.insert(name.clone()) {
+                break name;
+            }
+        })
+        .collect();
+
+    let first = rng.usize(size * 2 / 5..=size * 3 / 5);
+    let mut wires = BTreeSet::new();
+    for group in [0..first, first..size] {
+        for (i, component) in group.clone().enumerate() {
+            // the first few are wired to each other, the rest to some that came before
+            let mut joined = BTreeSet::new();
+            while joined.len() < i.min(4) {
+                joined.insert(rng.usize(group.start..component));
+            }
+            wires.extend(joined.into_iter().map(|other| (component, other)));
+        }
+    }
+    for _ in 0..3 {
+        while !wires.insert((rng.usize(..first), rng.usize(first..size))) {}
+    }
+
+    // each wire is listed once, under one of its ends
+    let mut lines: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for &(a, b) in &wires {
+        let (from, to) = if rng.bool() { (a, b) } else { (b, a) };
+        lines.entry(from).or_default().push(&names[to]);
+    }
+    let mut lines: Vec<String> = lines
+        .into_iter()
+        .map(|(from, to)| format!("{}: {}", names[from], to.join(" ")))
+        .collect();
+    rng.shuffle(&mut lines);
+
+    Generated {
+        input: lines.join("\n") + "\n",
+        part1: Some(first * (size - first)),
+        part2: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part1_stoer_wagner};
+
+    #[test]
+    fn test_generated() {
+        for seed in 0..5 {
+            let generated = generate(seed, 60);
+            assert_eq!(generated.part1, part1::process(&generated.input).ok());
+            assert_eq!(
+                generated.part1,
+                part1_stoer_wagner::process(&generated.input).ok()
+            );
+        }
+    }
+}
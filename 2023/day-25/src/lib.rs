@@ -1,3 +1,5 @@
 pub mod part1;
-pub mod part2;
+pub mod part1_stoer_wagner;
 
+pub mod wiring;
+pub mod generate;
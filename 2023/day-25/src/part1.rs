@@ -1,6 +1,19 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
-    todo!("day 01 - part 1");
+use crate::wiring::Graph;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let graph = Graph::parse(input).map_err(|err| err.locate(input))?;
+    graph
+        .min_cut_of_size(3)
+        .map(|cut| cut.product())
+        .ok_or_else(|| {
+            ParseError::new(
+                input,
+                "there are no three wires that split the machine in two",
+            )
+            .locate(input)
+        })
 }
 
 #[cfg(test)]
@@ -9,8 +22,19 @@ mod tests {
 
     #[test]
     fn test_process() {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input));
+        let input = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+        assert_eq!(Ok(54), process(input));
     }
 }
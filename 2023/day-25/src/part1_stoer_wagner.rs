@@ -0,0 +1,36 @@
+use crate::wiring::Graph;
+use aoc_common::ParseError;
+
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> Result<usize, ParseError> {
+    let graph = Graph::parse(input).map_err(|err| err.locate(input))?;
+    graph
+        .stoer_wagner()
+        .map(|cut| cut.product())
+        .ok_or_else(|| {
+            ParseError::new(input, "the machine needs at least two components").locate(input)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+        assert_eq!(Ok(54), process(input));
+    }
+}
@@ -0,0 +1,309 @@
+use aoc_common::{parse, ParseError};
+use hashbrown::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+pub struct Graph {
+    names: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    // (neighbour, edge index)
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub edges: Vec<(usize, usize)>,
+    pub component_sizes: (usize, usize),
+}
+
+impl Cut {
+    pub fn product(&self) -> usize {
+        self.component_sizes.0 * self.component_sizes.1
+    }
+}
+
+impl Graph {
+    pub fn parse(input: &str) -> Result<Graph, ParseError> {
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        let mut names = Vec::new();
+        let mut edges = Vec::new();
+
+        for line in input.lines() {
+            let (source, targets) = parse::split_once(line, ": ")?;
+            let source = index_of(source, &mut indices, &mut names);
+            targets.split_ascii_whitespace().for_each(|target| {
+                let target = index_of(target, &mut indices, &mut names);
+                edges.push((source, target));
+            });
+        }
+
+        let mut adjacency = vec![Vec::new(); names.len()];
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            adjacency[a].push((b, i));
+            adjacency[b].push((a, i));
+        }
+
+        Ok(Graph {
+            names,
+            edges,
+            adjacency,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    // Stoer–Wagner with a lazy max-heap for the maximum adjacency ordering
+    pub fn stoer_wagner(&self) -> Option<Cut> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let mut weights: Vec<HashMap<usize, u32>> = vec![HashMap::new(); self.len()];
+        for &(a, b) in &self.edges {
+            *weights[a].entry(b).or_default() += 1;
+            *weights[b].entry(a).or_default() += 1;
+        }
+        let mut members: Vec<Vec<usize>> = (0..self.len()).map(|i| vec![i]).collect();
+        let mut active: Vec<usize> = (0..self.len()).collect();
+
+        let mut best: Option<(u32, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let mut added = vec![false; self.len()];
+            let mut connectivity = vec![0u32; self.len()];
+            let mut heap = BinaryHeap::new();
+            heap.push((0u32, Reverse(active[0])));
+
+            let mut previous = active[0];
+            let mut last = active[0];
+            let mut cut_of_phase = 0;
+            let mut remaining = active.len();
+
+            while remaining > 0 {
+                let Some((weight, Reverse(node))) = heap.pop() else {
+                    // disconnected graph, the rest is its own component
+                    break;
+                };
+                if added[node] || weight != connectivity[node] {
+                    continue;
+                }
+                added[node] = true;
+                remaining -= 1;
+                previous = last;
+                last = node;
+                cut_of_phase = weight;
+
+                for (&next, &w) in &weights[node] {
+                    if !added[next] {
+                        connectivity[next] += w;
+                        heap.push((connectivity[next], Reverse(next)));
+                    }
+                }
+            }
+
+            if remaining > 0 {
+                let side = active
+                    .iter()
+                    .filter(|&&node| !added[node])
+                    .flat_map(|&node| members[node].iter().copied())
+                    .collect();
+                return Some(self.cut_from_side(side));
+            }
+
+            if best
+                .as_ref()
+                .is_none_or(|(weight, _)| cut_of_phase < *weight)
+            {
+                best = Some((cut_of_phase, members[last].clone()));
+            }
+
+            // merge last into previous
+            let merged = std::mem::take(&mut members[last]);
+            members[previous].extend(merged);
+            let last_weights = std::mem::take(&mut weights[last]);
+            for (next, w) in last_weights {
+                weights[next].remove(&last);
+                if next != previous {
+                    *weights[previous].entry(next).or_default() += w;
+                    *weights[next].entry(previous).or_default() += w;
+                }
+            }
+            active.retain(|&node| node != last);
+        }
+
+        best.map(|(_, side)| self.cut_from_side(side))
+    }
+
+    // Edmonds–Karp from a fixed source to every other node until the max flow equals the
+    // expected cut size, at which point the residual graph gives us one side of the cut
+    pub fn min_cut_of_size(&self, size: usize) -> Option<Cut> {
+        let source = 0;
+        (1..self.len()).find_map(|sink| {
+            let mut flow = vec![0i8; self.edges.len()];
+            for _ in 0..size {
+                if !self.augment(source, sink, &mut flow) {
+                    return None;
+                }
+            }
+            if self.augment(source, sink, &mut flow) {
+                return None;
+            }
+
+            let reachable = self.residual_reachable(source, &flow);
+            let side = (0..self.len()).filter(|&node| reachable[node]).collect();
+            Some(self.cut_from_side(side))
+        })
+    }
+
+    // flow[e] is +1 when a unit goes from edges[e].0 to edges[e].1, -1 for the other way
+    fn residual(&self, from: usize, edge: usize, flow: &[i8]) -> i8 {
+        match self.edges[edge].0 == from {
+            true => 1 - flow[edge],
+            false => 1 + flow[edge],
+        }
+    }
+
+    fn augment(&self, source: usize, sink: usize, flow: &mut [i8]) -> bool {
+        let mut parent: Vec<Option<(usize, usize)>> = vec![None; self.len()];
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([source]);
+        seen[source] = true;
+
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for &(next, edge) in &self.adjacency[node] {
+                if !seen[next] && self.residual(node, edge, flow) > 0 {
+                    seen[next] = true;
+                    parent[next] = Some((node, edge));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !seen[sink] {
+            return false;
+        }
+
+        let mut node = sink;
+        while let Some((prev, edge)) = parent[node] {
+            match self.edges[edge].0 == prev {
+                true => flow[edge] += 1,
+                false => flow[edge] -= 1,
+            }
+            node = prev;
+        }
+        true
+    }
+
+    fn residual_reachable(&self, source: usize, flow: &[i8]) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![source];
+        seen[source] = true;
+        while let Some(node) = stack.pop() {
+            for &(next, edge) in &self.adjacency[node] {
+                if !seen[next] && self.residual(node, edge, flow) > 0 {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    fn cut_from_side(&self, side: Vec<usize>) -> Cut {
+        let mut in_side = vec![false; self.len()];
+        side.iter().for_each(|&node| in_side[node] = true);
+
+        let edges = self
+            .edges
+            .iter()
+            .filter(|&&(a, b)| in_side[a] != in_side[b])
+            .copied()
+            .collect();
+
+        Cut {
+            edges,
+            component_sizes: (side.len(), self.len() - side.len()),
+        }
+    }
+}
+
+fn index_of<'a>(
+    name: &'a str,
+    indices: &mut HashMap<&'a str, usize>,
+    names: &mut Vec<String>,
+) -> usize {
+    *indices.entry(name).or_insert_with(|| {
+        names.push(name.to_string());
+        names.len() - 1
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    fn edge_names(graph: &Graph, cut: &Cut) -> Vec<(String, String)> {
+        let mut names: Vec<(String, String)> = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let (a, b) = (graph.name(a), graph.name(b));
+                (a.min(b).to_string(), a.max(b).to_string())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn expected_edges() -> Vec<(String, String)> {
+        [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_stoer_wagner() {
+        let graph = Graph::parse(INPUT).expect("Valid graph");
+        let cut = graph.stoer_wagner().expect("Graph has a cut");
+        assert_eq!(expected_edges(), edge_names(&graph, &cut));
+        assert_eq!(54, cut.product());
+        assert_eq!(15, cut.component_sizes.0 + cut.component_sizes.1);
+    }
+
+    #[test]
+    fn test_min_cut_of_size() {
+        let graph = Graph::parse(INPUT).expect("Valid graph");
+        let cut = graph.min_cut_of_size(3).expect("Graph has a 3-cut");
+        assert_eq!(expected_edges(), edge_names(&graph, &cut));
+        assert_eq!(54, cut.product());
+        assert_eq!(None, graph.min_cut_of_size(2));
+    }
+}
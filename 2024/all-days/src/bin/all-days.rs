@@ -1,69 +1,132 @@
+use aoc_common::runner::{self, Outcome, Run};
+use aoc_common::{answer, ParseError};
+use std::fmt::Display;
+use std::sync::OnceLock;
 use std::time::Duration;
 
-#[tracing::instrument]
+const USAGE: &str = "usage: all-days [--timeout <seconds>]
+  --timeout    how long a part gets before it's given up on, 30 seconds by default";
+
+// the answers to the real inputs, as `day-05 part1: <answer>`. empty ones aren't checked
+const ANSWERS: &str = include_str!("../../../answers.txt");
+
+// each part runs in a child process, see `runner::isolated`, so when one gives the wrong
+// answer, panics, crashes or runs past the timeout it's reported and the rest carry on
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+// set in a child, to the label of the one part it runs
+static PART: OnceLock<String> = OnceLock::new();
+
+// the first days return their answer as it is, the ones made from the template return a
+// Result that fails until they're solved
+trait Answer {
+    fn into_answer(self) -> Result<String, String>;
+}
+
+macro_rules! plain_answer {
+    ($($number:ty),*) => {$(
+        impl Answer for $number {
+            fn into_answer(self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        }
+    )*};
+}
+
+plain_answer!(u32, usize);
+
+impl<T: Display> Answer for Result<T, ParseError> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|found| found.to_string())
+            .map_err(|err| err.to_string())
+    }
+}
+
+fn run_part<T: Answer + 'static>(
+    label: &str,
+    process: fn(&str) -> T,
+    input: &str,
+    failures: &mut Vec<String>,
+) -> u128 {
+    let process = |input: &str| process(input).into_answer();
+    if let Some(part) = PART.get() {
+        if part == label {
+            runner::serve(process, input);
+        }
+        return 0;
+    }
+    let timeout = TIMEOUT.get().copied().unwrap_or(DEFAULT_TIMEOUT);
+    let Run {
+        outcome, duration, ..
+    } = runner::isolated(label, timeout);
+    // "Day 05 Part1" is kept as "day-05 part1"
+    let key = label.to_lowercase().replacen(' ', "-", 1);
+    let failure = match outcome {
+        Outcome::Ok(found) => match answer(ANSWERS, &key) {
+            Some(expected) if expected != found => Some(format!(
+                "wrong answer, expected {expected} but found {found}"
+            )),
+            _ => None,
+        },
+        failed => Some(failed.to_string()),
+    };
+    match failure {
+        None => println!("{label}: {duration:?}"),
+        Some(err) => {
+            println!("{label}: {err}");
+            failures.push(format!("{label}: {err}"));
+        }
+    }
+    duration.as_nanos()
+}
+
 fn main() -> Result<(), ()> {
-    use std::time::Instant;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(label) = runner::part_label(&args) {
+        PART.get_or_init(|| label.to_string());
+        return all_days();
+    }
+    match args.as_slice() {
+        [] => {}
+        [flag, value] if flag == "--timeout" => {
+            let timeout = runner::parse_timeout(value).map_err(|err| eprintln!("{err}"))?;
+            TIMEOUT.get_or_init(|| timeout);
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return Err(());
+        }
+    }
+    all_days()
+}
 
+#[tracing::instrument]
+fn all_days() -> Result<(), ()> {
     let mut total_time_in_nanos = 0;
+    let mut failures = Vec::new();
 
     // day 1
     let file = include_str!("../../../day-01/input.txt");
-    let now = Instant::now();
-    let _result = day_01::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 01 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_01::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 01 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    total_time_in_nanos += run_part("Day 01 Part1", day_01::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 01 Part2", day_01::part2::process, file, &mut failures);
     println!();
 
     // day 2
     let file = include_str!("../../../day-02/input.txt");
-    let now = Instant::now();
-    let _result = day_02::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 02 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_02::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 02 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    total_time_in_nanos += run_part("Day 02 Part1", day_02::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 02 Part2", day_02::part2::process, file, &mut failures);
     println!();
 
     // day 3
     let file = include_str!("../../../day-03/input.txt");
-    let now = Instant::now();
-    let _result = day_03::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 03 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_03::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 03 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    total_time_in_nanos += run_part("Day 03 Part1", day_03::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 03 Part2", day_03::part2::process, file, &mut failures);
     println!();
 
     // day 4
     let file = include_str!("../../../day-04/input.txt");
-    let now = Instant::now();
-    let _result = day_04::part1::process(file);
-    let duration = now.elapsed();
-    println!("Day 04 Part1: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
-
-    let now = Instant::now();
-    let _result = day_04::part2::process(file);
-    let duration = now.elapsed();
-    println!("Day 04 Part2: {duration:?}");
-    total_time_in_nanos += duration.as_nanos();
+    total_time_in_nanos += run_part("Day 04 Part1", day_04::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day 04 Part2", day_04::part2::process, file, &mut failures);
     println!();
 
     //// day 5
@@ -309,5 +372,12 @@ fn main() -> Result<(), ()> {
     let total_duration = Duration::from_nanos(total_time_in_nanos as u64);
 
     println!("Total time: {total_duration:?}");
+
+    if !failures.is_empty() {
+        println!();
+        println!("{} part(s) failed:", failures.len());
+        failures.iter().for_each(|failure| println!("{failure}"));
+        return Err(());
+    }
     Ok(())
 }
@@ -1,39 +1,12 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    _input
-        .lines()
-        .map(|line| {
-            let values: Vec<usize> = line
-                .split_whitespace()
-                .filter_map(|x| x.parse::<usize>().ok())
-                .collect();
-
-            if is_safe(&values) {
-                1
-            } else {
-                0
-            }
-        })
-        .sum()
-}
-
-fn is_safe(list: &[usize]) -> bool {
-    let mut ascending = true;
-    let mut descending = true;
+use crate::report::{parse_report, Rules};
 
-    for i in 0..list.len() - 1 {
-        if list[i] < list[i + 1] {
-            descending = false;
-        } else if list[i] > list[i + 1] {
-            ascending = false;
-        }
-
-        if list[i].abs_diff(list[i + 1]) > 3 || list[i] == list[i + 1] {
-            return false;
-        }
-    }
-
-    return descending ^ ascending;
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> usize {
+    let rules = Rules::default();
+    input
+        .lines()
+        .filter(|line| rules.is_safe(&parse_report(line)))
+        .count()
 }
 
 #[cfg(test)]
@@ -0,0 +1,185 @@
+use std::ops::RangeInclusive;
+
+// a report is safe when its levels all go up, or all go down, by a step in `steps` each time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub steps: RangeInclusive<usize>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules { steps: 1..=3 }
+    }
+}
+
+impl Rules {
+    pub fn new(steps: RangeInclusive<usize>) -> Rules {
+        Rules { steps }
+    }
+
+    // a report with one level, or none, is safe
+    pub fn is_safe(&self, levels: &[usize]) -> bool {
+        self.dampen(levels, 0).is_some()
+    }
+
+    // the fewest levels to take out to make the report safe, as indices in order, or None
+    // if it needs more than `most`. the levels kept are a chain where each follows on from
+    // the one kept before it, so for each level and number taken out so far, it's enough
+    // to know whether a chain can end there, which is linear in the report for a given
+    // `most`
+    pub fn dampen(&self, levels: &[usize], most: usize) -> Option<Vec<usize>> {
+        [true, false]
+            .into_iter()
+            .filter_map(|up| self.dampen_towards(levels, most, up))
+            .min_by_key(|removed| removed.len())
+    }
+
+    fn fits(&self, from: usize, to: usize, up: bool) -> bool {
+        let step = match up {
+            true => to.checked_sub(from),
+            false => from.checked_sub(to),
+        };
+        step.is_some_and(|step| self.steps.contains(&step))
+    }
+
+    fn dampen_towards(&self, levels: &[usize], most: usize, up: bool) -> Option<Vec<usize>> {
+        let width = most + 1;
+        // for a chain ending at level i with `removed` taken out before it, at i * width +
+        // removed, the level kept before i, or None when i is the first kept
+        let mut before: Vec<Option<Option<usize>>> = vec![None; levels.len() * width];
+        let mut best: Option<(usize, usize)> = None;
+        for (i, &level) in levels.iter().enumerate() {
+            for removed in 0..width {
+                if removed == i {
+                    before[i * width + removed] = Some(None);
+                } else if removed < i {
+                    // the level kept before, with `skipped` taken out between them
+                    let previous = (0..=removed)
+                        .filter(|&skipped| skipped < i)
+                        .map(|skipped| (i - 1 - skipped, removed - skipped))
+                        .find(|&(previous, removed)| {
+                            before[previous * width + removed].is_some()
+                                && self.fits(levels[previous], level, up)
+                        })
+                        .map(|(previous, _)| Some(previous));
+                    before[i * width + removed] = previous;
+                }
+                let total = removed + levels.len() - 1 - i;
+                if before[i * width + removed].is_some()
+                    && total <= most
+                    && best.is_none_or(|(_, best)| total < best)
+                {
+                    best = Some((i * width + removed, total));
+                }
+            }
+        }
+
+        let Some((end, _)) = best else {
+            return levels.is_empty().then(Vec::new);
+        };
+        let mut kept = vec![false; levels.len()];
+        let (mut i, mut removed) = (end / width, end % width);
+        loop {
+            kept[i] = true;
+            match before[i * width + removed].expect("A reachable chain") {
+                Some(previous) => {
+                    removed -= i - 1 - previous;
+                    i = previous;
+                }
+                None => break,
+            }
+        }
+        Some((0..levels.len()).filter(|&i| !kept[i]).collect())
+    }
+}
+
+pub fn parse_report(line: &str) -> Vec<usize> {
+    line.split_whitespace()
+        .filter_map(|x| x.parse::<usize>().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::generate::Rng;
+
+    #[test]
+    fn test_dampen() {
+        let rules = Rules::default();
+        let reports = [
+            "7 6 4 2 1",
+            "1 2 7 8 9",
+            "9 7 6 2 1",
+            "1 3 2 4 5",
+            "8 6 4 4 1",
+            "1 3 6 7 9",
+        ];
+        let removed: Vec<Option<Vec<usize>>> = reports
+            .iter()
+            .map(|report| rules.dampen(&parse_report(report), 1))
+            .collect();
+        assert_eq!(Some(vec![]), removed[0]);
+        assert_eq!(None, removed[1]);
+        assert_eq!(None, removed[2]);
+        assert!(matches!(removed[3].as_deref(), Some([1] | [2])));
+        assert!(matches!(removed[4].as_deref(), Some([2] | [3])));
+        assert_eq!(Some(vec![]), removed[5]);
+
+        assert_eq!(Some(vec![2, 3]), rules.dampen(&[1, 2, 9, 9, 3, 4], 2));
+        assert_eq!(None, rules.dampen(&[1, 2, 9, 9, 3, 4], 1));
+        assert!(rules.is_safe(&[5]) && rules.is_safe(&[]));
+        assert!(!Rules::new(2..=2).is_safe(&[1, 2, 3]));
+    }
+
+    // the fewest levels to take out by trying every way of taking out up to `most`
+    fn brute_force(rules: &Rules, levels: &[usize], most: usize) -> Option<usize> {
+        let safe = |kept: &[usize]| {
+            [true, false]
+                .into_iter()
+                .any(|up| kept.windows(2).all(|pair| rules.fits(pair[0], pair[1], up)))
+        };
+        (0..1usize << levels.len())
+            .filter(|mask| mask.count_ones() as usize <= most)
+            .filter(|mask| {
+                let kept: Vec<usize> = (0..levels.len())
+                    .filter(|i| mask & (1 << i) == 0)
+                    .map(|i| levels[i])
+                    .collect();
+                safe(&kept)
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn test_fuzz() {
+        let mut rng = Rng::with_seed(2);
+        for rules in [Rules::default(), Rules::new(0..=2), Rules::new(2..=4)] {
+            for _ in 0..3000 {
+                let mut level = rng.usize(10..20);
+                let levels: Vec<usize> = (0..rng.usize(..9))
+                    .map(|_| {
+                        level = (level + rng.usize(..11)).saturating_sub(5);
+                        level
+                    })
+                    .collect();
+                let most = rng.usize(..4);
+                let removed = rules.dampen(&levels, most);
+                let expected = brute_force(&rules, &levels, most);
+                assert_eq!(
+                    expected,
+                    removed.as_ref().map(Vec::len),
+                    "{levels:?} {most} {rules:?}"
+                );
+                if let Some(removed) = removed {
+                    let kept: Vec<usize> = (0..levels.len())
+                        .filter(|i| !removed.contains(i))
+                        .map(|i| levels[i])
+                        .collect();
+                    assert!(rules.is_safe(&kept), "{levels:?} {removed:?}");
+                }
+            }
+        }
+    }
+}
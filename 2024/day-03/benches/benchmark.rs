@@ -1,4 +1,5 @@
 use day_03::*;
+use regex::Regex;
 
 fn main() {
     // Run registered benchmarks.
@@ -6,15 +7,46 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )));
+fn part1() -> usize {
+    part1::process(divan::black_box(include_str!("../input.txt")))
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )));
+fn part2() -> usize {
+    part2::process(divan::black_box(include_str!("../input.txt")))
+}
+
+#[divan::bench]
+fn part1_regex() -> usize {
+    regex_part1(divan::black_box(include_str!("../input.txt")))
+}
+
+#[divan::bench]
+fn part2_regex() -> usize {
+    regex_part2(divan::black_box(include_str!("../input.txt")))
+}
+
+// how the parts were solved before the tokenizer
+fn regex_part1(input: &str) -> usize {
+    let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
+    re.captures_iter(input)
+        .map(|mat| mat[1].parse::<usize>().unwrap() * mat[2].parse::<usize>().unwrap())
+        .sum()
+}
+
+fn regex_part2(input: &str) -> usize {
+    let re = Regex::new(r"(mul\((\d+),(\d+)\)|do\(\)|don't\(\))").unwrap();
+    let mut enabled = true;
+    let mut sum = 0;
+    for mat in re.captures_iter(input) {
+        match &mat[0] {
+            "do()" => enabled = true,
+            "don't()" => enabled = false,
+            _ if enabled => {
+                sum += mat[2].parse::<usize>().unwrap() * mat[3].parse::<usize>().unwrap()
+            }
+            _ => {}
+        }
+    }
+    sum
 }
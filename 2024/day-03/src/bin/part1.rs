@@ -1,8 +1,15 @@
 use day_03::part1::process;
+use day_03::tokenizer::tokens;
 
+// --dump prints the instructions found, with their offsets, before the answer
 #[tracing::instrument]
 fn main() -> Result<(), ()> {
     let file = include_str!("../../input.txt");
+    if std::env::args().any(|arg| arg == "--dump") {
+        for token in tokens(file.as_bytes()) {
+            println!("{}", token);
+        }
+    }
     let result = process(file);
     println!("{}", result);
     Ok(())
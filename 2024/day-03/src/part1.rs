@@ -1,18 +1,8 @@
-use regex::Regex;
+use crate::tokenizer::{execute, tokens};
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
-
-    let mut sum = 0;
-
-    for mat in re.captures_iter(_input) {
-        let num1 = &mat[1].parse::<usize>().unwrap();
-        let num2 = &mat[2].parse::<usize>().unwrap();
-        sum += num1 * num2;
-    }
-
-    sum
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> usize {
+    execute(tokens(input.as_bytes()), false)
 }
 
 #[cfg(test)]
@@ -1,27 +1,8 @@
-use regex::Regex;
+use crate::tokenizer::{execute, tokens};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> usize {
-    let re = Regex::new(r"(mul\((\d+),(\d+)\)|do\(\)|don't\(\))").unwrap();
-
-    let mut enabled = true;
-    let mut sum = 0;
-
-    for mat in re.captures_iter(input) {
-        match &mat[0] {
-            "do()" => enabled = true,
-            "don't()" => enabled = false,
-            _ => {
-                if enabled {
-                    let num1 = &mat[2].parse::<usize>().unwrap();
-                    let num2 = &mat[3].parse::<usize>().unwrap();
-                    sum += num1 * num2;
-                }
-            }
-        };
-    }
-
-    sum
+    execute(tokens(input.as_bytes()), true)
 }
 
 #[cfg(test)]
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::io::{self, Read};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(usize, usize),
+    Do,
+    Dont,
+}
+
+// an instruction and the byte offset of its first byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub offset: usize,
+    pub instruction: Instruction,
+}
+
+const MUL: &[u8] = b"mul(";
+// "do()" shares its first two bytes
+const DONT: &[u8] = b"don't(";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    // how many bytes of MUL or DONT have matched
+    Mul(usize),
+    Do(usize),
+    DoOpen,
+    // the operand so far and how many digits it has, after the first operand for Second
+    First(usize, usize),
+    Second(usize, usize, usize),
+}
+
+// picks the instructions out of the corrupted memory a byte at a time, so the input can
+// come in pieces. none of the bytes part way through an instruction can start another, so
+// when one goes wrong only the byte that broke it needs looking at again
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    state: State,
+    start: usize,
+    offset: usize,
+}
+
+impl Tokenizer {
+    pub fn new() -> Tokenizer {
+        Tokenizer {
+            state: State::Start,
+            start: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn step(&mut self, byte: u8) -> Option<Token> {
+        // most of the memory is junk between instructions
+        if self.state == State::Start && byte != b'm' && byte != b'd' {
+            self.offset += 1;
+            return None;
+        }
+        let (state, instruction) = match self.advance(byte) {
+            Some(next) => next,
+            None => {
+                self.state = State::Start;
+                self.advance(byte).expect("Start takes any byte")
+            }
+        };
+        if self.state == State::Start {
+            self.start = self.offset;
+        }
+        self.state = state;
+        self.offset += 1;
+        instruction.map(|instruction| Token {
+            offset: self.start,
+            instruction,
+        })
+    }
+
+    pub fn feed(&mut self, bytes: &[u8], mut emit: impl FnMut(Token)) {
+        for &byte in bytes {
+            if let Some(token) = self.step(byte) {
+                emit(token);
+            }
+        }
+    }
+
+    // the state after the byte and anything it finishes, or None when it doesn't fit
+    fn advance(&self, byte: u8) -> Option<(State, Option<Instruction>)> {
+        let digit = byte.is_ascii_digit().then(|| (byte - b'0') as usize);
+        let next = match (self.state, byte) {
+            (State::Start, b'm') => State::Mul(1),
+            (State::Start, b'd') => State::Do(1),
+            (State::Start, _) => State::Start,
+            (State::Mul(matched), _) if matched < MUL.len() && byte == MUL[matched] => {
+                State::Mul(matched + 1)
+            }
+            (State::Mul(matched), _) if matched == MUL.len() => State::First(digit?, 1),
+            (State::Do(2), b'(') => State::DoOpen,
+            (State::DoOpen, b')') => return Some((State::Start, Some(Instruction::Do))),
+            (State::Do(matched), _) if matched < DONT.len() && byte == DONT[matched] => {
+                State::Do(matched + 1)
+            }
+            (State::Do(matched), b')') if matched == DONT.len() => {
+                return Some((State::Start, Some(Instruction::Dont)))
+            }
+            (State::First(value, _), b',') => State::Second(value, 0, 0),
+            (State::First(value, digits), _) if digits < 3 => {
+                State::First(value * 10 + digit?, digits + 1)
+            }
+            (State::Second(first, value, digits), b')') if digits > 0 => {
+                return Some((State::Start, Some(Instruction::Mul(first, value))))
+            }
+            (State::Second(first, value, digits), _) if digits < 3 => {
+                State::Second(first, value * 10 + digit?, digits + 1)
+            }
+            _ => return None,
+        };
+        Some((next, None))
+    }
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Tokenizer::new()
+    }
+}
+
+pub fn tokens(input: &[u8]) -> impl Iterator<Item = Token> + '_ {
+    let mut tokenizer = Tokenizer::new();
+    input.iter().filter_map(move |&byte| tokenizer.step(byte))
+}
+
+pub fn read_tokens(mut reader: impl Read, mut emit: impl FnMut(Token)) -> io::Result<()> {
+    let mut tokenizer = Tokenizer::new();
+    let mut buffer = vec![0; 1 << 16];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => tokenizer.feed(&buffer[..read], &mut emit),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+// the sum of the products, skipping the ones after a don't() until the next do() when
+// `conditionals` is set
+pub fn execute(tokens: impl IntoIterator<Item = Token>, conditionals: bool) -> usize {
+    let mut enabled = true;
+    let mut sum = 0;
+    for token in tokens {
+        match token.instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = !conditionals,
+            Instruction::Mul(a, b) if enabled => sum += a * b,
+            Instruction::Mul(..) => {}
+        }
+    }
+    sum
+}
+
+// as it's written in the memory
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Mul(a, b) => write!(f, "mul({a},{b})"),
+            Instruction::Do => write!(f, "do()"),
+            Instruction::Dont => write!(f, "don't()"),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.offset, self.instruction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instructions(input: &str) -> Vec<Instruction> {
+        tokens(input.as_bytes())
+            .map(|token| token.instruction)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let shown: Vec<String> = tokens(input.as_bytes()).map(|t| t.to_string()).collect();
+        assert_eq!(
+            vec![
+                "1: mul(2,4)",
+                "20: don't()",
+                "28: mul(5,5)",
+                "48: mul(11,8)",
+                "59: do()",
+                "64: mul(8,5)"
+            ],
+            shown
+        );
+        assert_eq!(48, execute(tokens(input.as_bytes()), true));
+        assert_eq!(161, execute(tokens(input.as_bytes()), false));
+    }
+
+    #[test]
+    fn test_operands() {
+        assert_eq!(
+            vec![Instruction::Mul(123, 456)],
+            instructions("mul(1234,5)mul(1,2345)mul(123,456)")
+        );
+        assert!(instructions("mul(,5)mul(5,)mul( 1,2)mul(-1,2)").is_empty());
+    }
+
+    #[test]
+    fn test_restarts() {
+        // the byte that breaks an instruction can start the next one
+        assert_eq!(
+            vec![Instruction::Mul(1, 2), Instruction::Do, Instruction::Dont],
+            instructions("mmul(1,2)do(do()dodon'tdon't()")
+        );
+        assert_eq!(vec![Instruction::Mul(3, 4)], instructions("mul(1mul(3,4)"));
+        let offsets: Vec<usize> = tokens(b"mumul(3,4)").map(|t| t.offset).collect();
+        assert_eq!(vec![2], offsets);
+    }
+
+    #[test]
+    fn test_read_tokens() {
+        // instructions split across reads still come out, with offsets into the whole input
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+                let read = self.0.len().min(buffer.len()).min(2);
+                buffer[..read].copy_from_slice(&self.0[..read]);
+                self.0 = &self.0[read..];
+                Ok(read)
+            }
+        }
+        let input = b"xmul(2,4)&don't()mul(11,8)do()mul(8,5)";
+        let mut read = Vec::new();
+        read_tokens(Trickle(input), |token| read.push(token)).unwrap();
+        assert_eq!(tokens(input).collect::<Vec<_>>(), read);
+        assert_eq!(48, execute(read, true));
+    }
+}
@@ -1,39 +1,9 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> usize {
-    let grid: Vec<&[u8]> = _input.lines().map(|x| x.as_bytes()).collect();
+use crate::search::{Grid, Stencil};
 
-    let mut count = 0;
-
-    for i in 0..grid.len() {
-        for j in 0..grid[0].len() {
-            if check_for_x_mas(&grid, i, j) {
-                count += 1;
-            }
-        }
-    }
-
-    count
-}
-
-fn check_for_x_mas(grid: &Vec<&[u8]>, i: usize, j: usize) -> bool {
-    if i == 0 || j == 0 || i == grid.len() - 1 || j == grid[0].len() - 1 || grid[i][j] != b'A' {
-        return false;
-    }
-
-    let corners = (
-        grid[i - 1][j - 1],
-        grid[i - 1][j + 1],
-        grid[i + 1][j + 1],
-        grid[i + 1][j - 1],
-    );
-
-    match corners {
-        (b'M', b'M', b'S', b'S') => true,
-        (b'S', b'M', b'M', b'S') => true,
-        (b'S', b'S', b'M', b'M') => true,
-        (b'M', b'S', b'S', b'M') => true,
-        (_, _, _, _) => false,
-    }
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> usize {
+    let x_mas = Stencil::parse("M.S\n.A.\nM.S");
+    Grid::parse(input).find_stencil(&x_mas, true).len()
 }
 
 #[cfg(test)]
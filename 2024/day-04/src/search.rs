@@ -0,0 +1,373 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    // (rows, cols) moved by one step
+    pub fn step(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+// where one of the words was found, from its first letter on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    // which of the words searched for
+    pub word: usize,
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+    pub len: usize,
+}
+
+impl WordMatch {
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (row_step, col_step) = self.direction.step();
+        let (row, col) = (self.row as isize, self.col as isize);
+        (0..self.len as isize)
+            .map(move |i| ((row + i * row_step) as usize, (col + i * col_step) as usize))
+    }
+}
+
+// a block of letters to look for, where '.' matches anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil {
+    pub height: usize,
+    pub width: usize,
+    // (row, col, letter) of the cells that aren't wildcards, in order
+    cells: Vec<(usize, usize, u8)>,
+}
+
+// the stencil mirrored left to right first if `reflected`, then turned clockwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub quarter_turns: usize,
+    pub reflected: bool,
+}
+
+impl Stencil {
+    pub fn parse(pattern: &str) -> Stencil {
+        let rows: Vec<&[u8]> = pattern.lines().map(str::as_bytes).collect();
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.iter()
+                    .enumerate()
+                    .filter(|(_, &letter)| letter != b'.')
+                    .map(move |(col, &letter)| (row, col, letter))
+            })
+            .collect();
+        Stencil {
+            height: rows.len(),
+            width: rows.iter().map(|row| row.len()).max().unwrap_or(0),
+            cells,
+        }
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Stencil {
+        let mut stencil = self.clone();
+        if orientation.reflected {
+            let width = stencil.width;
+            stencil = stencil.moved(|(row, col)| (row, width - 1 - col), stencil.height, width);
+        }
+        for _ in 0..orientation.quarter_turns % 4 {
+            let height = stencil.height;
+            stencil = stencil.moved(|(row, col)| (col, height - 1 - row), stencil.width, height);
+        }
+        stencil
+    }
+
+    fn moved(
+        &self,
+        to: impl Fn((usize, usize)) -> (usize, usize),
+        height: usize,
+        width: usize,
+    ) -> Stencil {
+        let mut cells: Vec<(usize, usize, u8)> = self
+            .cells
+            .iter()
+            .map(|&(row, col, letter)| {
+                let (row, col) = to((row, col));
+                (row, col, letter)
+            })
+            .collect();
+        cells.sort_unstable();
+        Stencil {
+            height,
+            width,
+            cells,
+        }
+    }
+
+    // the different ways round the stencil can go, turned and, if `reflections`, mirrored.
+    // a symmetric stencil comes out fewer times, so a match isn't counted again turned round
+    pub fn orientations(&self, reflections: bool) -> Vec<(Orientation, Stencil)> {
+        let mut orientations: Vec<(Orientation, Stencil)> = Vec::new();
+        for reflected in [false, reflections] {
+            for quarter_turns in 0..4 {
+                let orientation = Orientation {
+                    quarter_turns,
+                    reflected,
+                };
+                let stencil = self.oriented(orientation);
+                if orientations.iter().all(|(_, seen)| *seen != stencil) {
+                    orientations.push((orientation, stencil));
+                }
+            }
+        }
+        orientations
+    }
+
+    // the grid cells the letters cover with the top left corner at (row, col)
+    pub fn cells_at(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells.iter().map(move |&(r, c, _)| (row + r, col + c))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StencilMatch {
+    // the top left corner of the oriented stencil
+    pub row: usize,
+    pub col: usize,
+    pub orientation: Orientation,
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid<'a> {
+    rows: Vec<&'a [u8]>,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl<'a> Grid<'a> {
+    pub fn parse(input: &'a str) -> Grid<'a> {
+        let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        Grid {
+            height: rows.len(),
+            width: rows.iter().map(|row| row.len()).max().unwrap_or(0),
+            rows,
+        }
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<u8> {
+        let row = self.rows.get(usize::try_from(row).ok()?)?;
+        row.get(usize::try_from(col).ok()?).copied()
+    }
+
+    // every place each word reads in any of the 8 directions. a one-letter word reads the
+    // same every way, so it's found once per cell, going north
+    pub fn find_words(&self, words: &[&str]) -> Vec<WordMatch> {
+        let mut matches = Vec::new();
+        for (row, line) in self.rows.iter().enumerate() {
+            for (col, &letter) in line.iter().enumerate() {
+                for (word, text) in words.iter().enumerate() {
+                    let text = text.as_bytes();
+                    if text.first() != Some(&letter) {
+                        continue;
+                    }
+                    let directions = match text.len() {
+                        1 => &Direction::ALL[..1],
+                        _ => &Direction::ALL[..],
+                    };
+                    for &direction in directions {
+                        let (row_step, col_step) = direction.step();
+                        let reads = text.iter().enumerate().skip(1).all(|(i, &expected)| {
+                            let i = i as isize;
+                            self.get(row as isize + i * row_step, col as isize + i * col_step)
+                                == Some(expected)
+                        });
+                        if reads {
+                            matches.push(WordMatch {
+                                word,
+                                row,
+                                col,
+                                direction,
+                                len: text.len(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    // every place the stencil fits, in each of its distinct orientations
+    pub fn find_stencil(&self, stencil: &Stencil, reflections: bool) -> Vec<StencilMatch> {
+        let mut matches = Vec::new();
+        for (orientation, stencil) in stencil.orientations(reflections) {
+            for row in 0..(self.height + 1).saturating_sub(stencil.height) {
+                for col in 0..(self.width + 1).saturating_sub(stencil.width) {
+                    let fits = stencil
+                        .cells
+                        .iter()
+                        .all(|&(r, c, letter)| self.rows[row + r].get(col + c) == Some(&letter));
+                    if fits {
+                        matches.push(StencilMatch {
+                            row,
+                            col,
+                            orientation,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    // the grid with every cell not in `cells` blanked to '.', as the puzzle shows matches
+    pub fn render(&self, cells: impl IntoIterator<Item = (usize, usize)>) -> String {
+        let mut shown: Vec<Vec<u8>> = self.rows.iter().map(|row| vec![b'.'; row.len()]).collect();
+        for (row, col) in cells {
+            shown[row][col] = self.rows[row][col];
+        }
+        shown
+            .into_iter()
+            .map(|row| String::from_utf8_lossy(&row).into_owned())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_words() {
+        let grid = Grid::parse(EXAMPLE);
+        let matches = grid.find_words(&["XMAS"]);
+        assert_eq!(18, matches.len());
+        assert!(matches.contains(&WordMatch {
+            word: 0,
+            row: 0,
+            col: 5,
+            direction: Direction::East,
+            len: 4,
+        }));
+        let rendered = grid.render(matches.iter().flat_map(WordMatch::cells));
+        assert_eq!(
+            "....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX",
+            rendered
+        );
+
+        let matches = grid.find_words(&["XMAS", "SAMX"]);
+        assert_eq!(18, matches.iter().filter(|m| m.word == 1).count());
+        // a word that reads the same backwards is found both ways
+        let directions: Vec<Direction> = Grid::parse("ABA")
+            .find_words(&["ABA"])
+            .iter()
+            .map(|m| m.direction)
+            .collect();
+        assert_eq!(vec![Direction::East, Direction::West], directions);
+        // and a one-letter word only once
+        let matches = Grid::parse("AB\nBA").find_words(&["A"]);
+        let cells: Vec<(usize, usize)> = matches.iter().map(|m| (m.row, m.col)).collect();
+        assert_eq!(vec![(0, 0), (1, 1)], cells);
+    }
+
+    #[test]
+    fn test_stencil() {
+        let grid = Grid::parse(EXAMPLE);
+        let x_mas = Stencil::parse("M.S\n.A.\nM.S");
+        assert_eq!(4, x_mas.orientations(true).len());
+        let matches = grid.find_stencil(&x_mas, true);
+        assert_eq!(9, matches.len());
+        let rendered = grid.render(matches.iter().flat_map(|m| {
+            x_mas
+                .oriented(m.orientation)
+                .cells_at(m.row, m.col)
+                .collect::<Vec<_>>()
+        }));
+        assert_eq!(
+            ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+..........",
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        // an L has all 8, and is only found mirrored when that's asked for
+        let l = Stencil::parse("A.\nB.\nCD");
+        assert_eq!(8, l.orientations(true).len());
+        assert_eq!(4, l.orientations(false).len());
+        let turned = l.oriented(Orientation {
+            quarter_turns: 1,
+            reflected: false,
+        });
+        assert_eq!((2, 3), (turned.height, turned.width));
+        let grid = Grid::parse("CBA\nD..");
+        assert_eq!(1, grid.find_stencil(&l, false).len());
+        let grid = Grid::parse("ABC\n..D");
+        assert!(grid.find_stencil(&l, false).is_empty());
+        assert_eq!(
+            vec![StencilMatch {
+                row: 0,
+                col: 0,
+                orientation: Orientation {
+                    quarter_turns: 3,
+                    reflected: true,
+                },
+            }],
+            grid.find_stencil(&l, true)
+        );
+    }
+}
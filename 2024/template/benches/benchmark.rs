@@ -1,3 +1,4 @@
+use aoc_common::ParseError;
 use {{crate_name}}::*;
 
 fn main() {
@@ -6,15 +7,11 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )));
+fn part1() -> Result<String, ParseError> {
+    part1::process(divan::black_box(include_str!("../input.txt")))
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )));
+fn part2() -> Result<String, ParseError> {
+    part2::process(divan::black_box(include_str!("../input.txt")))
 }
@@ -0,0 +1,5 @@
+    // day {{number}}
+    let file = include_str!("../../../day-{{day}}/input.txt");
+    total_time_in_nanos += run_part("Day {{day}} Part1", day_{{day}}::part1::process, file, &mut failures);
+    total_time_in_nanos += run_part("Day {{day}} Part2", day_{{day}}::part2::process, file, &mut failures);
+    println!();
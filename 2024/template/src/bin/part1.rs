@@ -1,9 +1,10 @@
+use aoc_common::ParseError;
 use {{crate_name}}::part1::process;
 
 #[tracing::instrument]
-fn main() -> Result<(), ()> {
+fn main() -> Result<(), ParseError> {
     let file = include_str!("../../input.txt");
-    let result = process(file);
+    let result = process(file)?;
     println!("{}", result);
     Ok(())
 }
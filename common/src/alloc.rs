@@ -0,0 +1,169 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// the system allocator with running totals. it only counts once a binary installs it:
+//
+//     #[global_allocator]
+//     static ALLOC: CountingAllocator = CountingAllocator;
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    // growing counts as an allocation of the extra bytes, the same as divan's grow
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            match new_size.checked_sub(layout.size()) {
+                Some(extra) => grew(extra),
+                None => {
+                    LIVE.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+                }
+            }
+        }
+        new_ptr
+    }
+}
+
+fn grew(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(bytes, Ordering::Relaxed);
+    let live = LIVE.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    // the most that was live at once, over what was live before
+    pub peak: u64,
+}
+
+// runs `f` and counts what it allocates. all zeroes unless the allocator is installed
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as u64,
+        bytes: (BYTES.load(Ordering::Relaxed) - bytes) as u64,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live) as u64,
+    };
+    (result, stats)
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+// decimal units to four significant figures, the way divan shows sizes
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1000.0 {
+            break;
+        }
+        value /= 1000.0;
+        unit = next;
+    }
+    let decimals = match value {
+        value if value >= 100.0 => 1,
+        value if value >= 10.0 => 2,
+        _ => 3,
+    };
+    let value = format!("{:.*}", decimals, value);
+    format!(
+        "{} {}",
+        value.trim_end_matches('0').trim_end_matches('.'),
+        unit
+    )
+}
+
+pub fn parse_bytes(text: &str) -> Option<u64> {
+    let (value, unit) = text.split_once(' ')?;
+    let scale = match unit {
+        "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some((value.parse::<f64>().ok()? * scale).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        // other tests allocate on their own threads meanwhile, so these are lower bounds
+        let (sum, stats) = measure(|| {
+            let mut values: Vec<u64> = (0..1000).collect();
+            values.extend(0..1000);
+            values.iter().sum::<u64>()
+        });
+        assert_eq!(999_000, sum);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 16_000);
+        assert!(stats.peak >= 16_000);
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!("96 B", format_bytes(96));
+        assert_eq!("18.01 KB", format_bytes(18_010));
+        assert_eq!("6.2 KB", format_bytes(6_200));
+        assert_eq!("1.5 MB", format_bytes(1_500_000));
+        assert_eq!(Some(18_010), parse_bytes("18.01 KB"));
+        assert_eq!(Some(96), parse_bytes("96 B"));
+        assert_eq!(None, parse_bytes("96 bytes"));
+    }
+}
@@ -0,0 +1,56 @@
+// answers are kept one per line as `<key>: <answer>`, with the key `part1` in a day's
+// examples/expected and `day-05 part1` in a year's answers.txt. a key with nothing
+// after the colon is an empty slot, waiting for the puzzle to give its answer
+pub fn answer<'a>(answers: &'a str, key: &str) -> Option<&'a str> {
+    answers.lines().find_map(|line| {
+        let (found, answer) = line.split_once(':')?;
+        let answer = answer.trim();
+        (found.trim() == key && !answer.is_empty()).then_some(answer)
+    })
+}
+
+// the keys that have a line, filled in or not
+pub fn keys(answers: &str) -> impl Iterator<Item = &str> {
+    answers
+        .lines()
+        .filter_map(|line| Some(line.split_once(':')?.0.trim()))
+}
+
+// adds an empty slot for each key that doesn't have a line yet
+pub fn add_slots(answers: &str, new_keys: &[&str]) -> String {
+    let mut output = answers.to_string();
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+    for key in new_keys {
+        if !keys(answers).any(|found| found == *key) {
+            output.push_str(&format!("{key}:\n"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        let answers = "day-01 part1: 142\nday-01 part2:\nday-02 part1 : abc \n";
+        assert_eq!(Some("142"), answer(answers, "day-01 part1"));
+        assert_eq!(None, answer(answers, "day-01 part2"));
+        assert_eq!(Some("abc"), answer(answers, "day-02 part1"));
+        assert_eq!(None, answer(answers, "day-02 part2"));
+        assert_eq!(Some("281"), answer("part1:\npart2: 281", "part2"));
+    }
+
+    #[test]
+    fn test_add_slots() {
+        let answers = "day-01 part1: 142";
+        assert_eq!(
+            "day-01 part1: 142\nday-01 part2:\n",
+            add_slots(answers, &["day-01 part1", "day-01 part2"])
+        );
+        assert_eq!("part1:\npart2:\n", add_slots("", &["part1", "part2"]));
+    }
+}
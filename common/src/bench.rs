@@ -0,0 +1,654 @@
+use std::fmt::Write;
+
+use crate::alloc::{format_bytes, parse_bytes, AllocStats};
+use crate::{parse, ParseError};
+
+const UNITS: [(&str, f64); 4] = [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)];
+
+// one divan result. times are in nanoseconds, and the benchmark name is split
+// into the part it solves and the variant, so `part2_hash` is part2 / hash.
+// allocations are only there for runs with the count-alloc feature
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub commit: String,
+    pub day: String,
+    pub part: String,
+    pub variant: String,
+    pub fastest: f64,
+    pub slowest: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub samples: u64,
+    pub iters: u64,
+    pub alloc: Option<AllocStats>,
+}
+
+impl Record {
+    pub fn name(&self) -> String {
+        match self.variant.as_str() {
+            "" => self.part.clone(),
+            variant => format!("{}_{}", self.part, variant),
+        }
+    }
+
+    // json has no way to write NaN or infinity, so a record with one is refused rather
+    // than leaving a line in the history that can't be read back
+    pub fn to_json(&self) -> Result<String, String> {
+        let times = [self.fastest, self.slowest, self.median, self.mean];
+        if times.iter().any(|time| !time.is_finite()) {
+            return Err(format!(
+                "{} {} has a time that isn't a finite number",
+                self.day,
+                self.name()
+            ));
+        }
+        let alloc = self.alloc.map_or_else(String::new, |alloc| {
+            format!(
+                ",\"allocations\":{},\"alloc_bytes\":{},\"peak_bytes\":{}",
+                alloc.allocations, alloc.bytes, alloc.peak
+            )
+        });
+        Ok(format!(
+            "{{\"commit\":{},\"day\":{},\"part\":{},\"variant\":{},\"fastest_ns\":{},\"slowest_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"samples\":{},\"iters\":{}{}}}",
+            quote(&self.commit),
+            quote(&self.day),
+            quote(&self.part),
+            quote(&self.variant),
+            self.fastest,
+            self.slowest,
+            self.median,
+            self.mean,
+            self.samples,
+            self.iters,
+            alloc
+        ))
+    }
+
+    pub fn from_json(line: &str) -> Result<Record, ParseError> {
+        let fields = json_object(line)?;
+        let get = |key: &str| {
+            fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| ParseError::new(line, format!("there's no \"{}\" field", key)))
+        };
+        let text = |key: &str| get(key).and_then(unquote);
+        let alloc = match get("allocations") {
+            Ok(allocations) => Some(AllocStats {
+                allocations: parse::number(allocations)?,
+                bytes: parse::number(get("alloc_bytes")?)?,
+                peak: parse::number(get("peak_bytes")?)?,
+            }),
+            Err(_) => None,
+        };
+        Ok(Record {
+            commit: text("commit")?,
+            day: text("day")?,
+            part: text("part")?,
+            variant: text("variant")?,
+            fastest: parse::number(get("fastest_ns")?)?,
+            slowest: parse::number(get("slowest_ns")?)?,
+            median: parse::number(get("median_ns")?)?,
+            mean: parse::number(get("mean_ns")?)?,
+            samples: parse::number(get("samples")?)?,
+            iters: parse::number(get("iters")?)?,
+            alloc,
+        })
+    }
+}
+
+// the rows of divan's text tables, e.g. the output of `cargo bench -q`.
+// groups are named after the bench target, `day_01_bench` for day-01
+pub fn parse_divan(output: &str, commit: &str) -> Vec<Record> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut day = None;
+    let mut current = None;
+    let (mut section, mut section_row) = ("", 0);
+    for line in output.lines() {
+        if let Some(group) = line
+            .split_whitespace()
+            .next()
+            .and_then(|word| word.strip_suffix("_bench"))
+        {
+            day = Some(group.replace('_', "-"));
+            continue;
+        }
+        // nested rows, for benchmarks that take arguments, are left out
+        if let Some(row) = line
+            .strip_prefix("├─ ")
+            .or_else(|| line.strip_prefix("╰─ "))
+        {
+            let record = day.as_deref().and_then(|day| parse_row(day, row, commit));
+            current = record.is_some().then_some(records.len());
+            records.extend(record);
+            continue;
+        }
+
+        // the allocation profiler adds rows under a benchmark: a heading like
+        // `alloc:`, then the count, then the bytes. only the median is kept
+        let cells: Vec<&str> = line
+            .strip_prefix('│')
+            .unwrap_or(line)
+            .split('│')
+            .map(str::trim)
+            .collect();
+        let (Some(current), [first, _, median, ..]) = (current, &cells[..]) else {
+            continue;
+        };
+        if first.ends_with(':') {
+            (section, section_row) = (*first, 0);
+            continue;
+        }
+        section_row += 1;
+        let alloc = records[current]
+            .alloc
+            .get_or_insert_with(AllocStats::default);
+        match (section, section_row) {
+            ("alloc:" | "grow:", 1) => {
+                alloc.allocations += median
+                    .parse::<f64>()
+                    .map_or(0, |count| count.round() as u64)
+            }
+            ("alloc:" | "grow:", 2) => alloc.bytes += parse_bytes(median).unwrap_or(0),
+            ("max alloc:", 2) => alloc.peak = parse_bytes(median).unwrap_or(0),
+            _ => {}
+        }
+    }
+    records
+}
+
+fn parse_row(day: &str, row: &str, commit: &str) -> Option<Record> {
+    let (name, values) = row.split_once(' ')?;
+    let columns: Vec<&str> = values.split('│').map(str::trim).collect();
+    let [fastest, slowest, median, mean, samples, iters] = columns[..] else {
+        return None;
+    };
+    let (part, variant) = split_name(name);
+    Some(Record {
+        commit: commit.to_string(),
+        day: day.to_string(),
+        part,
+        variant,
+        fastest: duration(fastest)?,
+        slowest: duration(slowest)?,
+        median: duration(median)?,
+        mean: duration(mean)?,
+        samples: samples.parse().ok()?,
+        iters: iters.parse().ok()?,
+        alloc: None,
+    })
+}
+
+pub fn parse_history(input: &str) -> Result<Vec<Record>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Record::from_json(line).map_err(|err| err.locate(input)))
+        .collect()
+}
+
+// the latest run of each benchmark at a commit, sorted the way divan lists them
+pub fn at_commit<'a>(history: &'a [Record], commit: &str) -> Vec<&'a Record> {
+    let mut records: Vec<&Record> = Vec::new();
+    for record in history.iter().filter(|record| record.commit == commit) {
+        match records
+            .iter_mut()
+            .find(|other| other.day == record.day && other.name() == record.name())
+        {
+            Some(other) => *other = record,
+            None => records.push(record),
+        }
+    }
+    records.sort_by(|a, b| (&a.day, a.name()).cmp(&(&b.day, b.name())));
+    records
+}
+
+// the commits in the history, in the order they were first recorded
+pub fn commits(history: &[Record]) -> Vec<&str> {
+    let mut commits: Vec<&str> = Vec::new();
+    for record in history {
+        if !commits.contains(&record.commit.as_str()) {
+            commits.push(&record.commit);
+        }
+    }
+    commits
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub day: String,
+    pub name: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl Delta {
+    // change in the median, as a percentage of the old one
+    pub fn change(&self) -> Option<f64> {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => Some((after - before) / before * 100.0),
+            _ => None,
+        }
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+pub fn compare(history: &[Record], base: &str, head: &str) -> Vec<Delta> {
+    let (base, head) = (at_commit(history, base), at_commit(history, head));
+    let mut deltas: Vec<Delta> = base
+        .iter()
+        .map(|record| Delta {
+            day: record.day.clone(),
+            name: record.name(),
+            before: Some(record.median),
+            after: None,
+        })
+        .collect();
+    for record in head {
+        let name = record.name();
+        match deltas
+            .iter_mut()
+            .find(|delta| delta.day == record.day && delta.name == name)
+        {
+            Some(delta) => delta.after = Some(record.median),
+            None => deltas.push(Delta {
+                day: record.day.clone(),
+                name,
+                before: None,
+                after: Some(record.median),
+            }),
+        }
+    }
+    deltas.sort_by(|a, b| (&a.day, &a.name).cmp(&(&b.day, &b.name)));
+    deltas
+}
+
+pub fn report(deltas: &[Delta], threshold: f64) -> String {
+    let width = deltas
+        .iter()
+        .map(|delta| delta.day.len() + delta.name.len() + 1)
+        .max()
+        .unwrap_or(0);
+    let time = |time: Option<f64>| time.map_or_else(|| String::from("-"), format_duration);
+    let mut report = String::new();
+    for delta in deltas {
+        let name = format!("{} {}", delta.day, delta.name);
+        let change = match (delta.before, delta.change()) {
+            (None, _) => String::from("new"),
+            (Some(_), None) => String::from("gone"),
+            (_, Some(change)) => format!("{:+.1}%", change),
+        };
+        let flag = if delta.is_regression(threshold) {
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            report,
+            "{:width$}  {:>10} -> {:>10}  {:>7}{}",
+            name,
+            time(delta.before),
+            time(delta.after),
+            change,
+            flag
+        );
+    }
+    report
+}
+
+// divan's own table layout, so benchmarks.txt reads the same as before
+pub fn divan_table(records: &[&Record]) -> String {
+    let mut table = String::new();
+    let mut days: Vec<&str> = records.iter().map(|record| record.day.as_str()).collect();
+    days.dedup();
+    for day in days {
+        let rows: Vec<&&Record> = records.iter().filter(|record| record.day == day).collect();
+        let group = format!("{}_bench", day.replace('-', "_"));
+        let width = rows
+            .iter()
+            .map(|record| record.name().chars().count() + 3)
+            .chain([group.len()])
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let _ = writeln!(
+            table,
+            "{:width$}{:14}│ {:14}│ {:14}│ {:14}│ {:8}│ iters",
+            group, "fastest", "slowest", "median", "mean", "samples"
+        );
+        for (i, record) in rows.iter().enumerate() {
+            let branch = if i + 1 == rows.len() {
+                "╰─"
+            } else {
+                "├─"
+            };
+            let _ = writeln!(
+                table,
+                "{:width$}{:14}│ {:14}│ {:14}│ {:14}│ {:<8}│ {}",
+                format!("{} {}", branch, record.name()),
+                format_duration(record.fastest),
+                format_duration(record.slowest),
+                format_duration(record.median),
+                format_duration(record.mean),
+                record.samples,
+                record.iters
+            );
+        }
+        table.push('\n');
+    }
+    table
+}
+
+// allocation columns are added when any of the runs counted them
+pub fn markdown_table(records: &[&Record]) -> String {
+    let allocs = records.iter().any(|record| record.alloc.is_some());
+    let mut table = String::from("| Day | Part | Variant | Median | Mean | Samples |");
+    let mut rule = String::from("|-----|------|---------|-------:|-----:|--------:|");
+    if allocs {
+        table.push_str(" Allocs | Bytes | Peak |");
+        rule.push_str("-------:|------:|-----:|");
+    }
+    let _ = writeln!(table, "\n{}", rule);
+    for record in records {
+        let _ = write!(
+            table,
+            "| {} | {} | {} | {} | {} | {} |",
+            record.day,
+            record.part,
+            record.variant,
+            format_duration(record.median),
+            format_duration(record.mean),
+            record.samples
+        );
+        match record.alloc {
+            Some(alloc) if allocs => {
+                let (bytes, peak) = (format_bytes(alloc.bytes), format_bytes(alloc.peak));
+                let _ = write!(table, " {} | {} | {} |", alloc.allocations, bytes, peak);
+            }
+            None if allocs => table.push_str(" - | - | - |"),
+            _ => {}
+        }
+        table.push('\n');
+    }
+    table
+}
+
+// divan shows four significant figures in the largest unit that keeps the number under 1000
+pub fn format_duration(nanos: f64) -> String {
+    let (unit, scale) = UNITS
+        .iter()
+        .rev()
+        .find(|(_, scale)| nanos >= *scale)
+        .unwrap_or(&UNITS[0]);
+    let value = nanos / scale;
+    let decimals = match value {
+        value if value >= 100.0 => 1,
+        value if value >= 10.0 => 2,
+        _ => 3,
+    };
+    let value = format!("{:.*}", decimals, value);
+    let value = value.trim_end_matches('0').trim_end_matches('.');
+    format!("{} {}", value, unit)
+}
+
+fn duration(text: &str) -> Option<f64> {
+    let (value, unit) = text.split_once(' ')?;
+    let (_, scale) = UNITS.iter().find(|(name, _)| *name == unit)?;
+    // to the picosecond, so the history doesn't pick up float noise like 4131999.9999999995
+    let picos = (value.parse::<f64>().ok()? * scale * 1000.0).round();
+    Some(picos / 1000.0)
+}
+
+fn split_name(name: &str) -> (String, String) {
+    let numbered = name
+        .strip_prefix("part")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+    match name.split_once('_') {
+        Some((part, variant)) if numbered => (part.to_string(), variant.to_string()),
+        _ => (name.to_string(), String::new()),
+    }
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            // json strings can't hold control characters as they are
+            c if c < ' ' => write!(quoted, "\\u{:04x}", c as u32).expect("Writing to a string"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn unquote(value: &str) -> Result<String, ParseError> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| ParseError::expected(value, "a string"))?;
+    let mut text = String::new();
+    let mut rest = inner;
+    while let Some(at) = rest.find('\\') {
+        text.push_str(&rest[..at]);
+        let escape = &rest[at..];
+        let (c, len) = match escape.as_bytes().get(1) {
+            Some(b'n') => ('\n', 2),
+            Some(b'r') => ('\r', 2),
+            Some(b't') => ('\t', 2),
+            Some(b'b') => ('\u{8}', 2),
+            Some(b'f') => ('\u{c}', 2),
+            Some(b'u') => {
+                let c = escape
+                    .get(2..6)
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| ParseError::expected(escape, "a \\u escape of 4 hex digits"))?;
+                (c, 6)
+            }
+            Some(&b) if matches!(b, b'"' | b'\\' | b'/') => (b as char, 2),
+            _ => {
+                return Err(ParseError::expected(
+                    escape,
+                    "an escape like \\n or \\u0001",
+                ))
+            }
+        };
+        text.push(c);
+        rest = &escape[len..];
+    }
+    text.push_str(rest);
+    Ok(text)
+}
+
+// the fields of a flat object, which is all the history holds. values are
+// left as they're written, with strings still quoted
+fn json_object(line: &str) -> Result<Vec<(String, &str)>, ParseError> {
+    let body = line
+        .trim()
+        .strip_prefix('{')
+        .and_then(|body| body.strip_suffix('}'))
+        .ok_or_else(|| ParseError::expected(line, "a JSON object"))?;
+
+    let mut fields = Vec::new();
+    let mut rest = body.trim_start();
+    while !rest.is_empty() {
+        let key_end = string_end(rest).ok_or_else(|| ParseError::expected(rest, "a quoted key"))?;
+        let key = unquote(&rest[..key_end])?;
+        let (_, value) = parse::split_once(&rest[key_end..], ":")?;
+        let value = value.trim_start();
+        let value_end = match value.starts_with('"') {
+            true => {
+                string_end(value).ok_or_else(|| ParseError::expected(value, "a closing '\"'"))?
+            }
+            false => value.find(',').unwrap_or(value.len()),
+        };
+        fields.push((key, value[..value_end].trim_end()));
+        rest = value[value_end..].trim_start();
+        rest = match rest.strip_prefix(',') {
+            Some(next) => next.trim_start(),
+            None if rest.is_empty() => rest,
+            None => return Err(ParseError::expected(rest, "',' or '}'")),
+        };
+    }
+    Ok(fields)
+}
+
+// the length of the quoted string at the start of `text`, quotes included
+fn string_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "Timer precision: 20 ns
+day_06_bench    fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1        249.6 ns      │ 53.79 µs      │ 249.6 ns      │ 806.3 ns      │ 100     │ 100
+├─ part1_brute  299.1 ns      │ 330.4 ns      │ 309.6 ns      │ 308.9 ns      │ 100     │ 1600
+╰─ part2_brute  24.7 ms       │ 28.05 ms      │ 25.07 ms      │ 25.14 ms      │ 100     │ 100
+
+day_11_bench          fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ nearest_galaxies   3 ms          │ 3.2 ms        │ 3.1 ms        │ 3.1 ms        │ 100     │ 100
+╰─ part2_parallel     195.2 µs      │ 767.1 µs      │ 271.5 µs      │ 292.8 µs      │ 100     │ 100
+";
+
+    #[test]
+    fn test_parse_divan() {
+        let records = parse_divan(OUTPUT, "abc123");
+        assert_eq!(5, records.len());
+        assert_eq!("day-06", records[1].day);
+        assert_eq!(
+            ("part1", "brute"),
+            (records[1].part.as_str(), records[1].variant.as_str())
+        );
+        assert_eq!(1600, records[1].iters);
+        assert_eq!(25_070_000.0, records[2].median);
+        assert_eq!(
+            ("nearest_galaxies", ""),
+            (records[3].part.as_str(), records[3].variant.as_str())
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut record = parse_divan(OUTPUT, "abc123").remove(0);
+        record.commit = String::from("a \"quoted\" \\ commit");
+        let line = record.to_json().unwrap();
+        assert_eq!(Ok(record.clone()), Record::from_json(&line));
+
+        // control characters are escaped so the line stays valid json
+        record.variant = String::from("tab\there\nnew line \u{1}");
+        let line = record.to_json().unwrap();
+        assert!(line.contains(r#""variant":"tab\there\nnew line \u0001""#));
+        assert_eq!(Ok(record.clone()), Record::from_json(&line));
+        assert!(Record::from_json(&line.replace("\\u0001", "\\u00")).is_err());
+
+        record.median = f64::NAN;
+        assert_eq!(
+            Err(String::from(
+                "day-06 part1_tab\there\nnew line \u{1} has a time that isn't a finite number"
+            )),
+            record.to_json()
+        );
+
+        let error = parse_history("\n{\"commit\":\"abc\"}\n").unwrap_err();
+        assert_eq!(
+            (2, "there's no \"day\" field"),
+            (error.line, error.message.as_str())
+        );
+    }
+
+    #[test]
+    fn test_tables() {
+        let records = parse_divan(OUTPUT, "abc123");
+        let records: Vec<&Record> = records.iter().collect();
+        let table = divan_table(&records);
+        let day_06: String = OUTPUT
+            .lines()
+            .skip(1)
+            .take(5)
+            .map(|line| format!("{}\n", line))
+            .collect();
+        assert!(table.starts_with(&day_06));
+
+        let markdown = markdown_table(&records);
+        assert!(markdown.contains("| day-06 | part2 | brute | 25.07 ms | 25.14 ms | 100 |\n"));
+    }
+
+    #[test]
+    fn test_alloc_rows() {
+        let output = "day_11_bench       fastest       │ slowest       │ median        │ mean          │ samples │ iters
+╰─ part1           59.43 µs      │ 72.15 µs      │ 62.28 µs      │ 63.31 µs      │ 10      │ 10
+                   max alloc:    │               │               │               │         │
+                     6           │ 6             │ 6             │ 6             │         │
+                     18.01 KB    │ 18.01 KB      │ 18.01 KB      │ 18.01 KB      │         │
+                   alloc:        │               │               │               │         │
+                     8           │ 8             │ 8             │ 8             │         │
+                     6.2 KB      │ 6.2 KB        │ 6.2 KB        │ 6.2 KB        │         │
+                   dealloc:      │               │               │               │         │
+                     8           │ 8             │ 8             │ 8             │         │
+                     22.39 KB    │ 22.39 KB      │ 22.39 KB      │ 22.39 KB      │         │
+                   grow:         │               │               │               │         │
+                     25          │ 25            │ 25            │ 25            │         │
+                     16.19 KB    │ 16.19 KB      │ 16.19 KB      │ 16.19 KB      │         │
+";
+        let records = parse_divan(output, "abc123");
+        let expected = AllocStats {
+            allocations: 33,
+            bytes: 22_390,
+            peak: 18_010,
+        };
+        assert_eq!(Some(expected), records[0].alloc);
+        assert_eq!(
+            Ok(records[0].clone()),
+            Record::from_json(&records[0].to_json().unwrap())
+        );
+
+        let markdown = markdown_table(&[&records[0]]);
+        assert!(markdown.ends_with("| 10 | 33 | 22.39 KB | 18.01 KB |\n"));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut history = parse_divan(OUTPUT, "old");
+        let mut new = parse_divan(OUTPUT, "new");
+        new[0].median *= 1.2;
+        new[1].median *= 0.5;
+        new.remove(4);
+        history.extend(new);
+
+        let deltas = compare(&history, "old", "new");
+        let regressions: Vec<&str> = deltas
+            .iter()
+            .filter(|delta| delta.is_regression(5.0))
+            .map(|delta| delta.name.as_str())
+            .collect();
+        assert_eq!(vec!["part1"], regressions);
+        assert_eq!(None, deltas[4].after);
+
+        let report = report(&deltas, 5.0);
+        assert!(report.contains("+20.0%  REGRESSION"));
+        assert!(report.contains("-50.0%\n"));
+        assert!(report.contains("gone\n"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use crate::normalise;
+
+pub use fastrand::Rng;
+
+// a synthetic puzzle input, with the answers when they're known from the way it was built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated<A, B = A> {
+    pub input: String,
+    pub part1: Option<A>,
+    pub part2: Option<B>,
+}
+
+// real inputs can't be committed, so benches use one when it's there and
+// fall back to a generated input on a fresh clone
+pub fn bench_input(crate_dir: &str, generate: impl FnOnce() -> String) -> String {
+    match std::fs::read_to_string(Path::new(crate_dir).join("input.txt")) {
+        Ok(input) if !input.trim().is_empty() => normalise(&input).into_owned(),
+        _ => generate(),
+    }
+}
+
+// grows a random region of roughly `cells` cells from the middle of a width x height
+// grid. it stays simply connected and never touches itself at a corner, so its
+// outline is a simple closed loop
+pub fn region(rng: &mut Rng, width: usize, height: usize, cells: usize) -> Vec<Vec<bool>> {
+    let mut grid = vec![vec![false; width]; height];
+    let mut members = vec![(height / 2, width / 2)];
+    grid[height / 2][width / 2] = true;
+
+    let cells = cells.min(width * height * 3 / 4);
+    let mut attempts = 0;
+    while members.len() < cells && attempts < cells * 200 {
+        attempts += 1;
+        let (row, col) = members[rng.usize(..members.len())];
+        let (row, col) = match rng.u8(..4) {
+            0 if row > 0 => (row - 1, col),
+            1 if row + 1 < height => (row + 1, col),
+            2 if col > 0 => (row, col - 1),
+            3 if col + 1 < width => (row, col + 1),
+            _ => continue,
+        };
+        if !grid[row][col] && is_simple(&grid, row, col) {
+            grid[row][col] = true;
+            members.push((row, col));
+        }
+    }
+
+    grid
+}
+
+// the cell can join the region without making a hole or a pinch if the neighbours
+// already in the region form one unbroken arc around it
+fn is_simple(grid: &[Vec<bool>], row: usize, col: usize) -> bool {
+    const AROUND: [(isize, isize); 8] = [
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+    ];
+    let inside = AROUND.map(|(dr, dc)| {
+        let (row, col) = (row as isize + dr, col as isize + dc);
+        row >= 0
+            && col >= 0
+            && grid
+                .get(row as usize)
+                .and_then(|line| line.get(col as usize))
+                .copied()
+                .unwrap_or(false)
+    });
+
+    let arcs = (0..8)
+        .filter(|&i| inside[i] && !inside[(i + 7) % 8])
+        .count();
+    let touches = [1, 3, 5, 7].iter().any(|&i| inside[i]);
+    arcs == 1 && touches
+}
+
+// the corners of the region's outline, clockwise from its top left corner.
+// corner (row, col) is the top left of cell (row, col)
+pub fn outline(region: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let inside = |row: isize, col: isize| {
+        row >= 0
+            && col >= 0
+            && region
+                .get(row as usize)
+                .and_then(|line| line.get(col as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+    let start = region
+        .iter()
+        .enumerate()
+        .find_map(|(row, line)| line.iter().position(|&cell| cell).map(|col| (row, col)))
+        .expect("The region isn't empty");
+
+    // east, south, west, north
+    const STEPS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+    let mut corners = vec![start];
+    let (mut row, mut col) = (start.0 as isize, start.1 as isize);
+    let mut dir = 0;
+    loop {
+        row += STEPS[dir].0;
+        col += STEPS[dir].1;
+        if (row as usize, col as usize) == start {
+            break;
+        }
+
+        // the cells either side of the edge ahead, the region is kept on the right
+        let (ahead_left, ahead_right) = match dir {
+            0 => ((row - 1, col), (row, col)),
+            1 => ((row, col), (row, col - 1)),
+            2 => ((row, col - 1), (row - 1, col - 1)),
+            _ => ((row - 1, col - 1), (row - 1, col)),
+        };
+        let turn = if !inside(ahead_right.0, ahead_right.1) {
+            1
+        } else if inside(ahead_left.0, ahead_left.1) {
+            3
+        } else {
+            0
+        };
+        if turn != 0 {
+            dir = (dir + turn) % 4;
+            corners.push((row as usize, col as usize));
+        }
+    }
+
+    corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline() {
+        let region = vec![
+            vec![true, true, false],
+            vec![true, true, true],
+            vec![false, true, false],
+        ];
+        assert_eq!(
+            vec![
+                (0, 0),
+                (0, 2),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (2, 2),
+                (3, 2),
+                (3, 1),
+                (2, 1),
+                (2, 0)
+            ],
+            outline(&region)
+        );
+    }
+
+    #[test]
+    fn test_region() {
+        let mut rng = Rng::with_seed(7);
+        let region = region(&mut rng, 20, 20, 150);
+        let cells = region.iter().flatten().filter(|&&cell| cell).count();
+        assert_eq!(150, cells);
+
+        // a simple loop around the region visits each corner once
+        let corners = outline(&region);
+        let mut sorted = corners.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(corners.len(), sorted.len());
+    }
+}
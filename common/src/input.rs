@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::ParseError;
+
+const BOM: char = '\u{feff}';
+
+// puts the input into the form the parsers expect: no byte order mark, unix line
+// endings, no trailing whitespace on any line and exactly one newline at the end.
+// clean input is passed straight through without copying
+pub fn normalise(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    if is_normalised(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    for line in input.trim_end().lines() {
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    Cow::Owned(output)
+}
+
+// the crate's input.txt, normalised, for its bins. it's read when they run rather than
+// built in, so the crate and its benches still build on a fresh clone without one. a bin
+// can't do anything without it, so this says where it looked and exits
+pub fn read_input(crate_dir: &str) -> String {
+    let path = Path::new(crate_dir).join("input.txt");
+    match std::fs::read_to_string(&path) {
+        Ok(input) => normalise(&input).into_owned(),
+        Err(err) => {
+            eprintln!("couldn't read the puzzle input {}: {}", path.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn is_normalised(input: &str) -> bool {
+    if input.is_empty() {
+        return true;
+    }
+    input.ends_with('\n')
+        && !input.ends_with("\n\n")
+        && !input.contains('\r')
+        && input.lines().all(|line| line == line.trim_end())
+}
+
+// splits the input into sections separated by one or more blank lines. each block is
+// a slice of the input, without its final line ending, so errors can still be located
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        loop {
+            let (line, after) = next_line(rest)?;
+            if !line.trim().is_empty() {
+                break;
+            }
+            rest = after;
+        }
+
+        let start = rest;
+        let mut len = 0;
+        while let Some((line, after)) = next_line(rest) {
+            if line.trim().is_empty() {
+                break;
+            }
+            len = offset(start, line) + line.trim_end_matches('\r').len();
+            rest = after;
+        }
+        Some(&start[..len])
+    })
+}
+
+// for inputs made of exactly two blocks, like a header above a list
+pub fn two_blocks(input: &str) -> Result<(&str, &str), ParseError> {
+    let mut sections = blocks(input);
+    let expected = "two sections separated by a blank line";
+    let first = sections
+        .next()
+        .ok_or_else(|| ParseError::expected(input, expected))?;
+    let second = sections
+        .next()
+        .ok_or_else(|| ParseError::expected(first, expected))?;
+    match sections.next() {
+        Some(extra) => Err(ParseError::expected(extra, "the end of the input")),
+        None => Ok((first, second)),
+    }
+}
+
+fn next_line(input: &str) -> Option<(&str, &str)> {
+    match input.find('\n') {
+        Some(end) => Some((&input[..end], &input[end + 1..])),
+        None if input.is_empty() => None,
+        None => Some((input, "")),
+    }
+}
+
+fn offset(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert!(matches!(normalise("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert!(matches!(normalise("\u{feff}a\n"), Cow::Borrowed("a\n")));
+        assert_eq!("a\n\nb\n", normalise("\u{feff}a \r\n\r\nb\t\r\n\r\n\n"));
+        assert_eq!("a\nb\n", normalise("a\nb"));
+        assert_eq!("", normalise(" \n\n"));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "\na\nb\n\n\nc\r\n \r\nd";
+        assert_eq!(vec!["a\nb", "c", "d"], blocks(input).collect::<Vec<_>>());
+        assert_eq!(0, blocks("\n\n").count());
+    }
+
+    #[test]
+    fn test_two_blocks() {
+        assert_eq!(Ok(("a\nb", "c")), two_blocks("a\nb\r\n\r\nc\n"));
+
+        let input = "a\n\nb\n\nc";
+        let error = two_blocks(input).unwrap_err().locate(input);
+        assert_eq!(
+            "line 5, col 1: expected the end of the input, found 'c'",
+            error.to_string()
+        );
+        let error = two_blocks("a\nb").unwrap_err();
+        assert_eq!(
+            "expected two sections separated by a blank line, found 'a'",
+            error.message
+        );
+    }
+}
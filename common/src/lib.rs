@@ -0,0 +1,12 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod register;
+pub mod runner;
+
+pub use answers::answer;
+pub use input::{blocks, normalise, read_input, two_blocks};
+pub use parse::ParseError;
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::str::FromStr;
+
+const MAX_FOUND_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // both 1-based, 0 until the error has been located in the input
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub message: String,
+    // start of the offending text, used to work out the line and column later
+    address: usize,
+}
+
+impl ParseError {
+    // `found` has to be a slice of the puzzle input for `locate` to work
+    pub fn new(found: &str, message: impl Into<String>) -> ParseError {
+        let first_line = found.lines().next().unwrap_or("");
+        let found_text = match first_line.char_indices().nth(MAX_FOUND_LEN) {
+            Some((end, _)) => format!("{}...", &first_line[..end]),
+            None => first_line.to_string(),
+        };
+        ParseError {
+            line: 0,
+            column: 0,
+            found: found_text,
+            message: message.into(),
+            address: found.as_ptr() as usize,
+        }
+    }
+
+    pub fn expected(found: &str, expected: &str) -> ParseError {
+        let error = ParseError::new(found, "");
+        let message = format!("expected {}, found '{}'", expected, error.found);
+        ParseError { message, ..error }
+    }
+
+    // finds the line and column from where `found` sits in memory against `input`, so it
+    // only can when `found` was borrowed from this same `input`. anything else, say text
+    // from another string or a copy, can't be told apart from an error that's outside the
+    // input, and is left without a line and column rather than given a wrong one
+    pub fn locate(self, input: &str) -> ParseError {
+        let start = input.as_ptr() as usize;
+        if self.line != 0 || self.address < start || self.address > start + input.len() {
+            return self;
+        }
+
+        let before = &input[..self.address - start];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError {
+            line,
+            column,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            0 => write!(f, "{}", self.message),
+            _ => write!(
+                f,
+                "line {}, col {}: {}",
+                self.line, self.column, self.message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse::<T>()
+        .map_err(|_| ParseError::expected(s, "a number"))
+}
+
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::expected(s, &format!("'{}'", delimiter.escape_debug())))
+}
+
+// a non-empty rectangle where every cell is one of `cells`, returns the width
+pub fn grid(input: &str, cells: &str, expected: &str) -> Result<usize, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 {
+        return Err(ParseError::new(input, "the grid is empty"));
+    }
+    for line in input.lines() {
+        if let Some(col) = line.find(|c| !cells.contains(c)) {
+            return Err(ParseError::expected(&line[col..], expected));
+        }
+        if line.len() != width {
+            let expected = format!("a row of width {}", width);
+            return Err(ParseError::expected(line, &expected));
+        }
+    }
+    Ok(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "px{a<2006:qkq,m>2090:A,rfg}\nin{s=1351:px,qqz}";
+        let line = input.lines().nth(1).expect("Two lines");
+        let error = ParseError::new(&line[4..5], "unknown comparison '='").locate(input);
+        assert_eq!(2, error.line);
+        assert_eq!(5, error.column);
+        assert_eq!("line 2, col 5: unknown comparison '='", error.to_string());
+    }
+
+    #[test]
+    fn test_unlocated() {
+        let error = ParseError::expected("abc", "a number").locate("something else");
+        assert_eq!(0, error.line);
+        assert_eq!("expected a number, found 'abc'", error.to_string());
+    }
+
+    #[test]
+    fn test_helpers() {
+        let input = "seeds: 79 14\n\nx";
+        assert_eq!(Ok(79), number::<usize>(&input[7..9]));
+
+        let error = number::<usize>(&input[14..]).unwrap_err().locate(input);
+        assert_eq!(
+            "line 3, col 1: expected a number, found 'x'",
+            error.to_string()
+        );
+
+        let error = split_once(input, "@").unwrap_err().locate(input);
+        assert_eq!(
+            "line 1, col 1: expected '@', found 'seeds: 79 14'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(Ok(3), grid("..#\n#..", ".#", "'.' or '#'"));
+
+        let input = "..#\n#.x";
+        let error = grid(input, ".#", "'.' or '#'").unwrap_err().locate(input);
+        assert_eq!(
+            "line 2, col 3: expected '.' or '#', found 'x'",
+            error.to_string()
+        );
+
+        let input = "..#\n#.";
+        let error = grid(input, ".#", "'.' or '#'").unwrap_err().locate(input);
+        assert_eq!(
+            "line 2, col 1: expected a row of width 3, found '#.'",
+            error.to_string()
+        );
+        assert_eq!(
+            "the grid is empty",
+            grid("", ".#", "").unwrap_err().to_string()
+        );
+    }
+}
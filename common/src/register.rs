@@ -0,0 +1,240 @@
+use crate::answers;
+use std::path::Path;
+
+// the edits that hook a day generated from a year's template into that year's runner.
+// each returns Ok(None) when the day is already there
+
+// "day-05" -> 5
+pub fn day_number(day: &str) -> Option<u32> {
+    day.strip_prefix("day-")?.parse().ok()
+}
+
+// a runner entry is written with {{day}} for the padded number and {{number}} for the
+// bare one, so `// day {{number}}` becomes `// day 5` and `day_{{day}}` becomes `day_05`
+pub fn fill(entry: &str, number: u32) -> String {
+    entry
+        .replace("{{day}}", &format!("{number:02}"))
+        .replace("{{number}}", &number.to_string())
+}
+
+// adds `day-05 = { path = "../day-05" }` in order among the other days, or uncomments it
+pub fn add_dependency(manifest: &str, number: u32) -> Result<Option<String>, String> {
+    let dependency = format!("day-{number:02} = {{ path = \"../day-{number:02}\" }}");
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    if lines.iter().any(|line| line.trim() == dependency) {
+        return Ok(None);
+    }
+
+    match lines
+        .iter()
+        .position(|line| line.trim_start_matches('#').trim() == dependency)
+    {
+        Some(commented) => lines[commented] = &dependency,
+        None => {
+            // commented out days still count for the order
+            let days: Vec<(usize, u32)> = lines
+                .iter()
+                .enumerate()
+                .filter_map(|(at, line)| {
+                    let (name, _) = line.trim_start_matches('#').split_once(" = ")?;
+                    Some((at, day_number(name.trim())?))
+                })
+                .collect();
+            let at = match days.iter().find(|(_, day)| *day > number) {
+                Some(&(at, _)) => at,
+                None => match days.last() {
+                    Some(&(at, _)) => at + 1,
+                    None => dependencies_end(&lines)?,
+                },
+            };
+            lines.insert(at, &dependency);
+        }
+    }
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+fn dependencies_end(lines: &[&str]) -> Result<usize, String> {
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or("the manifest has no [dependencies] table")?;
+    let len = lines[start + 1..]
+        .iter()
+        .take_while(|line| !line.trim().is_empty() && !line.starts_with('['))
+        .count();
+    Ok(start + 1 + len)
+}
+
+// adds a filled in entry to the runner's main, in order among the `// day N` blocks
+pub fn add_to_runner(runner: &str, number: u32, entry: &str) -> Result<Option<String>, String> {
+    let used = format!("day_{number:02}::");
+    if runner
+        .lines()
+        .any(|line| line.contains(&used) && !line.trim_start().starts_with("//"))
+    {
+        return Ok(None);
+    }
+
+    let mut lines: Vec<&str> = runner.lines().collect();
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(at, line)| Some((at, line.trim().strip_prefix("// day ")?.parse().ok()?)))
+        .collect();
+    let entry = fill(entry, number);
+    let entry: Vec<&str> = entry.trim_end().lines().collect();
+
+    match days.iter().find(|(_, day)| *day > number) {
+        Some(&(at, _)) => {
+            lines.splice(at..at, entry.into_iter().chain([""]));
+        }
+        None => {
+            // each block ends with an empty println, there can be blank lines before it
+            let &(last, _) = days.last().ok_or("the runner has no `// day N` blocks")?;
+            let end = lines[last..]
+                .iter()
+                .position(|line| line.trim() == "println!();")
+                .map(|len| last + len + 1)
+                .ok_or("the last day's block never ends")?;
+            lines.splice(end..end, [""].into_iter().chain(entry));
+        }
+    }
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+// adds a day made by `cargo generate` to the year's all-days runner and answers.txt, with
+// the runner's entry taken from template/runner-entry.rs. returns what was changed
+pub fn register(year: &Path, day: &str) -> Result<Vec<String>, String> {
+    let number =
+        day_number(day).ok_or_else(|| format!("expected a day like day-05, found '{day}'"))?;
+    if !year.join(day).join("Cargo.toml").exists() {
+        return Err(format!("there's no {day} in {}", year.display()));
+    }
+    let entry = read(&year.join("template/runner-entry.rs"))?;
+    let mut notes = Vec::new();
+
+    let manifest = year.join("all-days/Cargo.toml");
+    update(&manifest, &mut notes, |text| add_dependency(text, number))?;
+    let runner = year.join("all-days/src/bin/all-days.rs");
+    update(&runner, &mut notes, |text| {
+        add_to_runner(text, number, &entry)
+    })?;
+
+    let store = year.join("answers.txt");
+    let keys = [format!("{day} part1"), format!("{day} part2")];
+    let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+    let text = match store.exists() {
+        true => read(&store)?,
+        false => String::new(),
+    };
+    let added = answers::add_slots(&text, &keys);
+    if added != text {
+        write(&store, &added)?;
+        notes.push(format!("added answer slots to {}", store.display()));
+    }
+    Ok(notes)
+}
+
+fn update(
+    path: &Path,
+    notes: &mut Vec<String>,
+    edit: impl FnOnce(&str) -> Result<Option<String>, String>,
+) -> Result<(), String> {
+    match edit(&read(path)?).map_err(|err| format!("{}: {err}", path.display()))? {
+        Some(text) => {
+            write(path, &text)?;
+            notes.push(format!("updated {}", path.display()));
+        }
+        None => notes.push(format!("{} already has it", path.display())),
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("couldn't read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|err| format!("couldn't write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = "    // day {{number}}
+    run(day_{{day}}::part1::process);
+    println!();
+";
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest = "[dependencies]
+tracing = { workspace = true }
+day-01 = { path = \"../day-01\" }
+day-03 = { path = \"../day-03\" }
+# day-04 = { path = \"../day-04\" }
+
+[lib]
+bench = false
+";
+        let added = add_dependency(manifest, 2).unwrap().unwrap();
+        assert_eq!(
+            manifest.replace("day-03 = ", "day-02 = { path = \"../day-02\" }\nday-03 = "),
+            added
+        );
+        let added = add_dependency(manifest, 4).unwrap().unwrap();
+        assert_eq!(manifest.replace("# day-04", "day-04"), added);
+        let added = add_dependency(manifest, 12).unwrap().unwrap();
+        assert_eq!(
+            manifest.replace("\n\n[lib]", "\nday-12 = { path = \"../day-12\" }\n\n[lib]"),
+            added
+        );
+        assert_eq!(Ok(None), add_dependency(manifest, 3));
+
+        let added = add_dependency("[dependencies]\ntracing = \"0.1\"\n", 1).unwrap();
+        assert_eq!(
+            Some("[dependencies]\ntracing = \"0.1\"\nday-01 = { path = \"../day-01\" }\n"),
+            added.as_deref()
+        );
+    }
+
+    #[test]
+    fn test_add_to_runner() {
+        let runner = "fn main() {
+    // day 1
+    run(day_01::part1::process);
+    println!();
+
+    // day 4
+    run(day_04::part1::process);
+
+    run(day_04::part2::process);
+    println!();
+
+    //// day 5
+    //run(day_05::part1::process);
+
+    total();
+}
+";
+        let added = add_to_runner(runner, 2, ENTRY).unwrap().unwrap();
+        assert_eq!(
+            runner.replace(
+                "    // day 4",
+                "    // day 2\n    run(day_02::part1::process);\n    println!();\n\n    // day 4"
+            ),
+            added
+        );
+        let added = add_to_runner(runner, 5, ENTRY).unwrap().unwrap();
+        assert_eq!(
+            runner.replace(
+                "    println!();\n\n    //// day 5",
+                "    println!();\n\n    // day 5\n    run(day_05::part1::process);\n    println!();\n\n    //// day 5"
+            ),
+            added
+        );
+        assert_eq!(Ok(None), add_to_runner(runner, 4, ENTRY));
+        assert!(add_to_runner("fn main() {}\n", 1, ENTRY).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use crate::alloc::{self, AllocStats};
+use std::fmt;
+use std::io::{Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Ok(String),
+    // the part returned an error, like a parse error
+    Failed(String),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Run {
+    pub outcome: Outcome,
+    pub duration: Duration,
+    pub allocs: AllocStats,
+}
+
+// a runner starts itself again with this and a part's label to run just that part
+pub const PART_FLAG: &str = "--part";
+
+// the line the child writes its run on, after anything the part printed
+const RUN_LINE: &str = "part-run";
+
+// the label, when this process was started to run one part for `isolated`
+pub fn part_label(args: &[String]) -> Option<&str> {
+    match args {
+        [flag, label] if flag == PART_FLAG => Some(label),
+        _ => None,
+    }
+}
+
+// runs a part in a child process, this same binary started with `PART_FLAG` and the label,
+// which has to find the part and hand it to `serve`. a panic, or a crash like a stack
+// overflow, is reported with the rest carrying on, and a part still going after `timeout`
+// is killed, so it doesn't hold on to threads or skew the timings and allocation counts
+// of the parts after it
+pub fn isolated(label: &str, timeout: Duration) -> Run {
+    match std::env::current_exe() {
+        Ok(exe) => {
+            let mut command = Command::new(exe);
+            command.args([PART_FLAG, label]);
+            wait_for(command, timeout)
+        }
+        Err(err) => failed(format!("couldn't find the runner to start the part: {err}")),
+    }
+}
+
+// in the child, runs the part and writes how it went for `isolated`, then exits
+pub fn serve<F>(process: F, input: &str) -> !
+where
+    F: FnOnce(&str) -> Result<String, String>,
+{
+    let run = run_here(process, input);
+    let mut stdout = std::io::stdout().lock();
+    let written = writeln!(stdout, "{}", encode(&run)).and_then(|_| stdout.flush());
+    std::process::exit(if written.is_ok() { 0 } else { 1 })
+}
+
+// runs a part in this process, still catching a panic, but with nothing to stop it running
+// on. for when it can't be in a child, like when its spans are being traced
+pub fn run_here<F>(process: F, input: &str) -> Run
+where
+    F: FnOnce(&str) -> Result<String, String>,
+{
+    let now = Instant::now();
+    let (result, allocs) =
+        alloc::measure(|| panic::catch_unwind(AssertUnwindSafe(|| process(input))));
+    let duration = now.elapsed();
+    let outcome = match result {
+        Ok(Ok(answer)) => Outcome::Ok(answer),
+        Ok(Err(err)) => Outcome::Failed(err),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    };
+    Run {
+        outcome,
+        duration,
+        allocs,
+    }
+}
+
+fn wait_for(mut command: Command, timeout: Duration) -> Run {
+    let started = Instant::now();
+    let spawned = command.stdin(Stdio::null()).stdout(Stdio::piped()).spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => return failed(format!("couldn't start the part: {err}")),
+    };
+
+    // read on another thread so a part that prints a lot can't fill the pipe and stall
+    let mut stdout = child.stdout.take().expect("Piped stdout");
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        let _ = sender.send(output);
+    });
+
+    let Ok(output) = receiver.recv_timeout(timeout) else {
+        // it's waited on so it doesn't linger as a zombie
+        let _ = child.kill();
+        let _ = child.wait();
+        return Run {
+            outcome: Outcome::TimedOut(timeout),
+            duration: started.elapsed(),
+            allocs: AllocStats::default(),
+        };
+    };
+    let status = child.wait();
+    match output.lines().rev().find_map(decode) {
+        Some(run) => run,
+        None => match status {
+            Ok(status) => failed(format!("the part's process ended with {status}")),
+            Err(err) => failed(format!("the part's process was lost: {err}")),
+        },
+    }
+}
+
+fn failed(message: String) -> Run {
+    Run {
+        outcome: Outcome::Failed(message),
+        duration: Duration::ZERO,
+        allocs: AllocStats::default(),
+    }
+}
+
+// the run on one line, tab separated with the answer or message last
+fn encode(run: &Run) -> String {
+    let (kind, text) = match &run.outcome {
+        Outcome::Ok(answer) => ("ok", answer.as_str()),
+        Outcome::Failed(err) => ("failed", err.as_str()),
+        Outcome::Panicked(message) => ("panicked", message.as_str()),
+        Outcome::TimedOut(_) => ("timed-out", ""),
+    };
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    format!(
+        "{RUN_LINE}\t{}\t{}\t{}\t{}\t{kind}\t{escaped}",
+        run.duration.as_nanos(),
+        run.allocs.allocations,
+        run.allocs.bytes,
+        run.allocs.peak
+    )
+}
+
+fn decode(line: &str) -> Option<Run> {
+    let mut fields = line.splitn(7, '\t');
+    if fields.next()? != RUN_LINE {
+        return None;
+    }
+    let mut number = || fields.next()?.parse::<u64>().ok();
+    let duration = Duration::from_nanos(number()?);
+    let allocs = AllocStats {
+        allocations: number()?,
+        bytes: number()?,
+        peak: number()?,
+    };
+    let kind = fields.next()?;
+    let mut text = String::new();
+    let mut chars = fields.next()?.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                other => other,
+            }),
+            c => text.push(c),
+        }
+    }
+    let outcome = match kind {
+        "ok" => Outcome::Ok(text),
+        "failed" => Outcome::Failed(text),
+        "panicked" => Outcome::Panicked(text),
+        _ => return None,
+    };
+    Some(Run {
+        outcome,
+        duration,
+        allocs,
+    })
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("no message")
+    }
+}
+
+// a number of seconds, like 30 or 2.5
+pub fn parse_timeout(text: &str) -> Result<Duration, String> {
+    text.parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("expected a number of seconds for the timeout, found '{text}'"))
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Ok(answer) => write!(f, "{answer}"),
+            Outcome::Failed(err) => write!(f, "{err}"),
+            Outcome::Panicked(message) => write!(f, "panicked: {message}"),
+            Outcome::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_run_here() {
+        let run = run_here(|input| Ok(input.len().to_string()), "abc");
+        assert_eq!(Outcome::Ok(String::from("3")), run.outcome);
+
+        let run = run_here(|_| Err(String::from("bad input")), "");
+        assert_eq!(Outcome::Failed(String::from("bad input")), run.outcome);
+
+        let run = run_here(|_| panic!("day {} isn't done", 22), "");
+        assert_eq!(
+            Outcome::Panicked(String::from("day 22 isn't done")),
+            run.outcome
+        );
+        let run = run_here(|_| todo!(), "");
+        assert_eq!(
+            Outcome::Panicked(String::from("not yet implemented")),
+            run.outcome
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let run = Run {
+            outcome: Outcome::Failed(String::from("line 2, col 1:\tbad\\input\nhere")),
+            duration: Duration::from_nanos(1234),
+            allocs: AllocStats {
+                allocations: 5,
+                bytes: 600,
+                peak: 70,
+            },
+        };
+        let line = encode(&run);
+        assert!(!line.contains('\n'));
+        assert_eq!(Some(run), decode(&line));
+        assert_eq!(None, decode("42"));
+    }
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_child() {
+        // whatever the part printed comes before its run
+        let line = encode(&run_here(|_| Ok(String::from("42")), ""));
+        let run = wait_for(
+            shell(&format!("echo noise; printf '%s\\n' '{line}'")),
+            TIMEOUT,
+        );
+        assert_eq!(Outcome::Ok(String::from("42")), run.outcome);
+
+        let run = wait_for(shell("echo noise; exit 3"), TIMEOUT);
+        assert_eq!(
+            Outcome::Failed(String::from("the part's process ended with exit status: 3")),
+            run.outcome
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        // the child is killed rather than waited for
+        let timeout = Duration::from_millis(50);
+        let run = wait_for(shell("sleep 5"), timeout);
+        assert_eq!(Outcome::TimedOut(timeout), run.outcome);
+        assert!(run.duration < Duration::from_secs(5));
+
+        assert_eq!(Ok(Duration::from_millis(2500)), parse_timeout("2.5"));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
+
+    #[test]
+    fn test_part_label() {
+        let args = [String::from(PART_FLAG), String::from("Day 05 Part1")];
+        assert_eq!(Some("Day 05 Part1"), part_label(&args));
+        assert_eq!(None, part_label(&args[1..]));
+    }
+}
@@ -0,0 +1,192 @@
+use aoc_common::answer;
+use std::path::{Path, PathBuf};
+
+// a year is a workspace named after it, its days are the `day-NN` crates inside
+pub struct Year {
+    pub name: String,
+    pub dir: PathBuf,
+    answers: String,
+}
+
+pub struct Day {
+    pub name: String,
+    pub dir: PathBuf,
+    pub parts: Vec<Part>,
+}
+
+// a part's solution is the module `part2`, its variants are modules like `part2_dumb`,
+// and each has a binary of the same name
+#[derive(Debug, PartialEq, Eq)]
+pub struct Part {
+    pub part: u8,
+    pub variant: Option<String>,
+}
+
+impl Part {
+    pub fn bin(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("part{}_{variant}", self.part),
+            None => format!("part{}", self.part),
+        }
+    }
+
+    fn parse(module: &str) -> Option<Part> {
+        let rest = module.strip_prefix("part")?;
+        let (part, variant) = match rest.split_once('_') {
+            Some((part, variant)) => (part, Some(variant.to_string())),
+            None => (rest, None),
+        };
+        Some(Part {
+            part: part.parse().ok()?,
+            variant,
+        })
+    }
+}
+
+impl Year {
+    pub fn all(root: &Path) -> Result<Vec<Year>, String> {
+        let mut years = Vec::new();
+        for name in entries(root)? {
+            if name.len() == 4 && name.bytes().all(|b| b.is_ascii_digit()) {
+                years.push(Year::open(root, &name)?);
+            }
+        }
+        Ok(years)
+    }
+
+    pub fn open(root: &Path, name: &str) -> Result<Year, String> {
+        let dir = root.join(name);
+        if !dir.join("Cargo.toml").exists() {
+            return Err(format!("there's no {name} workspace in {}", root.display()));
+        }
+        let answers = std::fs::read_to_string(dir.join("answers.txt")).unwrap_or_default();
+        Ok(Year {
+            name: name.to_string(),
+            dir,
+            answers,
+        })
+    }
+
+    pub fn days(&self) -> Result<Vec<Day>, String> {
+        let mut days = Vec::new();
+        for name in entries(&self.dir)? {
+            if day_name(&name).as_deref() == Some(name.as_str()) {
+                days.push(self.day(&name)?);
+            }
+        }
+        Ok(days)
+    }
+
+    pub fn day(&self, name: &str) -> Result<Day, String> {
+        let dir = self.dir.join(name);
+        if !dir.join("Cargo.toml").exists() {
+            return Err(format!("there's no {name} in {}", self.name));
+        }
+        let mut parts: Vec<Part> = entries(&dir.join("src"))?
+            .iter()
+            .filter_map(|file| Part::parse(file.strip_suffix(".rs")?))
+            .collect();
+        parts.sort_by(|a, b| (a.part, &a.variant).cmp(&(b.part, &b.variant)));
+        Ok(Day {
+            name: name.to_string(),
+            dir,
+            parts,
+        })
+    }
+
+    pub fn answer(&self, day: &Day, part: u8) -> Option<&str> {
+        answer(&self.answers, &format!("{} part{part}", day.name))
+    }
+}
+
+impl Day {
+    pub fn has_input(&self) -> bool {
+        std::fs::metadata(self.dir.join("input.txt")).is_ok_and(|file| file.len() > 0)
+    }
+
+    // the divan benches in benches/benchmark.rs, which are named after the binaries
+    pub fn benches(&self) -> Vec<String> {
+        let source = std::fs::read_to_string(self.dir.join("benches/benchmark.rs"));
+        bench_names(&source.unwrap_or_default())
+    }
+}
+
+// the functions marked #[divan::bench], other attributes can come in between
+fn bench_names(source: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut marked = false;
+    for line in source.lines().map(str::trim) {
+        if line.starts_with("#[divan::bench") {
+            marked = true;
+        } else if let Some(function) = line.strip_prefix("fn ").filter(|_| marked) {
+            let name = function.split(['(', '<']).next().unwrap_or(function);
+            names.push(name.trim().to_string());
+            marked = false;
+        }
+    }
+    names
+}
+
+// "5", "05", "day5" and "day-05" are all "day-05"
+pub fn day_name(arg: &str) -> Option<String> {
+    let number = arg.strip_prefix("day").unwrap_or(arg);
+    let number = number.strip_prefix('-').unwrap_or(number);
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("day-{:02}", number.parse::<u32>().ok()?))
+}
+
+fn entries(dir: &Path) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map_err(|err| format!("couldn't read {}: {err}", dir.display()))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_name() {
+        for arg in ["5", "05", "day5", "day-05", "day-5"] {
+            assert_eq!(Some("day-05"), day_name(arg).as_deref());
+        }
+        assert_eq!(Some("day-25"), day_name("25").as_deref());
+        assert_eq!(None, day_name("day-"));
+        assert_eq!(None, day_name("template"));
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(
+            Some(Part {
+                part: 2,
+                variant: Some(String::from("no_map"))
+            }),
+            Part::parse("part2_no_map")
+        );
+        assert_eq!("part1", Part::parse("part1").unwrap().bin());
+        assert_eq!("part2_no_map", Part::parse("part2_no_map").unwrap().bin());
+        assert_eq!(None, Part::parse("pascal"));
+        assert_eq!(None, Part::parse("parts"));
+    }
+
+    #[test]
+    fn test_bench_names() {
+        let source = "fn input() -> String {
+    String::new()
+}
+
+#[divan::bench]
+fn part1(bencher: divan::Bencher) {}
+
+#[divan::bench(args = [1, 2])]
+#[ignore]
+fn part2_dumb(n: usize) {}";
+        assert_eq!(vec!["part1", "part2_dumb"], bench_names(source));
+    }
+}
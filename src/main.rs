@@ -0,0 +1,355 @@
+mod layout;
+
+use aoc_common::register::register;
+use layout::{day_name, Day, Part, Year};
+use std::path::Path;
+use std::process::{Command, ExitCode, Stdio};
+
+const USAGE: &str = "usage: aoc <command>
+  list [<year>]             every day, part and variant, and whether it has an input and an answer
+  run <year> [<day> [<part>]] [--variant <name>] [-- <runner args>]
+                            a year goes through its all-days runner, a day through its part binaries
+  bench <year> [<day> [<part>]] [--variant <name>] [--alloc]
+                            divan benchmarks, kept in bench-history.jsonl when the year records them
+  check [<year> [<day>]]    runs every part and variant that has an input and an answer in
+                            answers.txt, and compares the two
+  new <year> <day>          a day from the year's template, added to its runner and answers.txt";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let result = match args.first().map(String::as_str) {
+        Some("list") => list(root, &args[1..]),
+        Some("run") => run(root, &mut args),
+        Some("bench") => bench(root, &mut args),
+        Some("check") => check(root, &args[1..]),
+        Some("new") => new(root, &args[1..]),
+        _ => Err(String::from(USAGE)),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list(root: &Path, args: &[String]) -> Result<bool, String> {
+    let years = match args {
+        [] => Year::all(root)?,
+        [year] => vec![Year::open(root, year)?],
+        _ => return Err(String::from(USAGE)),
+    };
+    let yes = |found: bool| if found { "yes" } else { "no" };
+    println!(
+        "{:<6}{:<8}{:<7}{:<18}{:<7}answer",
+        "year", "day", "part", "variant", "input"
+    );
+    for year in &years {
+        for day in year.days()? {
+            let input = day.has_input();
+            for part in &day.parts {
+                println!(
+                    "{:<6}{:<8}{:<7}{:<18}{:<7}{}",
+                    year.name,
+                    day.name,
+                    format!("part{}", part.part),
+                    part.variant.as_deref().unwrap_or("-"),
+                    yes(input),
+                    yes(year.answer(&day, part.part).is_some())
+                );
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn run(root: &Path, args: &mut Vec<String>) -> Result<bool, String> {
+    // everything after `--` goes to the year's runner, like --trace
+    let passthrough = match args.iter().position(|arg| arg == "--") {
+        Some(at) => args.split_off(at).split_off(1),
+        None => Vec::new(),
+    };
+    let variant = take_option(args, "--variant");
+    let selection = select(root, &args[1..])?;
+    let year = &selection.year;
+
+    let Some(day) = &selection.day else {
+        if variant.is_some() {
+            return Err(String::from("--variant needs a day"));
+        }
+        let mut command = cargo(
+            &year.dir,
+            &["run", "-q", "--release", "-p", "all-days", "--"],
+        );
+        return status(command.args(&passthrough));
+    };
+    let mut ok = true;
+    for part in chosen(day, selection.part, variant.as_deref())? {
+        println!("{} {} {}:", year.name, day.name, part.bin());
+        let bin = part.bin();
+        ok &= status(&mut cargo(
+            &year.dir,
+            &["run", "-q", "--release", "-p", &day.name, "--bin", &bin],
+        ))?;
+    }
+    Ok(ok)
+}
+
+fn bench(root: &Path, args: &mut Vec<String>) -> Result<bool, String> {
+    let variant = take_option(args, "--variant");
+    let alloc = take_flag(args, "--alloc");
+    let selection = select(root, &args[1..])?;
+    let year = &selection.year;
+
+    let mut command = vec!["bench", "-q"];
+    let mut filters = Vec::new();
+    match &selection.day {
+        Some(day) => {
+            command.extend(["-p", &day.name]);
+            // divan matches exactly on the whole path, and each bench is named after its binary
+            if selection.part.is_some() || variant.is_some() {
+                let bench = format!("{}_bench", day.name.replace('-', "_"));
+                let benches = day.benches();
+                for part in chosen(day, selection.part, variant.as_deref())? {
+                    // otherwise divan runs nothing and still succeeds
+                    if !benches.contains(&part.bin()) {
+                        return Err(format!("{} has no bench for {}", day.name, part.bin()));
+                    }
+                    filters.push(format!("{bench}::{}", part.bin()));
+                }
+            }
+        }
+        None if variant.is_some() => return Err(String::from("--variant needs a day")),
+        None => {}
+    }
+    if alloc {
+        command.extend(["--features", "count-alloc"]);
+    }
+    if !filters.is_empty() {
+        command.extend(["--", "--exact"]);
+        command.extend(filters.iter().map(String::as_str));
+    }
+    let whole_year = selection.day.is_none();
+
+    // years with the bench-history tool keep every run, and benchmarks.txt is made from it
+    if !year.dir.join("all-days/src/bin/bench-history.rs").exists() {
+        if !whole_year {
+            return status(&mut cargo(&year.dir, &command));
+        }
+        let output = cargo(&year.dir, &command)
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(cargo_error)?;
+        let table = String::from_utf8_lossy(&output.stdout);
+        print!("{table}");
+        write(&year.dir.join("benchmarks.txt"), &table)?;
+        return Ok(output.status.success());
+    }
+
+    let history = [
+        "run",
+        "-q",
+        "-p",
+        "all-days",
+        "--bin",
+        "bench-history",
+        "--",
+    ];
+    let mut benches = cargo(&year.dir, &command)
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(cargo_error)?;
+    let output = benches.stdout.take().expect("Piped stdout");
+    let recorded = status(
+        cargo(&year.dir, &history)
+            .arg("record")
+            .stdin(Stdio::from(output)),
+    )?;
+    let benched = benches.wait().map_err(cargo_error)?.success();
+    if whole_year && benched && recorded {
+        let output = cargo(&year.dir, &history)
+            .arg("table")
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(cargo_error)?;
+        write(
+            &year.dir.join("benchmarks.txt"),
+            &String::from_utf8_lossy(&output.stdout),
+        )?;
+    }
+    Ok(benched && recorded)
+}
+
+fn check(root: &Path, args: &[String]) -> Result<bool, String> {
+    let years = match args {
+        [] => Year::all(root)?,
+        [year] | [year, _] => vec![Year::open(root, year)?],
+        _ => return Err(String::from(USAGE)),
+    };
+    let (mut passed, mut skipped) = (0, 0);
+    let mut failures = Vec::new();
+    for year in &years {
+        let days = match args.get(1) {
+            Some(day) => vec![year.day(&parse_day(day)?)?],
+            None => year.days()?,
+        };
+        for day in days {
+            for part in &day.parts {
+                let label = format!("{} {} {}", year.name, day.name, part.bin());
+                let Some(expected) = year.answer(&day, part.part).filter(|_| day.has_input())
+                else {
+                    skipped += 1;
+                    continue;
+                };
+                let bin = part.bin();
+                let output = cargo(
+                    &year.dir,
+                    &["run", "-q", "--release", "-p", &day.name, "--bin", &bin],
+                )
+                .stderr(Stdio::inherit())
+                .output()
+                .map_err(cargo_error)?;
+                // some binaries print more than the answer after it
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let found = stdout.lines().next().unwrap_or("").trim();
+                let failure = match (output.status.success(), found == expected) {
+                    (true, true) => None,
+                    (true, false) => Some(format!("expected {expected} but found {found}")),
+                    (false, _) => Some(format!("failed with {}", output.status)),
+                };
+                match failure {
+                    None => {
+                        println!("{label}: ok");
+                        passed += 1;
+                    }
+                    Some(failure) => {
+                        println!("{label}: {failure}");
+                        failures.push(format!("{label}: {failure}"));
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{passed} passed, {} failed, {skipped} without an input or an answer",
+        failures.len()
+    );
+    failures.iter().for_each(|failure| println!("{failure}"));
+    Ok(failures.is_empty())
+}
+
+fn new(root: &Path, args: &[String]) -> Result<bool, String> {
+    let [year, day] = args else {
+        return Err(String::from(USAGE));
+    };
+    let year = Year::open(root, year)?;
+    let day = parse_day(day)?;
+    if year.dir.join(&day).exists() {
+        return Err(format!("{} already has {day}", year.name));
+    }
+    let generated = status(&mut cargo(
+        &year.dir,
+        &["generate", "--path", "./template", "--name", &day],
+    ))?;
+    if !generated {
+        eprintln!("cargo generate failed, it's installed with `cargo install cargo-generate`");
+        return Ok(false);
+    }
+    for note in register(&year.dir, &day)? {
+        println!("{note}");
+    }
+    Ok(true)
+}
+
+// the year, day and part given on the command line, the day and part can be left off
+struct Selection {
+    year: Year,
+    day: Option<Day>,
+    part: Option<u8>,
+}
+
+fn select(root: &Path, args: &[String]) -> Result<Selection, String> {
+    let (year, day, part) = match args {
+        [year] => (year, None, None),
+        [year, day] => (year, Some(day), None),
+        [year, day, part] => (year, Some(day), Some(part)),
+        _ => return Err(String::from(USAGE)),
+    };
+    let year = Year::open(root, year)?;
+    let day = match day {
+        Some(day) => Some(year.day(&parse_day(day)?)?),
+        None => None,
+    };
+    let part = match part {
+        Some(part) => Some(
+            part.strip_prefix("part")
+                .unwrap_or(part)
+                .parse()
+                .map_err(|_| format!("expected a part like 1 or part2, found '{part}'"))?,
+        ),
+        None => None,
+    };
+    Ok(Selection { year, day, part })
+}
+
+// the parts asked for, the main solution of each unless there's a variant
+fn chosen<'a>(
+    day: &'a Day,
+    part: Option<u8>,
+    variant: Option<&str>,
+) -> Result<Vec<&'a Part>, String> {
+    let parts: Vec<&Part> = day
+        .parts
+        .iter()
+        .filter(|found| part.is_none_or(|part| found.part == part))
+        .filter(|found| found.variant.as_deref() == variant)
+        .collect();
+    if parts.is_empty() {
+        let wanted = Part {
+            part: part.unwrap_or(1),
+            variant: variant.map(String::from),
+        };
+        return Err(format!("{} has no {}", day.name, wanted.bin()));
+    }
+    Ok(parts)
+}
+
+fn parse_day(day: &str) -> Result<String, String> {
+    day_name(day).ok_or_else(|| format!("expected a day like 5 or day-05, found '{day}'"))
+}
+
+fn cargo(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("cargo");
+    command.current_dir(dir).args(args);
+    command
+}
+
+fn status(command: &mut Command) -> Result<bool, String> {
+    Ok(command.status().map_err(cargo_error)?.success())
+}
+
+fn cargo_error(err: std::io::Error) -> String {
+    format!("couldn't run cargo: {err}")
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|err| format!("couldn't write {}: {err}", path.display()))
+}
+
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let at = args.iter().position(|arg| arg == name)?;
+    let value = args.get(at + 1).cloned();
+    args.drain(at..(at + 2).min(args.len()));
+    value
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let found = args.iter().any(|arg| arg == name);
+    args.retain(|arg| arg != name);
+    found
+}